@@ -1,23 +1,36 @@
-use std::collections::HashSet;
+use std::cell::Cell;
+use std::collections::{HashSet, VecDeque};
+use std::ffi::OsString;
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Instant, SystemTime};
 
 use notify::{Event as FsEvent, RecommendedWatcher, RecursiveMode, Watcher};
 
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 use crate::config::profiles::{AuthMethod, ConfigError, Profile, ProfileStore};
+use crate::config::settings::{Column, ColumnWidths, PanelPosition, ProfileSort, Settings};
+use crate::config::state::SessionState;
+use crate::connection::cache::ListingCache;
 use crate::connection::sftp::{
-    add_to_known_hosts, count_files, download_batch, download_file_to_dir, upload_batch,
-    upload_file_fresh, SftpConnection, SftpError,
+    add_to_known_hosts, bare_host, copy_batch, count_files, delete_batch, describe_connection, download_batch, download_file_to_dir, glob_match,
+    count_remote_paths, exec_remote, host_port, remote_mtime_fresh, search_remote, upload_batch, upload_file_fresh, FreeSpaceSlot, ListingSlot, SearchHandle,
+    ExecSlot, SearchProgress, SftpConnection, SftpError, TransferOptions,
 };
+use crate::grep::{run_grep, GrepHandle, GrepProgress};
+use crate::input::TextInput;
+use crate::paths::expand_local_path;
+use crate::perms::format_permissions;
 use crate::transfer::queue::{
-    ProgressHandle, TransferHandle, TransferProgress, TransferState, UploadProgress, UploadState,
+    ProgressHandle, QueueProgress, QueuedJob, TransferHandle, TransferJob, TransferProgress, TransferState, UploadProgress, UploadState,
 };
-use crate::ui::theme::{ensure_themes, load_theme_choice, ThemeChoice};
+use crate::ui::panels::format_size;
+use crate::ui::theme::{ensure_themes, ThemeChoice};
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -48,12 +61,134 @@ impl ActivePanel {
 /// A single entry in a file panel (local or remote)
 #[derive(Debug, Clone)]
 pub struct FileEntry {
+    /// Display name; names that are not valid UTF-8 are rendered lossily.
     pub name: String,
+    /// The exact name bytes. Operations join paths with this, never `name`.
+    pub raw_name: OsString,
     pub size: Option<u64>,
     pub modified: Option<SystemTime>,
     pub is_dir: bool,
-    /// Unix permission string like "rwxr-xr-x" — only set for remote entries
+    /// Unix permission string like "rwxr-xr-x" (of the link target for symlinks)
     pub permissions: Option<String>,
+    /// The entry is a symlink; `is_dir` / `size` describe its target.
+    pub is_symlink: bool,
+    /// Where the symlink points, as stored in the link (may be relative).
+    pub link_target: Option<String>,
+}
+
+impl FileEntry {
+    /// A file with any execute bit set, shown like `ls -F` with a `*`.
+    pub fn is_executable(&self) -> bool {
+        !self.is_dir
+            && self
+                .permissions
+                .as_deref()
+                .is_some_and(|p| p.contains('x'))
+    }
+}
+
+/// How an entry relates to the entry of the same name in the other panel
+/// (compare mode, '=').
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareStatus {
+    /// No entry of that name in the other panel.
+    OnlyHere,
+    /// Modified later than the other panel's file.
+    Newer,
+    /// Modified earlier than the other panel's file.
+    Older,
+    /// Same modification time but a different size (or file vs. directory).
+    Differs,
+    /// Same size and modification time.
+    Identical,
+}
+
+impl CompareStatus {
+    /// One-character marker shown in the mark column of unmarked entries.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::OnlyHere => "+",
+            Self::Newer => ">",
+            Self::Older => "<",
+            Self::Differs => "≠",
+            Self::Identical => "=",
+        }
+    }
+}
+
+/// Compare status of every entry in `here` against `other`, by name. Files
+/// are compared by modification time (whole seconds, as SFTP reports them)
+/// and size; directories present on both sides and ".." get no status.
+fn compare_entries(here: &[FileEntry], other: &[FileEntry]) -> Vec<Option<CompareStatus>> {
+    let by_name: std::collections::HashMap<&str, &FileEntry> =
+        other.iter().map(|e| (e.name.as_str(), e)).collect();
+    let secs = |e: &FileEntry| {
+        e.modified
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+    };
+    here.iter()
+        .map(|e| {
+            if e.name == ".." {
+                return None;
+            }
+            let o = match by_name.get(e.name.as_str()) {
+                Some(o) => o,
+                None => return Some(CompareStatus::OnlyHere),
+            };
+            if e.is_dir && o.is_dir {
+                return None;
+            }
+            if e.is_dir != o.is_dir {
+                return Some(CompareStatus::Differs);
+            }
+            Some(match (secs(e), secs(o)) {
+                (Some(a), Some(b)) if a > b => CompareStatus::Newer,
+                (Some(a), Some(b)) if a < b => CompareStatus::Older,
+                _ if e.size == o.size => CompareStatus::Identical,
+                _ => CompareStatus::Differs,
+            })
+        })
+        .collect()
+}
+
+/// What a one-way sync local → remote would do in the current directories.
+#[derive(Debug, Default)]
+pub struct SyncPlan {
+    /// Local entries to upload, with the reason (`OnlyHere`, `Newer` or `Differs`).
+    pub upload: Vec<(FileEntry, CompareStatus)>,
+    /// Remote entries (name, is_dir) missing locally — deleted only on request.
+    pub extra: Vec<(String, bool)>,
+}
+
+/// Plan a one-way sync of the `local` listing onto `remote`: files missing
+/// on the server, newer locally or of a different size are uploaded, new
+/// local directories are uploaded whole. Identical and older files are
+/// skipped, and directories present on both sides are not descended into.
+pub fn plan_sync(local: &[FileEntry], remote: &[FileEntry]) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+    let local_status = compare_entries(local, remote);
+    for (entry, status) in local.iter().zip(local_status) {
+        match status {
+            Some(status @ (CompareStatus::OnlyHere | CompareStatus::Newer)) => {
+                plan.upload.push((entry.clone(), status));
+            }
+            // A file/directory clash can't be fixed by an upload.
+            Some(CompareStatus::Differs) if !entry.is_dir
+                && remote.iter().any(|r| r.name == entry.name && !r.is_dir) =>
+            {
+                plan.upload.push((entry.clone(), CompareStatus::Differs));
+            }
+            _ => {}
+        }
+    }
+    let remote_status = compare_entries(remote, local);
+    for (entry, status) in remote.iter().zip(remote_status) {
+        if status == Some(CompareStatus::OnlyHere) {
+            plan.extra.push((entry.name.clone(), entry.is_dir && !entry.is_symlink));
+        }
+    }
+    plan
 }
 
 /// State of a single file panel
@@ -61,58 +196,323 @@ pub struct FileEntry {
 pub struct PanelState {
     pub path: PathBuf,
     pub entries: Vec<FileEntry>,
+    /// Cursor position within `visible_indices` — not an index into `entries`.
+    /// Use `selected_index()` / `selected_entry()` to resolve the real entry.
     pub selected: usize,
-    /// Indices of entries that have been marked with Space.
-    pub marked: HashSet<usize>,
+    /// Indices into `entries` that are currently shown, in display order.
+    /// Rebuilt by `rebuild_visible()` whenever `entries` or `show_hidden` changes.
+    visible_indices: Vec<usize>,
+    /// Show entries whose name starts with '.' (".." is always shown).
+    show_hidden: bool,
+    /// Names of the entries that have been marked with Space. Keyed by name,
+    /// not position, so reloads and reordering cannot move a mark onto
+    /// another file.
+    pub marked: HashSet<String>,
+    /// Free bytes on the filesystem holding `path` (None = unknown).
+    pub free_space: Option<u64>,
+    /// Lower-cased prefix typed for the type-ahead jump.
+    type_ahead: String,
+    /// Time of the last type-ahead key; the buffer expires after `TYPE_AHEAD_TIMEOUT`.
+    type_ahead_at: Option<Instant>,
+    /// Compare status per entry (same indices as `entries`); empty while
+    /// compare mode is off. Filled by `App::update_comparison`.
+    pub compare: Vec<Option<CompareStatus>>,
+    /// First visible row of the list, kept by `render_panel` between frames
+    /// so the selection moves freely until it nears an edge.
+    pub scroll_offset: Cell<usize>,
+    /// Number of list rows in the last render, the page size for PgUp/PgDn.
+    pub view_height: Cell<usize>,
+    /// Directory of the loaded listing; a load of another directory records
+    /// this one in `back`.
+    visited: Option<PathBuf>,
+    /// Directories left by navigation, most recent last (Alt+Left).
+    back: Vec<PathBuf>,
+    /// Directories left by going back, most recent last (Alt+Right).
+    forward: Vec<PathBuf>,
 }
 
+/// Entries kept in a panel's back history.
+const HISTORY_LIMIT: usize = 100;
+
+/// Maximum gap between two clicks on the same row to count as a double-click.
+const DOUBLE_CLICK_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(400);
+
+/// Pause after which the next type-ahead key starts a new prefix.
+const TYPE_AHEAD_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(800);
+
 impl PanelState {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
             entries: Vec::new(),
             selected: 0,
+            visible_indices: Vec::new(),
+            show_hidden: true,
             marked: HashSet::new(),
+            free_space: None,
+            type_ahead: String::new(),
+            type_ahead_at: None,
+            compare: Vec::new(),
+            scroll_offset: Cell::new(0),
+            view_height: Cell::new(0),
+            visited: None,
+            back: Vec::new(),
+            forward: Vec::new(),
         }
     }
 
-    /// Toggle the mark on the currently highlighted entry (Space key).
-    /// The ".." entry cannot be marked.
-    pub fn toggle_mark(&mut self) {
-        let entry = match self.entries.get(self.selected) {
-            Some(e) if e.name != ".." => e,
-            _ => return,
+    /// Note that the panel now shows `path`. Coming from another directory
+    /// pushes that one onto the back history and clears the forward one.
+    fn record_visit(&mut self, path: &std::path::Path) {
+        let Some(previous) = self.visited.replace(path.to_path_buf()) else {
+            return;
         };
-        // Entry is valid — toggle its index in the set
-        let _ = entry; // satisfy borrow checker; index is self.selected
-        if self.marked.contains(&self.selected) {
-            self.marked.remove(&self.selected);
+        if previous != path {
+            self.back.push(previous);
+            if self.back.len() > HISTORY_LIMIT {
+                self.back.remove(0);
+            }
+            self.forward.clear();
+        }
+    }
+
+    /// Directory one step back (or forward) in the history, if any.
+    pub fn history_target(&self, forward: bool) -> Option<&PathBuf> {
+        if forward {
+            self.forward.last()
         } else {
-            self.marked.insert(self.selected);
+            self.back.last()
         }
     }
 
-    /// Mark all non-".." entries. If all are already marked, unmark all (toggle).
-    pub fn mark_all(&mut self) {
-        let eligible: Vec<usize> = self
+    /// Move one step back (or forward) in the history: the target becomes
+    /// the visited directory and the current one goes onto the other stack.
+    /// Done right before loading the target, so that load records nothing.
+    fn step_history(&mut self, forward: bool) {
+        let (from, to) = if forward {
+            (&mut self.forward, &mut self.back)
+        } else {
+            (&mut self.back, &mut self.forward)
+        };
+        if let Some(target) = from.pop() {
+            if let Some(current) = self.visited.replace(target) {
+                to.push(current);
+            }
+        }
+    }
+
+    /// Rebuild the visible index list after `entries` changed and keep the
+    /// cursor inside it. Marks are dropped on entries that no longer exist or
+    /// are hidden, so an operation never reaches a file the user cannot see,
+    /// and ".." never stays marked whether or not the listing has it.
+    fn rebuild_visible(&mut self) {
+        let show_hidden = self.show_hidden;
+        self.visible_indices = self
             .entries
             .iter()
             .enumerate()
-            .filter(|(_, e)| e.name != "..")
+            .filter(|(_, e)| show_hidden || e.name == ".." || !e.name.starts_with('.'))
             .map(|(i, _)| i)
             .collect();
+        self.selected = self.selected.min(self.visible_indices.len().saturating_sub(1));
+        let names: HashSet<&str> = self
+            .visible_indices
+            .iter()
+            .map(|&i| self.entries[i].name.as_str())
+            .collect();
+        self.marked.retain(|name| name != ".." && names.contains(name.as_str()));
+    }
+
+    /// Show or hide dotfiles. The cursor stays on the highlighted entry if it
+    /// remains visible, otherwise on the same row.
+    pub fn set_show_hidden(&mut self, show: bool) {
+        if self.show_hidden == show {
+            return;
+        }
+        let (name, pos) = self.cursor_snapshot();
+        self.show_hidden = show;
+        self.rebuild_visible();
+        self.reselect_by_name(name.as_deref(), pos);
+    }
+
+    /// Number of entries left out of the listing by the dotfile filter.
+    pub fn hidden_count(&self) -> usize {
+        self.entries.len() - self.visible_indices.len()
+    }
+
+    /// Whether `entry` is marked.
+    pub fn is_marked(&self, entry: &FileEntry) -> bool {
+        self.marked.contains(&entry.name)
+    }
+
+    /// Number of shown entries.
+    pub fn visible_len(&self) -> usize {
+        self.visible_indices.len()
+    }
+
+    /// Move the cursor to the entry called `name`, if it is shown.
+    pub fn select_name(&mut self, name: &str) {
+        let hit = self.visible_entries().position(|(_, e)| e.name == name);
+        if let Some(pos) = hit {
+            self.selected = pos;
+        }
+    }
+
+    /// Put the cursor back on `name` after a reload. When that entry is gone,
+    /// fall back to visible position `fallback` (clamped to the list).
+    pub fn reselect_by_name(&mut self, name: Option<&str>, fallback: usize) {
+        let hit = name.and_then(|n| self.visible_entries().position(|(_, e)| e.name == n));
+        self.select(hit.unwrap_or(fallback));
+    }
+
+    /// Name of the highlighted entry and the cursor position, taken before a
+    /// reload and handed to `reselect_by_name` afterwards.
+    pub fn cursor_snapshot(&self) -> (Option<String>, usize) {
+        (self.selected_entry().map(|e| e.name.clone()), self.selected)
+    }
+
+    /// Move the cursor to visible position `pos` (clamped to the list).
+    pub fn select(&mut self, pos: usize) {
+        self.selected = pos.min(self.visible_indices.len().saturating_sub(1));
+    }
+
+    /// Shown entries together with their index into `entries`.
+    pub fn visible_entries(&self) -> impl Iterator<Item = (usize, &FileEntry)> {
+        self.visible_indices
+            .iter()
+            .filter_map(move |&i| self.entries.get(i).map(|e| (i, e)))
+    }
+
+    /// Index into `entries` of the highlighted row.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.visible_indices.get(self.selected).copied()
+    }
+
+    /// The highlighted entry.
+    pub fn selected_entry(&self) -> Option<&FileEntry> {
+        self.selected_index().and_then(|i| self.entries.get(i))
+    }
+
+    /// Exact name bytes of the entry displayed as `name`, for operations on
+    /// names kept by dialogs and marks. Falls back to `name` itself when no
+    /// entry shows it (e.g. a name the user typed).
+    pub fn raw_name(&self, name: &str) -> OsString {
+        self.entries
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| e.raw_name.clone())
+            .unwrap_or_else(|| name.into())
+    }
+
+    /// Entries an operation applies to: the visible marked entries in display
+    /// order, or the highlighted entry when nothing is marked. Never contains "..".
+    pub fn target_entries(&self) -> Vec<&FileEntry> {
+        if self.marked.is_empty() {
+            return self.selected_entry().filter(|e| e.name != "..").into_iter().collect();
+        }
+        self.visible_entries()
+            .filter(|(_, e)| self.is_marked(e) && e.name != "..")
+            .map(|(_, e)| e)
+            .collect()
+    }
+
+    /// Append `c` to the type-ahead prefix (starting over after the timeout)
+    /// and move the cursor to the first visible entry whose name starts with
+    /// it, ignoring case. Returns the current prefix.
+    pub fn type_ahead(&mut self, c: char) -> &str {
+        let expired = self
+            .type_ahead_at
+            .is_none_or(|t| t.elapsed() > TYPE_AHEAD_TIMEOUT);
+        if expired {
+            self.type_ahead.clear();
+        }
+        self.type_ahead.extend(c.to_lowercase());
+        self.type_ahead_at = Some(Instant::now());
+        let prefix = &self.type_ahead;
+        let hit = self
+            .visible_entries()
+            .position(|(_, e)| e.name != ".." && e.name.to_lowercase().starts_with(prefix));
+        if let Some(pos) = hit {
+            self.selected = pos;
+        }
+        &self.type_ahead
+    }
+
+    /// Forget the type-ahead prefix (any non-type-ahead key).
+    pub fn reset_type_ahead(&mut self) {
+        self.type_ahead.clear();
+        self.type_ahead_at = None;
+    }
+
+    /// Toggle the mark on the currently highlighted entry (Space key).
+    /// The ".." entry cannot be marked.
+    pub fn toggle_mark(&mut self) {
+        let name = match self.selected_entry() {
+            Some(e) if e.name != ".." => e.name.clone(),
+            _ => return,
+        };
+        if !self.marked.remove(&name) {
+            self.marked.insert(name);
+        }
+    }
+
+    /// Names of all visible non-".." entries.
+    fn markable_names(&self) -> Vec<String> {
+        self.visible_entries()
+            .filter(|(_, e)| e.name != "..")
+            .map(|(_, e)| e.name.clone())
+            .collect()
+    }
+
+    /// Mark all visible non-".." entries. If all are already marked, unmark all (toggle).
+    pub fn mark_all(&mut self) {
+        let eligible = self.markable_names();
 
-        if eligible.iter().all(|i| self.marked.contains(i)) {
+        if eligible.iter().all(|n| self.marked.contains(n)) {
             // All marked → clear all
             self.marked.clear();
         } else {
             // Some or none marked → mark all eligible
-            for i in eligible {
-                self.marked.insert(i);
+            self.marked.extend(eligible);
+        }
+    }
+
+    /// Flip the mark of every visible non-".." entry.
+    pub fn invert_marks(&mut self) {
+        for name in self.markable_names() {
+            if !self.marked.remove(&name) {
+                self.marked.insert(name);
             }
         }
     }
 
+    /// Mark (or with `mark == false` unmark) every visible entry whose name
+    /// matches the glob `pattern`. Without `*`/`?` the name must match exactly.
+    /// ".." is never matched. Returns the number of entries whose mark changed.
+    pub fn mark_by_pattern(&mut self, pattern: &str, mark: bool) -> usize {
+        let hits: Vec<String> = self
+            .visible_entries()
+            .filter(|(_, e)| e.name != ".." && glob_match(pattern, &e.name))
+            .map(|(_, e)| e.name.clone())
+            .collect();
+        hits.into_iter()
+            .filter(|name| if mark { self.marked.insert(name.clone()) } else { self.marked.remove(name) })
+            .count()
+    }
+
+    /// Mark every visible entry modified before `cutoff` (`older`) or at or
+    /// after it. Entries without a modification time, like "..", are never
+    /// marked. Returns the number of newly marked entries.
+    pub fn mark_by_age(&mut self, cutoff: SystemTime, older: bool) -> usize {
+        let hits: Vec<String> = self
+            .visible_entries()
+            .filter(|(_, e)| e.name != "..")
+            .filter(|(_, e)| e.modified.is_some_and(|t| if older { t < cutoff } else { t >= cutoff }))
+            .map(|(_, e)| e.name.clone())
+            .collect();
+        hits.into_iter().filter(|name| self.marked.insert(name.clone())).count()
+    }
+
     /// Clear all marks (called when the directory is reloaded).
     pub fn clear_marks(&mut self) {
         self.marked.clear();
@@ -124,29 +524,44 @@ impl PanelState {
         if self.path.parent().is_some() {
             self.entries.push(FileEntry {
                 name: "..".to_string(),
+                raw_name: OsString::from(".."),
                 size: None,
                 modified: None,
                 is_dir: true,
                 permissions: None,
+                is_symlink: false,
+                link_target: None,
             });
         }
         let read_dir = std::fs::read_dir(&self.path)?;
         let mut entries: Vec<FileEntry> = read_dir
             .filter_map(|e| e.ok())
             .map(|e| {
-                let meta = e.metadata().ok();
+                // Follow links for type/size so a symlinked directory can be entered.
+                let is_symlink = e.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+                let meta = std::fs::metadata(e.path()).ok();
+                let raw_name = e.file_name();
                 FileEntry {
-                    name: e.file_name().to_string_lossy().to_string(),
+                    name: raw_name.to_string_lossy().to_string(),
+                    raw_name,
                     size: meta.as_ref().filter(|m| m.is_file()).map(|m| m.len()),
                     modified: meta.as_ref().and_then(|m| m.modified().ok()),
-                    is_dir: meta.map(|m| m.is_dir()).unwrap_or(false),
-                    permissions: None,
+                    is_dir: meta.as_ref().is_some_and(|m| m.is_dir()),
+                    permissions: meta.map(|m| format_permissions(m.permissions().mode())),
+                    is_symlink,
+                    link_target: is_symlink
+                        .then(|| std::fs::read_link(e.path()).ok())
+                        .flatten()
+                        .map(|t| t.to_string_lossy().to_string()),
                 }
             })
             .collect();
         entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
         self.entries.extend(entries);
-        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        self.rebuild_visible();
+        self.free_space = local_free_space(&self.path);
+        let path = self.path.clone();
+        self.record_visit(&path);
         Ok(())
     }
 
@@ -157,19 +572,42 @@ impl PanelState {
     }
 
     pub fn move_down(&mut self) {
-        if self.selected + 1 < self.entries.len() {
+        if self.selected + 1 < self.visible_indices.len() {
             self.selected += 1;
         }
     }
 
+    /// Rows one page moves: the last rendered height, less one row so the
+    /// previous page's edge stays visible.
+    fn page_size(&self) -> usize {
+        self.view_height.get().saturating_sub(1).max(1)
+    }
+
+    pub fn page_up(&mut self) {
+        self.selected = self.selected.saturating_sub(self.page_size());
+    }
+
+    pub fn page_down(&mut self) {
+        let last = self.visible_indices.len().saturating_sub(1);
+        self.selected = (self.selected + self.page_size()).min(last);
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.visible_indices.len().saturating_sub(1);
+    }
+
     /// Used for local panel navigation only.
     pub fn enter_selected(&mut self) -> Result<(), AppError> {
-        if let Some(entry) = self.entries.get(self.selected) {
+        if let Some(entry) = self.selected_entry() {
             if entry.is_dir {
                 let new_path = if entry.name == ".." {
                     self.path.parent().unwrap_or(&self.path).to_path_buf()
                 } else {
-                    self.path.join(&entry.name)
+                    self.path.join(&entry.raw_name)
                 };
                 self.path = new_path;
                 self.selected = 0;
@@ -191,20 +629,20 @@ impl PanelState {
 
     /// Load remote entries directly into this panel state.
     pub fn load_remote(&mut self, path: PathBuf, entries: Vec<FileEntry>) {
+        self.record_visit(&path);
         self.path = path;
         self.entries = entries;
         self.selected = 0;
         self.marked.clear();
+        self.rebuild_visible();
     }
 
     /// Refresh remote entries in-place, preserving scroll position and valid marks.
     /// Use `load_remote()` when navigating to a new path (position reset is correct there).
     pub fn refresh_remote(&mut self, path: PathBuf, entries: Vec<FileEntry>) {
-        let new_len = entries.len();
         self.path = path;
         self.entries = entries;
-        self.selected = self.selected.min(new_len.saturating_sub(1));
-        self.marked.retain(|&i| i < new_len);
+        self.rebuild_visible();
     }
 }
 
@@ -237,6 +675,8 @@ pub struct NewProfileForm {
     pub save_password: bool,
     /// Password text entered for keychain storage (never persisted to TOML).
     pub password: String,
+    /// Focus the remote panel after connecting.
+    pub focus_remote: bool,
 }
 
 impl NewProfileForm {
@@ -252,12 +692,30 @@ impl NewProfileForm {
             local_start_path: String::new(),
             save_password: false,
             password: String::new(),
+            focus_remote: false,
+        }
+    }
+
+    /// Pre-fill the form for editing an existing profile.
+    pub fn from_profile(p: &Profile) -> Self {
+        Self {
+            name:             p.name.clone(),
+            host:             p.host.clone(),
+            port:             p.port.to_string(),
+            user:             p.user.clone(),
+            auth:             p.auth.clone(),
+            key_path:         p.key_path.clone().unwrap_or_else(|| "~/.ssh/id_rsa".to_string()),
+            remote_path:      p.remote_path.clone().unwrap_or_default(),
+            local_start_path: p.local_start_path.clone().unwrap_or_default(),
+            save_password:    p.has_saved_password,
+            password:         String::new(),
+            focus_remote:     p.focus_remote,
         }
     }
 
     /// Return a mutable reference to the string field at `field` index.
-    /// Fields 4 (Auth toggle) and 8 (save_password toggle) have no
-    /// string backing — returns None.
+    /// Fields 4 (Auth toggle), 8 (save_password toggle) and 10 (focus
+    /// toggle) have no string backing — returns None.
     pub fn active_field_mut(&mut self, field: usize) -> Option<&mut String> {
         match field {
             0 => Some(&mut self.name),
@@ -301,6 +759,12 @@ impl NewProfileForm {
             // Placeholder — callers (save_new_profile / save_edited_profile)
             // override this based on actual keychain result.
             has_saved_password: self.save_password,
+            last_used: None,
+            verify_uploads: false,
+            jump_host: None,
+            jump_user: None,
+            jump_port: None,
+            focus_remote: self.focus_remote,
         })
     }
 }
@@ -308,20 +772,127 @@ impl NewProfileForm {
 pub struct ProfileDialog {
     pub mode: ProfileDialogMode,
     pub store: ProfileStore,
+    /// Row in the displayed (sorted) list — map with `selected_index()`.
     pub list_selected: usize,
     pub form: NewProfileForm,
     pub active_profile: Option<usize>,
+    pub sort: ProfileSort,
+    /// Case-insensitive name/host filter of the list ('/').
+    pub filter: String,
+    /// True while keystrokes go into `filter` instead of being commands.
+    pub filtering: bool,
+    /// Store indices in display order (only profiles matching `filter`).
+    order: Vec<usize>,
 }
 
 impl ProfileDialog {
-    pub fn new(store: ProfileStore) -> Self {
-        Self {
+    pub fn new(store: ProfileStore, sort: ProfileSort) -> Self {
+        let mut dialog = Self {
             mode: ProfileDialogMode::List,
             store,
             list_selected: 0,
             form: NewProfileForm::new(),
             active_profile: None,
+            sort,
+            filter: String::new(),
+            filtering: false,
+            order: Vec::new(),
+        };
+        dialog.resort();
+        dialog
+    }
+
+    /// Store indices in the order the list is displayed.
+    pub fn display_order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Store index of the highlighted row.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.order.get(self.list_selected).copied()
+    }
+
+    /// Rebuild the display order; call after the store or filter was modified.
+    pub fn resort(&mut self) {
+        let profiles = &self.store.profiles;
+        let needle = self.filter.to_lowercase();
+        let mut order: Vec<usize> = (0..profiles.len())
+            .filter(|&i| {
+                profiles[i].name.to_lowercase().contains(&needle)
+                    || profiles[i].host.to_lowercase().contains(&needle)
+            })
+            .collect();
+        match self.sort {
+            ProfileSort::Insertion => {}
+            ProfileSort::Name => order.sort_by_key(|&i| profiles[i].name.to_lowercase()),
+            ProfileSort::Host => order.sort_by_key(|&i| profiles[i].host.to_lowercase()),
+            ProfileSort::LastUsed => {
+                order.sort_by_key(|&i| std::cmp::Reverse(profiles[i].last_used));
+            }
+        }
+        self.order = order;
+        let max = self.order.len().saturating_sub(1);
+        self.list_selected = self.list_selected.min(max);
+    }
+
+    /// Switch to the next sort order and keep the highlighted profile.
+    pub fn cycle_sort(&mut self) {
+        let current = self.selected_index();
+        self.sort = self.sort.next();
+        self.resort();
+        if let Some(pos) = current.and_then(|c| self.order.iter().position(|&i| i == c)) {
+            self.list_selected = pos;
+        }
+    }
+
+    /// Append a copy of the highlighted profile named "<name> (Kopie)" (or
+    /// "(Kopie 2)", … when taken), highlight it and return its store index.
+    /// The copy starts without keychain password and usage history.
+    pub fn duplicate_selected(&mut self) -> Option<usize> {
+        let original = self.store.profiles.get(self.selected_index()?)?;
+        let taken = |name: &str| self.store.profiles.iter().any(|p| p.name == name);
+        let mut name = format!("{} (Kopie)", original.name);
+        let mut n = 2;
+        while taken(&name) {
+            name = format!("{} (Kopie {})", original.name, n);
+            n += 1;
+        }
+        let mut copy = original.clone();
+        copy.name = name;
+        copy.has_saved_password = false;
+        copy.last_used = None;
+
+        self.store.add(copy);
+        let index = self.store.profiles.len() - 1;
+        self.resort();
+        if let Some(pos) = self.order.iter().position(|&i| i == index) {
+            self.list_selected = pos;
+        }
+        Some(index)
+    }
+
+    /// Swap the highlighted profile with its neighbour in `profiles.toml`
+    /// order and keep it highlighted. Returns false at either end of the list.
+    pub fn move_selected(&mut self, up: bool) -> bool {
+        let Some(idx) = self.selected_index() else {
+            return false;
+        };
+        let other = if up {
+            match idx.checked_sub(1) {
+                Some(i) => i,
+                None => return false,
+            }
+        } else if idx + 1 < self.store.profiles.len() {
+            idx + 1
+        } else {
+            return false;
+        };
+        self.store.profiles.swap(idx, other);
+        self.resort();
+        if let Some(pos) = self.order.iter().position(|&i| i == other) {
+            self.list_selected = pos;
         }
+        true
     }
 
     pub fn list_move_up(&mut self) {
@@ -330,8 +901,33 @@ impl ProfileDialog {
         }
     }
 
+    /// Append to the filter and jump to the first match.
+    pub fn filter_push(&mut self, c: char) {
+        self.filter.push(c);
+        self.list_selected = 0;
+        self.resort();
+    }
+
+    /// Remove the last filter character.
+    pub fn filter_pop(&mut self) {
+        self.filter.pop();
+        self.list_selected = 0;
+        self.resort();
+    }
+
+    /// Drop the filter, keeping the highlighted profile selected.
+    pub fn clear_filter(&mut self) {
+        let current = self.selected_index();
+        self.filter.clear();
+        self.filtering = false;
+        self.resort();
+        if let Some(pos) = current.and_then(|c| self.order.iter().position(|&i| i == c)) {
+            self.list_selected = pos;
+        }
+    }
+
     pub fn list_move_down(&mut self) {
-        let max = self.store.profiles.len().saturating_sub(1);
+        let max = self.order.len().saturating_sub(1);
         if self.list_selected < max {
             self.list_selected += 1;
         }
@@ -352,6 +948,10 @@ pub struct PasswordDialog {
     /// Current password input (masked in UI)
     pub input: String,
     pub error: Option<String>,
+    /// Only run a connection test with the password (profile dialog 'T').
+    pub test_only: bool,
+    /// Store the password in the keychain once the connect succeeds (Tab).
+    pub remember: bool,
 }
 
 impl PasswordDialog {
@@ -360,19 +960,172 @@ impl PasswordDialog {
             profile,
             input: String::new(),
             error: None,
+            test_only: false,
+            remember: false,
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Connection test ('T' in the profile dialog)
+// ---------------------------------------------------------------------------
+
+/// Frames of the status bar spinner shown while a connection test runs.
+const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// A connection attempt running on a background thread. The result slot is
+/// filled once with Ok or the error message.
+pub struct ConnectionTest {
+    pub profile_name: String,
+    started: Instant,
+    result: Arc<Mutex<Option<Result<(), String>>>>,
+}
+
+impl ConnectionTest {
+    /// Spinner glyph for the current moment, advancing every 100 ms.
+    pub fn spinner(&self) -> char {
+        SPINNER[(self.started.elapsed().as_millis() / 100) as usize % SPINNER.len()]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Background connect
+// ---------------------------------------------------------------------------
+
+/// Result slot of a connect running on a worker thread.
+type ConnectSlot = Arc<Mutex<Option<Result<SftpConnection, SftpError>>>>;
+
+/// A panel connection being established on a background thread.
+///
+/// Threading: ssh2 marks `Session` and `Sftp` as `Send` (every libssh2 call
+/// goes through the session's internal mutex), so the worker builds the whole
+/// `SftpConnection` — TCP connect, handshake, host key check, auth, SFTP
+/// subsystem — and moves it into `result`. `poll_connect` takes it from there
+/// on the UI thread, which owns it afterwards (only background listings
+/// borrow its SFTP handle, see `PendingListing`).
+/// Cancelling just drops this struct: libssh2 calls cannot be interrupted, so
+/// the worker runs to completion and the orphaned connection is dropped with
+/// the last reference to the slot.
+pub struct PendingConnect {
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    started: Instant,
+    result: ConnectSlot,
+}
+
+impl PendingConnect {
+    /// "user@host:port" of the target, for the overlay.
+    pub fn target(&self) -> String {
+        format!("{}@{}", self.profile.user, host_port(&self.profile.host, self.profile.port))
+    }
+
+    /// Whole seconds since the attempt started.
+    pub fn elapsed_secs(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+
+    /// Spinner glyph for the current moment, advancing every 100 ms.
+    pub fn spinner(&self) -> char {
+        SPINNER[(self.started.elapsed().as_millis() / 100) as usize % SPINNER.len()]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Background remote listing
+// ---------------------------------------------------------------------------
+
+/// What `poll_remote_listing` does with a finished listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListingKind {
+    /// Navigation or reload: replace the panel contents; marker shown.
+    Load,
+    /// Periodic refresh: keep cursor and marks, no marker, errors ignored.
+    Refresh,
+    /// Re-fetch of a listing shown from the cache: like `Refresh`, but
+    /// errors are reported since the cached view may be gone.
+    Revalidate,
+    /// Fresh listing for the sync preview ('m'): like `Revalidate`, then the
+    /// sync is planned against it rather than against a cached view.
+    Sync,
+}
+
+/// A remote directory listing running on a worker thread, so a large
+/// directory or a slow link does not freeze the UI. The panel keeps showing
+/// the previous listing (with a "Lade…" marker) until `poll_remote_listing`
+/// takes the result. Starting another listing replaces this one, which drops
+/// the stale result unseen.
+pub struct PendingListing {
+    /// Directory being listed; becomes the panel path once the listing arrives.
+    path: PathBuf,
+    /// `remote_path` when the listing started. A synchronous directory change
+    /// in between (e.g. a bookmark jump) makes the result stale.
+    from: PathBuf,
+    /// Cursor to restore (name, else position) after a reload of the same
+    /// directory; None puts it at the top, as after entering a directory.
+    reselect: Option<(Option<String>, usize)>,
+    kind: ListingKind,
+    /// Some(forward) when this is a step through the panel history, taken
+    /// only once the listing arrives.
+    history: Option<bool>,
+    result: ListingSlot,
+}
+
+impl PendingListing {
+    /// True while the panel should show the loading marker.
+    pub fn visible(&self) -> bool {
+        self.kind == ListingKind::Load
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Status log ('l' — history of status messages)
+// ---------------------------------------------------------------------------
+
+/// Oldest entries are dropped beyond this many.
+const STATUS_LOG_CAPACITY: usize = 500;
+
+/// One recorded status message.
+pub struct LogEntry {
+    /// Unix seconds when the message appeared.
+    pub time: u64,
+    pub text: String,
+    /// Shown in red — the message reports a failure.
+    pub is_error: bool,
+}
+
+/// Scroll state of the log overlay.
+pub struct LogDialog {
+    /// Selected entry; starts at the newest.
+    pub selected: usize,
+}
+
+impl LogDialog {
+    pub fn move_up(&mut self, by: usize) {
+        self.selected = self.selected.saturating_sub(by);
+    }
+
+    pub fn move_down(&mut self, by: usize, len: usize) {
+        self.selected = (self.selected + by).min(len.saturating_sub(1));
+    }
+}
+
+/// Heuristic for failure messages; status texts carry no severity.
+fn is_error_message(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    ["fehlgeschlagen", "fehler", "failed", "error", "abgelehnt", "ungültig"]
+        .iter()
+        .any(|w| lower.contains(w))
+}
+
 // ---------------------------------------------------------------------------
 // Rename dialog state
 // ---------------------------------------------------------------------------
 
-/// Which panel the rename/mkdir/delete applies to.
+/// Which listing the rename/mkdir/delete applies to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PanelSide {
-    Left,
-    Right,
+    Local,
+    Remote,
 }
 
 pub struct RenameDialog {
@@ -380,84 +1133,127 @@ pub struct RenameDialog {
     /// Original name of the entry being renamed.
     pub original: String,
     /// Current text in the input field.
-    pub input: String,
-    /// Byte offset of the cursor inside `input` (always on a char boundary).
-    pub cursor_pos: usize,
+    pub input: TextInput,
+    /// Validation error from the last confirm attempt.
+    pub error: Option<String>,
 }
 
 impl RenameDialog {
     pub fn new(side: PanelSide, original: String) -> Self {
-        let cursor_pos = original.len(); // start at end
-        Self { side, original: original.clone(), input: original, cursor_pos }
+        Self { side, input: TextInput::new(original.clone()), original, error: None }
     }
+}
 
-    /// Insert a character at the cursor position and advance the cursor.
-    pub fn insert(&mut self, c: char) {
-        self.input.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
-    }
+// ---------------------------------------------------------------------------
+// Upload-as dialog state ('u')
+// ---------------------------------------------------------------------------
 
-    /// Delete the character to the left of the cursor (Backspace).
-    pub fn backspace(&mut self) {
-        if self.cursor_pos == 0 {
-            return;
-        }
-        // Step back one char boundary
-        let mut pos = self.cursor_pos;
-        loop {
-            pos -= 1;
-            if self.input.is_char_boundary(pos) {
-                break;
-            }
-        }
-        self.input.remove(pos);
-        self.cursor_pos = pos;
+pub struct UploadAsDialog {
+    /// Name of the local file being uploaded.
+    pub original: String,
+    /// Remote file name; starts as the local name.
+    pub input: TextInput,
+}
+
+impl UploadAsDialog {
+    pub fn new(original: String) -> Self {
+        Self { input: TextInput::new(original.clone()), original }
     }
+}
 
-    /// Delete the character to the right of the cursor (Delete key).
-    pub fn delete_forward(&mut self) {
-        if self.cursor_pos >= self.input.len() {
-            return;
-        }
-        self.input.remove(self.cursor_pos);
+// ---------------------------------------------------------------------------
+// Batch rename dialog state (F2 with marked entries)
+// ---------------------------------------------------------------------------
+
+pub struct BatchRenameDialog {
+    pub side: PanelSide,
+    /// Names of the marked entries, in listing order.
+    pub names: Vec<String>,
+    /// Rename pattern, e.g. `IMG_{n:03}.{ext}`.
+    pub input: TextInput,
+    /// Validation error from the last confirm attempt.
+    pub error: Option<String>,
+}
+
+impl BatchRenameDialog {
+    pub fn new(side: PanelSide, names: Vec<String>) -> Self {
+        let input = "{name}.{ext}".to_string();
+        Self { side, names, input: TextInput::new(input), error: None }
     }
+}
 
-    /// Move cursor one character to the left.
-    pub fn move_left(&mut self) {
-        if self.cursor_pos == 0 {
-            return;
+/// Expand a batch-rename `pattern` for every name in `names`.
+///
+/// Tokens: `{n}` counter starting at 1, `{n:03}` zero-padded counter,
+/// `{name}` file name without extension, `{ext}` extension without the dot.
+/// Returns (old, new) pairs; unchanged names are skipped. Fails when the
+/// pattern is malformed or the result would collide with another entry
+/// (`existing` = all names currently in the directory).
+pub fn plan_batch_rename(
+    pattern: &str,
+    names: &[String],
+    existing: &[String],
+) -> Result<Vec<(String, String)>, String> {
+    let mut plan = Vec::new();
+    let mut targets: HashSet<String> = HashSet::new();
+    for (i, old) in names.iter().enumerate() {
+        let new = expand_rename_pattern(pattern, old, i + 1)?;
+        if new.is_empty() || new == "." || new == ".." || new.contains('/') {
+            return Err(format!("Ungültiger Name: '{}'", new));
         }
-        let mut pos = self.cursor_pos;
-        loop {
-            pos -= 1;
-            if self.input.is_char_boundary(pos) {
-                break;
-            }
+        if !targets.insert(new.clone()) {
+            return Err(format!("Doppelter Zielname: '{}'", new));
         }
-        self.cursor_pos = pos;
-    }
-
-    /// Move cursor one character to the right.
-    pub fn move_right(&mut self) {
-        if self.cursor_pos >= self.input.len() {
-            return;
+        if new != *old && existing.contains(&new) {
+            return Err(format!("'{}' existiert bereits", new));
         }
-        let mut pos = self.cursor_pos + 1;
-        while pos <= self.input.len() && !self.input.is_char_boundary(pos) {
-            pos += 1;
+        if new != *old {
+            plan.push((old.clone(), new));
         }
-        self.cursor_pos = pos;
     }
+    Ok(plan)
+}
 
-    /// Jump to start of input.
-    pub fn move_home(&mut self) {
-        self.cursor_pos = 0;
+/// Widest zero padding `{n:WIDTH}` accepts; anything larger is a typo and
+/// would only build huge names.
+const MAX_COUNTER_WIDTH: usize = 32;
+
+/// Expand all `{…}` tokens of `pattern` for a single entry.
+pub fn expand_rename_pattern(pattern: &str, name: &str, n: usize) -> Result<String, String> {
+    let (stem, ext) = match name.rfind('.') {
+        Some(pos) if pos > 0 => (&name[..pos], &name[pos + 1..]),
+        _ => (name, ""),
+    };
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| "Fehlende '}' im Muster".to_string())?;
+        let token = &rest[start + 1..start + end];
+        match token {
+            "name" => out.push_str(stem),
+            "ext" => out.push_str(ext),
+            "n" => out.push_str(&n.to_string()),
+            _ => match token.strip_prefix("n:").and_then(|w| w.parse::<usize>().ok()) {
+                Some(width) if width > MAX_COUNTER_WIDTH => {
+                    return Err(format!("Zählerbreite höchstens {}: {{{}}}", MAX_COUNTER_WIDTH, token))
+                }
+                Some(width) => out.push_str(&format!("{:0width$}", n, width = width)),
+                None => return Err(format!("Unbekannter Platzhalter: {{{}}}", token)),
+            },
+        }
+        rest = &rest[start + end + 1..];
     }
-
-    /// Jump to end of input.
-    pub fn move_end(&mut self) {
-        self.cursor_pos = self.input.len();
+    out.push_str(rest);
+    // "{name}.{ext}" on an entry without extension would leave a trailing dot.
+    if ext.is_empty() {
+        if let Some(stripped) = out.strip_suffix('.') {
+            return Ok(stripped.to_string());
+        }
     }
+    Ok(out)
 }
 
 // ---------------------------------------------------------------------------
@@ -466,81 +1262,425 @@ impl RenameDialog {
 
 pub struct MkdirDialog {
     pub side: PanelSide,
-    pub input: String,
-    /// Byte offset of the cursor inside `input` (always on a char boundary).
-    pub cursor_pos: usize,
+    pub input: TextInput,
+    /// Validation error from the last confirm attempt.
+    pub error: Option<String>,
 }
 
 impl MkdirDialog {
     pub fn new(side: PanelSide) -> Self {
-        Self { side, input: String::new(), cursor_pos: 0 }
+        Self { side, input: TextInput::default(), error: None }
     }
+}
 
-    /// Insert a character at the cursor position and advance the cursor.
-    pub fn insert(&mut self, c: char) {
-        self.input.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
-    }
+// ---------------------------------------------------------------------------
+// Touch dialog state ('n' — create empty file, Shift+F4 — create and edit)
+// ---------------------------------------------------------------------------
 
-    /// Delete the character to the left of the cursor (Backspace).
-    pub fn backspace(&mut self) {
-        if self.cursor_pos == 0 {
-            return;
-        }
-        let mut pos = self.cursor_pos;
-        loop {
-            pos -= 1;
-            if self.input.is_char_boundary(pos) {
-                break;
-            }
-        }
-        self.input.remove(pos);
-        self.cursor_pos = pos;
+pub struct TouchDialog {
+    pub side: PanelSide,
+    /// Open the new file in the editor right after creating it (Shift+F4).
+    pub edit: bool,
+    pub input: TextInput,
+    /// Validation error from the last confirm attempt.
+    pub error: Option<String>,
+}
+
+impl TouchDialog {
+    pub fn new(side: PanelSide, edit: bool) -> Self {
+        Self { side, edit, input: TextInput::default(), error: None }
     }
+}
 
-    /// Delete the character to the right of the cursor (Delete key).
-    pub fn delete_forward(&mut self) {
-        if self.cursor_pos >= self.input.len() {
-            return;
+// ---------------------------------------------------------------------------
+// Go-to-path dialog state (Ctrl+G)
+// ---------------------------------------------------------------------------
+
+pub struct GotoDialog {
+    pub side: PanelSide,
+    pub input: TextInput,
+    /// Why the last confirm failed (missing path, not a directory).
+    pub error: Option<String>,
+    /// Directories offered by the last ambiguous Tab completion; cleared on edit.
+    pub candidates: Vec<String>,
+}
+
+impl GotoDialog {
+    /// Start with the panel's current directory, ready to be extended.
+    pub fn new(side: PanelSide, current: &std::path::Path) -> Self {
+        let mut input = current.to_string_lossy().to_string();
+        if !input.ends_with('/') {
+            input.push('/');
         }
-        self.input.remove(self.cursor_pos);
+        Self { side, input: TextInput::new(input), error: None, candidates: Vec::new() }
     }
 
-    /// Move cursor one character to the left.
-    pub fn move_left(&mut self) {
-        if self.cursor_pos == 0 {
-            return;
+    /// Drop the error and completion candidates after the input changed.
+    pub fn edited(&mut self) {
+        self.error = None;
+        self.candidates.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mark-by-pattern dialog state ('+' marks, '-' unmarks)
+// ---------------------------------------------------------------------------
+
+pub struct MarkPatternDialog {
+    /// true = mark matches ('+'), false = unmark them ('-').
+    pub mark: bool,
+    pub input: TextInput,
+}
+
+impl MarkPatternDialog {
+    /// Start with "*" so Enter alone (un)marks everything.
+    pub fn new(mark: bool) -> Self {
+        Self { mark, input: TextInput::new("*") }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mark-by-age dialog state ('a')
+// ---------------------------------------------------------------------------
+
+pub struct MarkAgeDialog {
+    /// true = mark entries older than the input, false = newer ones (Tab).
+    pub older: bool,
+    /// Relative age ("30d", "12h", "2w") or a date ("2024-01-31").
+    pub input: TextInput,
+    /// Parse error of the last Enter; cleared on edit.
+    pub error: Option<String>,
+}
+
+impl MarkAgeDialog {
+    /// Start with "30d", older entries.
+    pub fn new() -> Self {
+        Self { older: true, input: TextInput::new("30d"), error: None }
+    }
+}
+
+/// Turn the mark-by-age input into a cut-off time: "30d", "12h" and "2w"
+/// count back from `now`, "YYYY-MM-DD" is local midnight of that day.
+pub fn parse_age_cutoff(input: &str, now: SystemTime) -> Result<SystemTime, String> {
+    let input = input.trim();
+    let invalid = || format!("'{}' — erwartet z.B. 30d, 12h, 2w oder 2024-01-31", input);
+    if let Some((y, m, d)) = parse_date(input) {
+        let secs = local_midnight(y, m, d).ok_or_else(invalid)?;
+        return Ok(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs));
+    }
+    let unit = match input.chars().last() {
+        Some('h') => 3_600,
+        Some('d') => 86_400,
+        Some('w') => 604_800,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = input[..input.len() - 1].parse().map_err(|_| invalid())?;
+    count
+        .checked_mul(unit)
+        .and_then(|secs| now.checked_sub(std::time::Duration::from_secs(secs)))
+        .ok_or_else(invalid)
+}
+
+/// Split "YYYY-MM-DD" into its numbers (not yet checked for a valid day).
+fn parse_date(input: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = input.splitn(3, '-');
+    let year = parts.next().filter(|p| p.len() == 4)?.parse().ok()?;
+    let month = parts.next().filter(|p| p.len() == 2)?.parse().ok()?;
+    let day = parts.next().filter(|p| p.len() == 2)?.parse().ok()?;
+    Some((year, month, day))
+}
+
+/// Unix seconds of local midnight on the given day, or None for dates that
+/// do not exist (mktime would roll "02-30" over into March) or lie before 1970.
+fn local_midnight(year: i32, month: u32, day: u32) -> Option<u64> {
+    // Safe: mktime only reads and normalises the zeroed `tm` we own.
+    let (secs, tm) = unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        tm.tm_year = year - 1900;
+        tm.tm_mon = month as i32 - 1;
+        tm.tm_mday = day as i32;
+        tm.tm_isdst = -1;
+        (libc::mktime(&mut tm), tm)
+    };
+    if tm.tm_mon != month as i32 - 1 || tm.tm_mday != day as i32 {
+        return None;
+    }
+    u64::try_from(secs).ok()
+}
+
+// ---------------------------------------------------------------------------
+// Chown dialog state ('o' — change owner of remote entries)
+// ---------------------------------------------------------------------------
+
+pub struct ChownDialog {
+    /// Remote entries to change (marked entries or the highlighted one).
+    pub names: Vec<String>,
+    /// "uid:gid", numeric — SFTP has no user/group name lookup.
+    pub input: TextInput,
+}
+
+impl ChownDialog {
+    /// Pre-fill the input with the current owner, cursor at the end.
+    pub fn new(names: Vec<String>, uid: Option<u32>, gid: Option<u32>) -> Self {
+        let input = match (uid, gid) {
+            (Some(u), Some(g)) => format!("{}:{}", u, g),
+            _ => String::new(),
+        };
+        Self { names, input: TextInput::new(input) }
+    }
+
+    /// Parse the input as "uid:gid".
+    pub fn parse(&self) -> Option<(u32, u32)> {
+        let (uid, gid) = self.input.buf.trim().split_once(':')?;
+        Some((uid.trim().parse().ok()?, gid.trim().parse().ok()?))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Search dialog state ('/' — recursive remote search)
+// ---------------------------------------------------------------------------
+
+pub struct SearchDialog {
+    /// Substring or glob (`*`, `?`), matched case-insensitively.
+    pub input: TextInput,
+    /// Remote directory the search walks.
+    pub root: PathBuf,
+    /// Set once the search thread runs; the dialog then shows results.
+    pub handle: Option<SearchHandle>,
+    cancel: Arc<AtomicBool>,
+    /// Cursor within the result list.
+    pub selected: usize,
+}
+
+impl SearchDialog {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            input: TextInput::default(),
+            root,
+            handle: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            selected: 0,
         }
-        let mut pos = self.cursor_pos;
-        loop {
-            pos -= 1;
-            if self.input.is_char_boundary(pos) {
-                break;
-            }
+    }
+
+    /// Ask the search thread to stop (no-op when it already finished).
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Number of results collected so far.
+    pub fn result_count(&self) -> usize {
+        self.handle
+            .as_ref()
+            .map(|h| h.lock().unwrap().matches.len())
+            .unwrap_or(0)
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.result_count() {
+            self.selected += 1;
         }
-        self.cursor_pos = pos;
     }
+}
 
-    /// Move cursor one character to the right.
-    pub fn move_right(&mut self) {
-        if self.cursor_pos >= self.input.len() {
-            return;
+// ---------------------------------------------------------------------------
+// Grep dialog state ('g' — content search below the local directory)
+// ---------------------------------------------------------------------------
+
+pub struct GrepDialog {
+    pub input: TextInput,
+    /// Treat `input` as a regular expression instead of literal text (Tab).
+    pub regex: bool,
+    /// Invalid regex message, shown under the input.
+    pub error: Option<String>,
+    /// Local directory the search walks.
+    pub root: PathBuf,
+    /// Set once the search thread runs; the dialog then shows results.
+    pub handle: Option<GrepHandle>,
+    cancel: Arc<AtomicBool>,
+    /// Cursor within the result list.
+    pub selected: usize,
+}
+
+impl GrepDialog {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            input: TextInput::default(),
+            regex: false,
+            error: None,
+            root,
+            handle: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            selected: 0,
         }
-        let mut pos = self.cursor_pos + 1;
-        while pos <= self.input.len() && !self.input.is_char_boundary(pos) {
-            pos += 1;
+    }
+
+    /// Ask the search thread to stop (no-op when it already finished).
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Number of results collected so far.
+    pub fn result_count(&self) -> usize {
+        self.handle
+            .as_ref()
+            .map(|h| h.lock().unwrap().hits.len())
+            .unwrap_or(0)
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.result_count() {
+            self.selected += 1;
         }
-        self.cursor_pos = pos;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Command palette state (Ctrl+P — searchable list of all actions)
+// ---------------------------------------------------------------------------
+
+/// An action that can be run from the command palette. Each maps onto the
+/// same code path as its keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    Connect,
+    Disconnect,
+    Reconnect,
+    Upload,
+    UploadAs,
+    Download,
+    Edit,
+    Rename,
+    Mkdir,
+    Touch,
+    EditNew,
+    Delete,
+    Chown,
+    Info,
+    StatusLog,
+    Search,
+    Grep,
+    Cut,
+    Copy,
+    Paste,
+    Tail,
+    Shell,
+    RemoteShell,
+    ConnectionDetails,
+    TempCleanup,
+    MarkAll,
+    MarkPattern,
+    UnmarkPattern,
+    MarkAge,
+    InvertMarks,
+    SwitchPanel,
+    SwapPanels,
+    SinglePanel,
+    ToggleHidden,
+    Compare,
+    Sync,
+    ExactSizes,
+    LocalPermissions,
+    RelativeDates,
+    Refresh,
+    GoTo,
+    HistoryBack,
+    HistoryForward,
+    CycleTheme,
+    Help,
+    Quit,
+}
+
+/// Palette entries: (label, shortcut shown next to it, action).
+pub const PALETTE_COMMANDS: &[(&str, &str, PaletteAction)] = &[
+    ("Verbinden (Profile öffnen)", "F9 / p", PaletteAction::Connect),
+    ("Verbindung trennen", "F3", PaletteAction::Disconnect),
+    ("Letzte Verbindung wiederherstellen", "Shift+F3", PaletteAction::Reconnect),
+    ("Upload (lokal → remote)", "F5", PaletteAction::Upload),
+    ("Upload unter anderem Namen", "u", PaletteAction::UploadAs),
+    ("Download (remote → lokal)", "F6", PaletteAction::Download),
+    ("Datei bearbeiten", "F4", PaletteAction::Edit),
+    ("Umbenennen", "F2", PaletteAction::Rename),
+    ("Verzeichnis erstellen", "F7", PaletteAction::Mkdir),
+    ("Leere Datei erstellen", "n", PaletteAction::Touch),
+    ("Neue Datei erstellen und bearbeiten", "Shift+F4", PaletteAction::EditNew),
+    ("Löschen", "F8", PaletteAction::Delete),
+    ("Besitzer ändern", "o", PaletteAction::Chown),
+    ("Details zum Eintrag", "i", PaletteAction::Info),
+    ("Meldungsverlauf anzeigen", "l", PaletteAction::StatusLog),
+    ("Remote rekursiv suchen", "/", PaletteAction::Search),
+    ("Dateiinhalte durchsuchen", "g", PaletteAction::Grep),
+    ("Ausschneiden", "x", PaletteAction::Cut),
+    ("Kopieren", "y", PaletteAction::Copy),
+    ("Einfügen", "v", PaletteAction::Paste),
+    ("Remote-Datei verfolgen (tail)", "t", PaletteAction::Tail),
+    ("Shell-Befehl ausführen (lokal / remote)", "!", PaletteAction::Shell),
+    ("Remote-Shell öffnen (ssh)", "s", PaletteAction::RemoteShell),
+    ("Verbindungsdetails protokollieren", "", PaletteAction::ConnectionDetails),
+    ("Temp-Dateien aufräumen", "c", PaletteAction::TempCleanup),
+    ("Alle markieren / abwählen", "*", PaletteAction::MarkAll),
+    ("Nach Muster markieren", "+", PaletteAction::MarkPattern),
+    ("Nach Muster abwählen", "-", PaletteAction::UnmarkPattern),
+    ("Nach Alter markieren", "a", PaletteAction::MarkAge),
+    ("Markierung umkehren", "#", PaletteAction::InvertMarks),
+    ("Panel wechseln", "Tab", PaletteAction::SwitchPanel),
+    ("Panels tauschen", "Ctrl+U", PaletteAction::SwapPanels),
+    ("Einzelpanel (volle Breite) umschalten", "Ctrl+W", PaletteAction::SinglePanel),
+    ("Versteckte Dateien ein/aus", ".", PaletteAction::ToggleHidden),
+    ("Panels vergleichen", "=", PaletteAction::Compare),
+    ("Sync lokal → remote (mit Vorschau)", "m", PaletteAction::Sync),
+    ("Exakte Größen umschalten", "Ctrl+Z", PaletteAction::ExactSizes),
+    ("Rechte-Spalte im lokalen Panel", "Ctrl+O", PaletteAction::LocalPermissions),
+    ("Relatives Datum umschalten", "Ctrl+D", PaletteAction::RelativeDates),
+    ("Panel aktualisieren", "Ctrl+R", PaletteAction::Refresh),
+    ("Gehe zu Pfad", "Ctrl+G", PaletteAction::GoTo),
+    ("Zurück im Verzeichnisverlauf", "Alt+←", PaletteAction::HistoryBack),
+    ("Vorwärts im Verzeichnisverlauf", "Alt+→", PaletteAction::HistoryForward),
+    ("Theme umschalten", "Ctrl+T", PaletteAction::CycleTheme),
+    ("Tastaturkürzel anzeigen", "F1", PaletteAction::Help),
+    ("Beenden", "F10 / q", PaletteAction::Quit),
+];
+
+#[derive(Default)]
+pub struct PaletteDialog {
+    pub input: TextInput,
+    /// Cursor within the filtered command list.
+    pub selected: usize,
+}
+
+impl PaletteDialog {
+    /// Commands whose label or shortcut contains the input (case-insensitive).
+    pub fn matches(&self) -> Vec<&'static (&'static str, &'static str, PaletteAction)> {
+        let needle = self.input.buf.to_lowercase();
+        PALETTE_COMMANDS
+            .iter()
+            .filter(|(label, key, _)| {
+                label.to_lowercase().contains(&needle) || key.to_lowercase().contains(&needle)
+            })
+            .collect()
+    }
+
+    /// The action under the cursor, if the filter left any.
+    pub fn selected_action(&self) -> Option<PaletteAction> {
+        self.matches().get(self.selected).map(|(_, _, action)| *action)
     }
 
-    /// Jump to start of input.
-    pub fn move_home(&mut self) {
-        self.cursor_pos = 0;
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
     }
 
-    /// Jump to end of input.
-    pub fn move_end(&mut self) {
-        self.cursor_pos = self.input.len();
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.matches().len() {
+            self.selected += 1;
+        }
     }
 }
 
@@ -553,12 +1693,66 @@ pub struct DeleteDialog {
     /// All entries to delete: (name, is_dir).
     /// When a single entry is targeted this Vec has exactly one element.
     pub entries: Vec<(String, bool)>,
+    /// Recursive count of everything that will be removed; only gathered
+    /// when the selection contains directories.
+    pub summary: Option<TreeCount>,
+    /// The preflight walk still running on a worker thread; the dialog shows
+    /// "zähle…" until `App::poll_delete_count` moves its result to `summary`.
+    pub counting: Option<TreeCountSlot>,
+    cancel: Arc<AtomicBool>,
+    /// Move to the trash instead of deleting (settings `safe_delete` /
+    /// `remote_trash`, depending on `side`).
+    pub trash: bool,
 }
 
 impl DeleteDialog {
     /// Create a dialog for one or more entries.
     pub fn new_multi(side: PanelSide, entries: Vec<(String, bool)>) -> Self {
-        Self { side, entries }
+        Self {
+            side,
+            entries,
+            summary: None,
+            counting: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            trash: false,
+        }
+    }
+
+    /// Ask the preflight walk to stop (no-op when it already finished).
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Upper bound for the delete preflight walk so huge trees don't freeze the UI.
+pub const TREE_COUNT_LIMIT: usize = 10_000;
+
+/// Files and directories found by a bounded recursive walk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreeCount {
+    pub files: usize,
+    pub dirs: usize,
+    /// The walk stopped at `TREE_COUNT_LIMIT`; the real numbers are higher.
+    pub truncated: bool,
+}
+
+/// Result slot of a background preflight walk: None while it runs, an
+/// error when the remote walk could not connect.
+pub type TreeCountSlot = Arc<Mutex<Option<Result<TreeCount, String>>>>;
+
+impl TreeCount {
+    /// Count one entry. Returns false once the limit is reached — stop walking.
+    pub fn add(&mut self, is_dir: bool) -> bool {
+        if self.files + self.dirs >= TREE_COUNT_LIMIT {
+            self.truncated = true;
+            return false;
+        }
+        if is_dir {
+            self.dirs += 1;
+        } else {
+            self.files += 1;
+        }
+        true
     }
 }
 
@@ -582,69 +1776,142 @@ pub enum EditRequest {
         remote_path: std::path::PathBuf,
         /// mtime of temp file before the editor was launched.
         mtime_before: SystemTime,
+        /// Server-side mtime (Unix seconds) at download time — used to detect
+        /// concurrent changes before uploading back.
+        remote_mtime_before: Option<u64>,
         /// Owns the temp directory; auto-deleted when this value is dropped.
         _temp_dir: tempfile::TempDir,
     },
 }
 
+// ---------------------------------------------------------------------------
+// Remote clipboard ('x' / 'y' / 'v')
+// ---------------------------------------------------------------------------
+
+/// Operation performed when the clipboard is pasted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardOp {
+    /// Move the entries into the paste directory (server-side rename).
+    Cut,
+    /// Duplicate the entries into the paste directory (streamed on the server).
+    Copy,
+}
+
+/// Remote entries waiting to be pasted into another remote directory.
+pub struct RemoteClipboard {
+    pub op: ClipboardOp,
+    /// Absolute remote source paths.
+    pub paths: Vec<PathBuf>,
+}
+
+// ---------------------------------------------------------------------------
+// Temp file cleanup dialog ('c')
+// ---------------------------------------------------------------------------
+
+/// Lists leftovers in the remote-edit temp directory (e.g. after a crash).
+pub struct TempCleanupDialog {
+    /// (name, size in bytes) of every top-level entry in the temp directory.
+    pub entries: Vec<(String, u64)>,
+}
+
+impl TempCleanupDialog {
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|(_, size)| size).sum()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sync preview dialog ('m')
+// ---------------------------------------------------------------------------
+
+/// Dry-run preview of a one-way sync local → remote; nothing is changed
+/// until it is confirmed.
+pub struct SyncDialog {
+    pub plan: SyncPlan,
+    /// Also delete remote entries that don't exist locally ('d' toggles).
+    pub delete_extra: bool,
+    /// Cursor in the preview list (uploads, then extras).
+    pub selected: usize,
+}
+
+impl SyncDialog {
+    pub fn len(&self) -> usize {
+        self.plan.upload.len() + self.plan.extra.len()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Details dialog ('i')
+// ---------------------------------------------------------------------------
+
+/// Full metadata of the highlighted entry, gathered when the popup opens.
+/// For symlinks the fields describe the target (like the panel columns);
+/// `None` means the value is unavailable on that side or could not be read.
+pub struct InfoDialog {
+    /// Absolute local path or remote path.
+    pub path: String,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub link_target: Option<String>,
+    pub size: Option<u64>,
+    /// Modification time in seconds since the Unix epoch.
+    pub mtime: Option<u64>,
+    /// Permission bits (lower 12 bits of st_mode).
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // Shell command dialog ('!')
 // ---------------------------------------------------------------------------
 
 pub struct ShellDialog {
-    pub input: String,
-    pub cursor_pos: usize,
+    /// Run on the connected host (remote panel active) instead of locally.
+    pub remote: bool,
+    pub input: TextInput,
     /// None = input phase; Some(lines) = output/result phase.
     pub output: Option<Vec<String>>,
     pub scroll: usize,
     pub exit_code: Option<i32>,
+    /// Output lines that fit in the popup, recorded by the last render
+    /// (0 until the output has been drawn once). Used for the scroll bounds.
+    pub view_height: Cell<usize>,
+    /// Wrap long output lines at the popup width ('w' toggles).
+    pub wrap: bool,
+    /// Screen rows the output takes at the last render — more than the line
+    /// count while wrapping. 0 until drawn once.
+    pub content_rows: Cell<usize>,
+    /// Names offered by the last ambiguous Tab completion; cleared on edit.
+    pub candidates: Vec<String>,
+    /// Remote command still running on a worker (`exec_remote`); the
+    /// dialog stays in the input phase with a "läuft…" line until then.
+    pub running: Option<ExecSlot>,
+    cancel: Arc<AtomicBool>,
 }
 
 impl ShellDialog {
-    pub fn new() -> Self {
+    pub fn new(remote: bool) -> Self {
         Self {
-            input: String::new(),
-            cursor_pos: 0,
+            remote,
+            input: TextInput::default(),
             output: None,
             scroll: 0,
             exit_code: None,
+            view_height: Cell::new(0),
+            wrap: false,
+            content_rows: Cell::new(0),
+            candidates: Vec::new(),
+            running: None,
+            cancel: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub fn insert(&mut self, c: char) {
-        self.input.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
-    }
-
-    pub fn backspace(&mut self) {
-        if self.cursor_pos == 0 { return; }
-        let mut pos = self.cursor_pos;
-        loop { pos -= 1; if self.input.is_char_boundary(pos) { break; } }
-        self.input.remove(pos);
-        self.cursor_pos = pos;
-    }
-
-    pub fn delete_forward(&mut self) {
-        if self.cursor_pos < self.input.len() { self.input.remove(self.cursor_pos); }
-    }
-
-    pub fn move_left(&mut self) {
-        if self.cursor_pos == 0 { return; }
-        let mut pos = self.cursor_pos;
-        loop { pos -= 1; if self.input.is_char_boundary(pos) { break; } }
-        self.cursor_pos = pos;
-    }
-
-    pub fn move_right(&mut self) {
-        if self.cursor_pos >= self.input.len() { return; }
-        let mut pos = self.cursor_pos + 1;
-        while pos <= self.input.len() && !self.input.is_char_boundary(pos) { pos += 1; }
-        self.cursor_pos = pos;
+    /// Stop waiting for a running remote command (no-op when none runs).
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
     }
 
-    pub fn move_home(&mut self) { self.cursor_pos = 0; }
-    pub fn move_end(&mut self)  { self.cursor_pos = self.input.len(); }
-
     pub fn scroll_up(&mut self) {
         self.scroll = self.scroll.saturating_sub(1);
     }
@@ -662,6 +1929,11 @@ impl ShellDialog {
         let max = total_lines.saturating_sub(visible);
         self.scroll = (self.scroll + page).min(max);
     }
+
+    /// Pull the scroll offset back after the popup grew (terminal resize).
+    pub fn clamp_scroll(&mut self, total_lines: usize, visible: usize) {
+        self.scroll = self.scroll.min(total_lines.saturating_sub(visible));
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -671,11 +1943,58 @@ impl ShellDialog {
 /// How often to poll the remote directory for background changes.
 const REMOTE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
 
+/// How often to time a round trip for the status bar latency indicator.
+const LATENCY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Profiles listed in the disconnected remote panel.
+const QUICK_CONNECT_MAX: usize = 9;
+
 pub struct PermissionFixDialog {
     pub path: String,
     pub mode: u32,
 }
 
+/// Shown when a remotely edited file changed on the server while the editor
+/// was open, or when that could not be checked. Holds the edit request so its
+/// temp copy stays alive.
+pub struct EditConflictDialog {
+    pub req: EditRequest,
+    /// The server copy could not be stat'ed, so a change cannot be ruled out.
+    pub unverified: bool,
+}
+
+impl EditConflictDialog {
+    /// Path of the conflicting remote file.
+    pub fn path(&self) -> String {
+        match &self.req {
+            EditRequest::Remote { remote_path, .. } => remote_path.display().to_string(),
+            EditRequest::Local { path } => path.display().to_string(),
+        }
+    }
+}
+
+/// Shown after editing a remote file when `confirm_edit_upload` is set.
+/// Holds the edit request so its temp copy stays alive.
+pub struct EditUploadDialog {
+    pub req: EditRequest,
+}
+
+impl EditUploadDialog {
+    /// Path of the edited remote file.
+    pub fn path(&self) -> String {
+        match &self.req {
+            EditRequest::Remote { remote_path, .. } => remote_path.display().to_string(),
+            EditRequest::Local { path } => path.display().to_string(),
+        }
+    }
+}
+
+/// Confirmation before F4 pulls a remote file larger than `EDIT_SIZE_LIMIT`.
+pub struct EditSizeDialog {
+    pub name: String,
+    pub size: u64,
+}
+
 pub struct HostKeyDialog {
     pub host: String,
     pub port: u16,
@@ -687,11 +2006,19 @@ pub struct HostKeyDialog {
 }
 
 pub struct App {
-    pub left: PanelState,
-    pub right: PanelState,
+    /// Logical panels — use `local()` / `remote()` to pick by content.
+    left: PanelState,
+    right: PanelState,
+    /// Focused logical panel. Tab, key routing and the focus border all use
+    /// it; where it appears on screen is decided by `screen_left()` alone.
     pub active: ActivePanel,
     pub running: bool,
+    /// Message in the status bar; set it through `set_status` so it is logged.
     pub status_message: Option<String>,
+    /// Every status message shown so far (newest last, capped).
+    pub status_log: VecDeque<LogEntry>,
+    /// Status log overlay ('l')
+    pub log_dialog: Option<LogDialog>,
     /// Live SFTP connection (if connected)
     pub sftp: Option<SftpConnection>,
     /// Profile manager dialog
@@ -700,83 +2027,276 @@ pub struct App {
     pub password_dialog: Option<PasswordDialog>,
     /// Active upload progress handle (None when idle)
     pub upload_progress: Option<ProgressHandle>,
+    /// Remote target directory of the running upload; its cached listings
+    /// are dropped when the upload ends.
+    upload_remote_dir: Option<PathBuf>,
     /// Active download progress handle (None when idle)
     pub download_progress: Option<TransferHandle>,
+    /// Uploads and downloads requested while another transfer was running;
+    /// started one after another by `poll_transfer_queue`.
+    pub transfer_queue: VecDeque<QueuedJob>,
+    /// Uploads and downloads finished since the queue was last idle; with the
+    /// running one and `transfer_queue` this gives "Auftrag n/m".
+    pub queue_jobs_done: usize,
+    /// Files transferred by the jobs counted in `queue_jobs_done`.
+    pub queue_files_done: usize,
+    /// Active server-side copy progress handle (None when idle)
+    pub copy_progress: Option<TransferHandle>,
+    /// Progress of a background remote delete (directory trees).
+    pub delete_progress: Option<TransferHandle>,
     /// Rename dialog (F2)
     pub rename_dialog: Option<RenameDialog>,
+    /// Pattern rename dialog (F2 with marked entries)
+    pub batch_rename_dialog: Option<BatchRenameDialog>,
     /// Mkdir dialog (F7)
     pub mkdir_dialog: Option<MkdirDialog>,
+    /// Create-empty-file dialog ('n')
+    pub touch_dialog: Option<TouchDialog>,
+    /// Go-to-path prompt (Ctrl+G)
+    pub goto_dialog: Option<GotoDialog>,
+    pub upload_as_dialog: Option<UploadAsDialog>,
+    pub mark_pattern_dialog: Option<MarkPatternDialog>,
+    /// Mark by modification time dialog ('a')
+    pub mark_age_dialog: Option<MarkAgeDialog>,
+    /// Change-owner dialog ('o')
+    pub chown_dialog: Option<ChownDialog>,
+    /// Recursive remote search ('/')
+    pub search_dialog: Option<SearchDialog>,
+    /// Local content search ('g')
+    pub grep_dialog: Option<GrepDialog>,
+    /// Command palette (Ctrl+P)
+    pub palette_dialog: Option<PaletteDialog>,
     /// Delete confirmation dialog (F8)
     pub delete_dialog: Option<DeleteDialog>,
     /// Keyboard shortcut help overlay (F1)
     pub help_visible: bool,
+    /// Quit confirmation while a transfer is still running (q / F10)
+    pub quit_confirm_visible: bool,
     /// Pending editor launch from F4 — consumed by the main loop.
     pub pending_edit: Option<EditRequest>,
+    /// Arguments for the system `ssh` binary; set by `open_remote_shell`,
+    /// consumed by the main loop which suspends the TUI around the session.
+    pub pending_remote_shell: Option<Vec<String>>,
     /// Shell command dialog ('!')
     pub shell_dialog: Option<ShellDialog>,
+    /// Remote entries cut with 'x' or copied with 'y', pasted with 'v'.
+    pub clipboard: Option<RemoteClipboard>,
+    /// Some when the temp file cleanup overlay is open.
+    pub temp_cleanup_dialog: Option<TempCleanupDialog>,
+    /// Sync preview local → remote ('m')
+    pub sync_dialog: Option<SyncDialog>,
+    /// Metadata popup for the highlighted entry ('i')
+    pub info_dialog: Option<InfoDialog>,
+    /// Running profile connection test (None when idle)
+    pub connection_test: Option<ConnectionTest>,
+    /// Panel connect in progress (overlay shown, Esc cancels).
+    pub pending_connect: Option<PendingConnect>,
     /// Permission fix dialog for profile config
     pub permission_dialog: Option<PermissionFixDialog>,
+    /// Overwrite confirmation after a concurrent remote change (F4)
+    pub edit_conflict_dialog: Option<EditConflictDialog>,
+    /// Upload confirmation after a remote edit (settings `confirm_edit_upload`)
+    pub edit_upload_dialog: Option<EditUploadDialog>,
+    /// Size warning before editing a large remote file (F4)
+    pub edit_size_dialog: Option<EditSizeDialog>,
     /// Unknown-host-key confirmation dialog
     pub host_key_dialog: Option<HostKeyDialog>,
-    /// When true the panels are rendered swapped: remote on the left, local on the right.
+    /// When true the two logical panels trade places on screen (Ctrl+U).
+    /// Purely visual — see `screen_left()`.
     pub panels_swapped: bool,
+    /// Only the active panel is shown, full width (Ctrl+W); Tab switches
+    /// which one. Purely visual — see `panel_areas`.
+    pub single_panel: bool,
+    /// Render without colours (settings `no_color`, `NO_COLOR`, `--no-color`).
+    pub no_color: bool,
+    /// Password from `--password-file` / `$VELA_PASSWORD` with the name of
+    /// the profile given on the command line. Only that profile uses it, and
+    /// only for the first attempt: a retry falls back to the keychain or the
+    /// password dialog.
+    pub preset_password: Option<(String, Zeroizing<String>)>,
+    /// Space moves the cursor down after toggling a mark (settings `mark_advances`).
+    pub mark_advances: bool,
+    /// File list columns and their widths (settings `columns`, `column_widths`).
+    pub columns: Vec<Column>,
+    pub column_widths: ColumnWidths,
+    /// The disconnected remote panel lists recent profiles (settings `quick_connect`).
+    pub quick_connect: bool,
+    /// Profiles of that list, most recently used first (`refresh_quick_connect`).
+    pub quick_profiles: Vec<Profile>,
+    /// Highlighted row of the quick-connect list.
+    pub quick_selected: usize,
+    /// F8 moves local entries to the desktop trash (settings `safe_delete`).
+    pub safe_delete: bool,
+    /// F8 moves remote entries to `~/.vela_trash` (settings `remote_trash`).
+    pub remote_trash: bool,
+    /// Size column shows exact byte counts instead of rounded units (Ctrl+Z).
+    pub exact_sizes: bool,
+    /// The local panel shows the permissions column too (Ctrl+O).
+    pub local_permissions: bool,
+    /// Date column shows the age ("vor 3 Std.") instead of the timestamp (Ctrl+D).
+    pub relative_dates: bool,
+    /// Colour entries by how they compare with the other panel ('=').
+    pub compare_mode: bool,
+    /// Profile of the last successful connect, for Shift+F3 reconnect.
+    /// Replaced when another profile connects.
+    pub last_profile: Option<Profile>,
+    /// Profile of the most recent connect, successful or not; shown by the
+    /// connection details action.
+    pub last_connect_attempt: Option<Profile>,
+    /// Last left click (time, panel, row) for double-click detection.
+    last_click: Option<(Instant, ActivePanel, usize)>,
+    /// Logical panel that holds the remote listing (settings: `remote_panel`).
+    /// The other panel holds the local listing.
+    pub remote_side: ActivePanel,
     /// Dark / Light / Auto theme selection.
     pub theme_choice: ThemeChoice,
+    /// settings.toml as read at startup. Everything reads it from here;
+    /// changes made in the UI go back to disk through `save_settings`.
+    pub settings: Settings,
     /// Holds the notify watcher alive; dropping it stops the OS watch.
     local_watcher: Option<RecommendedWatcher>,
     /// Receive side of the notify event channel.
     local_watcher_rx: Option<mpsc::Receiver<notify::Result<FsEvent>>>,
-    /// Path currently being watched — compared to `left.path` to detect navigation.
+    /// Path currently being watched — compared to the local panel path to detect navigation.
     local_watched_path: Option<PathBuf>,
     /// Timestamp of last remote refresh; None = never refreshed (fires immediately on connect).
     last_remote_refresh: Option<Instant>,
+    /// Remote listing in flight (navigation, reload or periodic refresh).
+    pub pending_listing: Option<PendingListing>,
+    /// Remote directory and result slot of a running free-space query.
+    pending_free_space: Option<(PathBuf, FreeSpaceSlot)>,
+    /// Recently visited remote listings, shown at once on navigation.
+    listing_cache: ListingCache,
+    /// Round trip of the last `stat` on the remote directory; None before the
+    /// first measurement or after a failed one.
+    pub latency: Option<std::time::Duration>,
+    /// The last latency probe failed — the link may be dead.
+    pub latency_failed: bool,
+    /// Timestamp of the last latency probe; None = probe on the next poll.
+    last_latency_check: Option<Instant>,
 }
 
 impl App {
-    pub fn new() -> Result<Self, AppError> {
+    pub fn new(settings: Settings) -> Result<Self, AppError> {
+        let remote_side = match settings.remote_panel {
+            PanelPosition::Left => ActivePanel::Left,
+            PanelPosition::Right => ActivePanel::Right,
+        };
         let home = dirs_or_cwd();
-        let mut left = PanelState::new(home.clone());
-        left.load_local()?;
-        let right = PanelState::new(home);
+        // Resume in the directory of the last session if it still exists.
+        let start = SessionState::load()
+            .local_path
+            .filter(|p| p.is_dir())
+            .unwrap_or_else(|| home.clone());
+        let mut local = PanelState::new(start);
+        local.show_hidden = settings.show_hidden;
+        local.load_local()?;
+        let mut remote = PanelState::new(home);
+        remote.show_hidden = settings.show_hidden;
+        let (left, right) = match remote_side {
+            ActivePanel::Left => (remote, local),
+            ActivePanel::Right => (local, remote),
+        };
         let mut app = Self {
             left,
             right,
-            active: ActivePanel::Left,
+            active: remote_side.toggle(),
             running: true,
             status_message: None,
+            status_log: VecDeque::new(),
+            log_dialog: None,
             sftp: None,
             profile_dialog: None,
             password_dialog: None,
             upload_progress: None,
+            upload_remote_dir: None,
+            transfer_queue: VecDeque::new(),
+            queue_jobs_done: 0,
+            queue_files_done: 0,
             download_progress: None,
+            copy_progress: None,
+            delete_progress: None,
             rename_dialog: None,
+            batch_rename_dialog: None,
             mkdir_dialog: None,
+            touch_dialog: None,
+            goto_dialog: None,
+            upload_as_dialog: None,
+            mark_pattern_dialog: None,
+            mark_age_dialog: None,
+            chown_dialog: None,
+            search_dialog: None,
+            grep_dialog: None,
+            palette_dialog: None,
             delete_dialog: None,
             help_visible: false,
+            quit_confirm_visible: false,
             pending_edit: None,
+            pending_remote_shell: None,
             shell_dialog: None,
+            clipboard: None,
+            temp_cleanup_dialog: None,
+            sync_dialog: None,
+            info_dialog: None,
+            connection_test: None,
+            pending_connect: None,
             permission_dialog: None,
+            edit_conflict_dialog: None,
+            edit_upload_dialog: None,
+            edit_size_dialog: None,
             host_key_dialog: None,
             panels_swapped: false,
-            theme_choice: load_theme_choice(),
+            single_panel: false,
+            // https://no-color.org: any non-empty NO_COLOR disables colours.
+            no_color: settings.no_color
+                || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+            preset_password: None,
+            mark_advances: settings.mark_advances,
+            columns: settings.columns.clone(),
+            column_widths: settings.column_widths,
+            quick_connect: settings.quick_connect,
+            quick_profiles: Vec::new(),
+            quick_selected: 0,
+            safe_delete: settings.safe_delete,
+            remote_trash: settings.remote_trash,
+            exact_sizes: false,
+            local_permissions: false,
+            relative_dates: false,
+            compare_mode: false,
+            last_profile: None,
+            last_connect_attempt: None,
+            last_click: None,
+            remote_side,
+            theme_choice: settings.theme.as_deref().map(ThemeChoice::from_str).unwrap_or(ThemeChoice::Auto),
             local_watcher: None,
             local_watcher_rx: None,
             local_watched_path: None,
             last_remote_refresh: None,
+            pending_listing: None,
+            pending_free_space: None,
+            listing_cache: ListingCache::new(
+                settings.listing_cache_size,
+                std::time::Duration::from_secs(settings.listing_cache_ttl_secs),
+            ),
+            latency: None,
+            latency_failed: false,
+            last_latency_check: None,
+            settings,
         };
+        if let Some(e) = &app.settings.load_error {
+            app.set_status(format!("Einstellungen fehlerhaft, Standardwerte aktiv: {}", e));
+        }
         // Check profile config permissions on startup
-        match ProfileStore::load() {
-            Err(ConfigError::UnsafePermissions { path, mode }) => {
-                app.permission_dialog = Some(PermissionFixDialog { path, mode });
-            }
-            _ => {}
+        if let Err(ConfigError::UnsafePermissions { path, mode }) = ProfileStore::load() {
+            app.permission_dialog = Some(PermissionFixDialog { path, mode });
         }
+        app.refresh_quick_connect();
         app.start_local_watcher();
         ensure_themes();
         Ok(app)
     }
 
-    /// Register a non-recursive notify watcher on `self.left.path`.
+    /// Register a non-recursive notify watcher on the local panel's path.
     /// Drops any previous watcher first. Fails silently if the OS cannot
     /// create a watcher (e.g. inotify limit reached).
     pub fn start_local_watcher(&mut self) {
@@ -795,20 +2315,20 @@ impl App {
             Err(_) => return,
         };
         if watcher
-            .watch(self.left.path.as_path(), RecursiveMode::NonRecursive)
+            .watch(self.local().path.as_path(), RecursiveMode::NonRecursive)
             .is_ok()
         {
             self.local_watcher = Some(watcher);
             self.local_watcher_rx = Some(rx);
-            self.local_watched_path = Some(self.left.path.clone());
+            self.local_watched_path = Some(self.local().path.clone());
         }
     }
 
     /// Drain filesystem events and refresh the local panel if any arrived.
     /// Auto-restarts the watcher when the user has navigated to a new directory.
     pub fn poll_local_fs(&mut self) {
-        // Restart watcher if left.path changed since last watch registration.
-        if self.local_watched_path.as_deref() != Some(self.left.path.as_path()) {
+        // Restart watcher if the local path changed since last watch registration.
+        if self.local_watched_path.as_deref() != Some(self.local().path.as_path()) {
             self.start_local_watcher();
         }
 
@@ -835,7 +2355,7 @@ impl App {
 
         if got_event && !self.is_transferring() {
             // load_local() already clamps `selected` — no extra position save needed.
-            let _ = self.left.load_local();
+            let _ = self.local_mut().load_local();
         }
     }
 
@@ -843,7 +2363,7 @@ impl App {
     /// Skips when transferring or disconnected. Timer resets before the I/O
     /// call so a slow server cannot cause back-to-back list_dir() calls.
     pub fn poll_remote_refresh(&mut self) {
-        if self.is_transferring() || !self.is_connected() {
+        if self.is_transferring() || !self.is_connected() || self.pending_listing.is_some() {
             return;
         }
         let should_refresh = match self.last_remote_refresh {
@@ -855,31 +2375,301 @@ impl App {
         }
         // Reset timer before the I/O call to avoid rapid re-entry on slow servers.
         self.last_remote_refresh = Some(Instant::now());
-        let conn = match self.sftp.as_mut() {
-            Some(c) => c,
+        if let Some(path) = self.sftp.as_ref().map(|c| c.remote_path.clone()) {
+            // Transient errors are silently ignored to avoid status bar spam.
+            self.start_remote_listing(path, None, ListingKind::Refresh);
+        }
+    }
+
+    /// Change the remote panel to directory `path`. A cached listing is shown
+    /// right away and re-fetched in the background; otherwise the panel
+    /// keeps its contents until the background listing arrives.
+    /// `history` is Some(forward) for a step through the panel history.
+    fn navigate_remote(&mut self, path: PathBuf, history: Option<bool>) {
+        let Some(entries) = self.listing_cache.get(&path) else {
+            self.start_remote_listing(path, None, ListingKind::Load);
+            if let Some(pending) = self.pending_listing.as_mut() {
+                pending.history = history;
+            }
+            return;
+        };
+        let Some(conn) = self.sftp.as_mut() else {
+            return;
+        };
+        conn.remote_path = path.clone();
+        if let Some(forward) = history {
+            self.remote_mut().step_history(forward);
+        }
+        self.remote_mut().load_remote(path.clone(), entries);
+        self.update_remote_free_space();
+        self.start_remote_listing(path, None, ListingKind::Revalidate);
+    }
+
+    /// List remote directory `path` in the background; `poll_remote_listing`
+    /// loads it into the remote panel. Replaces a listing still in flight.
+    fn start_remote_listing(
+        &mut self,
+        path: PathBuf,
+        reselect: Option<(Option<String>, usize)>,
+        kind: ListingKind,
+    ) {
+        let Some(conn) = self.sftp.as_ref() else {
+            return;
+        };
+        let result = conn.list_dir_in_background(path.clone());
+        self.pending_listing = Some(PendingListing {
+            path,
+            from: conn.remote_path.clone(),
+            reselect,
+            kind,
+            history: None,
+            result,
+        });
+    }
+
+    /// Load a finished background listing into the remote panel.
+    /// Should be called once per render frame.
+    pub fn poll_remote_listing(&mut self) {
+        let outcome = match self.pending_listing.as_ref() {
+            Some(p) => p.result.lock().unwrap().take(),
             None => return,
         };
-        match conn.list_dir() {
+        let Some(outcome) = outcome else {
+            return;
+        };
+        let Some(pending) = self.pending_listing.take() else {
+            return;
+        };
+        let Some(conn) = self.sftp.as_mut() else {
+            return;
+        };
+        if conn.remote_path != pending.from {
+            return;
+        }
+        match outcome {
             Ok(entries) => {
-                let path = conn.remote_path.clone();
-                self.right.refresh_remote(path, entries);
+                let changed_dir = conn.remote_path != pending.path;
+                conn.remote_path = pending.path.clone();
+                self.listing_cache.insert(pending.path.clone(), entries.clone());
+                if pending.kind != ListingKind::Load {
+                    self.remote_mut().refresh_remote(pending.path, entries);
+                    if pending.kind == ListingKind::Sync {
+                        self.show_sync_plan();
+                    }
+                    return;
+                }
+                if let Some(forward) = pending.history {
+                    self.remote_mut().step_history(forward);
+                }
+                self.remote_mut().load_remote(pending.path, entries);
+                if let Some((keep, pos)) = pending.reselect {
+                    self.remote_mut().reselect_by_name(keep.as_deref(), pos);
+                }
+                if changed_dir {
+                    self.update_remote_free_space();
+                }
+            }
+            Err(_) if pending.kind == ListingKind::Refresh => {}
+            Err(e) => {
+                self.listing_cache.invalidate_tree(&pending.path);
+                self.set_status(if pending.path == pending.from {
+                    format!("Listing fehlgeschlagen: {}", e)
+                } else {
+                    format!("Verzeichnis öffnen fehlgeschlagen: {}", e)
+                });
             }
-            Err(_) => {} // Transient errors are silently ignored to avoid status bar spam.
         }
     }
 
-    pub fn active_panel_mut(&mut self) -> &mut PanelState {
-        match self.active {
-            ActivePanel::Left => &mut self.left,
-            ActivePanel::Right => &mut self.right,
+    /// Time a `stat` of the remote directory on a fixed interval for the
+    /// status bar indicator. Like the refresh it runs on the main session,
+    /// so the value is what the next panel operation will feel.
+    pub fn poll_latency(&mut self) {
+        let Some(conn) = self.sftp.as_ref() else {
+            return;
+        };
+        // A listing in flight holds the session between its calls and would
+        // inflate the measurement (and block this frame); try again later.
+        if self.pending_listing.is_some()
+            || self
+                .last_latency_check
+                .is_some_and(|last| last.elapsed() < LATENCY_INTERVAL)
+        {
+            return;
         }
+        let started = Instant::now();
+        let result = conn.sftp().stat(&conn.remote_path);
+        self.last_latency_check = Some(Instant::now());
+        self.latency_failed = result.is_err();
+        self.latency = result.ok().map(|_| started.elapsed());
+    }
+
+    pub fn active_panel_mut(&mut self) -> &mut PanelState {
+        self.panel_mut(self.active)
+    }
+
+    pub fn panel(&self, side: ActivePanel) -> &PanelState {
+        match side {
+            ActivePanel::Left => &self.left,
+            ActivePanel::Right => &self.right,
+        }
+    }
+
+    pub fn panel_mut(&mut self, side: ActivePanel) -> &mut PanelState {
+        match side {
+            ActivePanel::Left => &mut self.left,
+            ActivePanel::Right => &mut self.right,
+        }
+    }
+
+    /// Logical panel that holds the local listing.
+    pub fn local_side(&self) -> ActivePanel {
+        self.remote_side.toggle()
+    }
+
+    pub fn local(&self) -> &PanelState {
+        self.panel(self.local_side())
+    }
+
+    pub fn local_mut(&mut self) -> &mut PanelState {
+        self.panel_mut(self.local_side())
+    }
+
+    pub fn remote(&self) -> &PanelState {
+        self.panel(self.remote_side)
+    }
+
+    pub fn remote_mut(&mut self) -> &mut PanelState {
+        self.panel_mut(self.remote_side)
+    }
+
+    /// True when the focused panel is the one holding the remote listing.
+    pub fn is_remote_active(&self) -> bool {
+        self.active == self.remote_side
     }
 
     pub fn toggle_panel(&mut self) {
         self.active = self.active.toggle();
     }
 
+    /// Left click on `panel`: focus it and select the clicked row. A second
+    /// click on the same row within `DOUBLE_CLICK_TIMEOUT` opens the entry —
+    /// directories are entered, files edited (like Enter / F4).
+    pub fn mouse_click(&mut self, panel: ActivePanel, pos: Option<usize>) {
+        self.active = panel;
+        self.active_panel_mut().reset_type_ahead();
+        let Some(pos) = pos else {
+            self.last_click = None;
+            return;
+        };
+        self.active_panel_mut().select(pos);
+
+        let now = Instant::now();
+        let double = matches!(self.last_click, Some((at, p, row))
+            if p == panel && row == pos && now.duration_since(at) < DOUBLE_CLICK_TIMEOUT);
+        if !double {
+            self.last_click = Some((now, panel, pos));
+            return;
+        }
+        self.last_click = None;
+
+        let is_dir = match self.panel(panel).selected_entry() {
+            Some(e) => e.is_dir,
+            None => return,
+        };
+        if !is_dir {
+            self.prepare_edit();
+        } else if !self.is_remote_active() {
+            if let Err(e) = self.local_mut().enter_selected() {
+                self.set_status(e.to_string());
+            }
+        } else if self.is_connected() {
+            self.remote_enter_selected();
+        }
+    }
+
+    /// Scroll wheel over `panel`: move its cursor without changing focus.
+    pub fn mouse_scroll(&mut self, panel: ActivePanel, down: bool) {
+        let state = self.panel_mut(panel);
+        if down {
+            state.move_down();
+        } else {
+            state.move_up();
+        }
+    }
+
+    /// Show `text` in the status bar and record it in the status log, so
+    /// every message is logged even when another replaces it in the same frame.
+    pub fn set_status(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        self.push_log(text.clone());
+        self.status_message = Some(text);
+    }
+
+    /// Append `note` to the current status message and its log entry.
+    fn append_status(&mut self, note: &str) {
+        match self.status_message.as_mut() {
+            Some(msg) => {
+                msg.push_str(note);
+                if let Some(entry) = self.status_log.back_mut() {
+                    entry.text.push_str(note);
+                    entry.is_error = is_error_message(&entry.text);
+                }
+            }
+            None => self.set_status(note.trim_start_matches(" — ")),
+        }
+    }
+
+    /// Append one line to the status log, dropping the oldest when full.
+    fn push_log(&mut self, text: String) {
+        if self.status_log.len() >= STATUS_LOG_CAPACITY {
+            self.status_log.pop_front();
+        }
+        let time = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.status_log.push_back(LogEntry { time, is_error: is_error_message(&text), text });
+    }
+
+    /// Write what a connect to the current profile (or the last attempted
+    /// one) does to the status log: address, user, auth method and expanded
+    /// key path. For comparing a failing connect with a working `ssh` call.
+    pub fn log_connection_details(&mut self) {
+        let profile = match (self.sftp.as_ref(), self.last_connect_attempt.as_ref()) {
+            (Some(conn), _) => conn.profile.clone(),
+            (None, Some(profile)) => profile.clone(),
+            (None, None) => {
+                self.set_status("Noch kein Verbindungsversuch".to_string());
+                return;
+            }
+        };
+        self.push_log(format!("Verbindungsdetails für '{}':", profile.name));
+        for line in describe_connection(&profile) {
+            self.push_log(format!("  {}", line));
+        }
+        self.set_status(format!(
+            "Verbindungsdetails für '{}' im Meldungsverlauf (l)",
+            profile.name
+        ));
+    }
+
+    /// Open the status log scrolled to the newest entry.
+    pub fn open_log_dialog(&mut self) {
+        self.log_dialog = Some(LogDialog { selected: self.status_log.len().saturating_sub(1) });
+    }
+
+    /// Quit, but ask first while a transfer is running — leaving would abort it.
+    pub fn request_quit(&mut self) {
+        if self.is_transferring() {
+            self.quit_confirm_visible = true;
+        } else {
+            self.quit();
+        }
+    }
+
     pub fn quit(&mut self) {
+        self.remember_session();
         // Explicitly drop the SFTP connection before exiting so the SSH
         // session is cleanly closed (ssh2 sends a disconnect packet on drop).
         self.sftp = None;
@@ -888,11 +2678,53 @@ impl App {
 
     pub fn open_profile_dialog(&mut self) {
         let store = ProfileStore::load().unwrap_or_default();
-        self.profile_dialog = Some(ProfileDialog::new(store));
+        let sort = self.settings.profile_sort;
+        self.profile_dialog = Some(ProfileDialog::new(store, sort));
     }
 
     pub fn close_profile_dialog(&mut self) {
         self.profile_dialog = None;
+        // Profiles may have been added, renamed or deleted.
+        self.refresh_quick_connect();
+    }
+
+    /// Reload the quick-connect list of the disconnected remote panel: the
+    /// most recently used profiles first, never-used ones after them in file
+    /// order, at most `QUICK_CONNECT_MAX`.
+    pub fn refresh_quick_connect(&mut self) {
+        if !self.quick_connect {
+            return;
+        }
+        let mut profiles = ProfileStore::load().unwrap_or_default().profiles;
+        profiles.sort_by_key(|p| std::cmp::Reverse(p.last_used));
+        profiles.truncate(QUICK_CONNECT_MAX);
+        self.quick_selected = self.quick_selected.min(profiles.len().saturating_sub(1));
+        self.quick_profiles = profiles;
+    }
+
+    /// True while the remote panel shows the quick-connect list instead of a listing.
+    pub fn quick_connect_shown(&self) -> bool {
+        self.quick_connect && !self.is_connected() && !self.quick_profiles.is_empty()
+    }
+
+    /// Move the quick-connect highlight one row up or down.
+    pub fn quick_connect_move(&mut self, down: bool) {
+        let max = self.quick_profiles.len().saturating_sub(1);
+        self.quick_selected = if down {
+            (self.quick_selected + 1).min(max)
+        } else {
+            self.quick_selected.saturating_sub(1)
+        };
+    }
+
+    /// Connect with the highlighted quick-connect profile (Enter).
+    pub fn quick_connect_selected(&mut self) {
+        if self.pending_connect.is_some() {
+            return;
+        }
+        if let Some(profile) = self.quick_profiles.get(self.quick_selected).cloned() {
+            self.begin_connect(profile);
+        }
     }
 
     /// Open the permission fix dialog with path and current mode.
@@ -921,6 +2753,10 @@ impl App {
     pub fn begin_connect(&mut self, profile: Profile) {
         match profile.auth {
             AuthMethod::Password => {
+                if let Some((_, pw)) = self.preset_password.take_if(|(name, _)| *name == profile.name) {
+                    self.do_connect(profile, Some(&pw));
+                    return;
+                }
                 if profile.has_saved_password {
                     if let Ok(Some(pw)) =
                         crate::config::profiles::load_password(&profile.name)
@@ -937,15 +2773,141 @@ impl App {
         }
     }
 
-    /// Perform the actual SFTP connect (called after password is entered or for key auth).
+    /// Check that `profile` can connect without touching the panels. A
+    /// password from the profile form is used as given; otherwise password
+    /// profiles take the keychain entry or ask via the password dialog.
+    pub fn test_connection(&mut self, profile: Profile, password: Option<String>) {
+        if self.connection_test.is_some() {
+            self.set_status("Verbindungstest läuft bereits".to_string());
+            return;
+        }
+        if profile.auth == AuthMethod::Password && password.is_none() {
+            if profile.has_saved_password {
+                if let Ok(Some(pw)) = crate::config::profiles::load_password(&profile.name) {
+                    self.start_connection_test(profile, Some(pw));
+                    return;
+                }
+            }
+            let mut dlg = PasswordDialog::new(profile);
+            dlg.test_only = true;
+            self.password_dialog = Some(dlg);
+            return;
+        }
+        self.start_connection_test(profile, password);
+    }
+
+    /// Run `SftpConnection::connect` on a background thread and drop the
+    /// session again; `poll_connection_test` reports the outcome.
+    pub fn start_connection_test(&mut self, profile: Profile, password: Option<String>) {
+        let result = Arc::new(Mutex::new(None));
+        let result_clone = Arc::clone(&result);
+        let profile_name = profile.name.clone();
+        let password = password.map(Zeroizing::new);
+        std::thread::spawn(move || {
+            let outcome = SftpConnection::connect(&profile, password.as_ref().map(|z| z.as_str()))
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+            *result_clone.lock().unwrap() = Some(outcome);
+        });
+        self.set_status(format!("Teste Verbindung zu '{}'…", profile_name));
+        self.connection_test = Some(ConnectionTest { profile_name, started: Instant::now(), result });
+    }
+
+    /// Report the result of a running connection test.
+    /// Should be called once per render frame.
+    pub fn poll_connection_test(&mut self) {
+        let Some(test) = self.connection_test.as_ref() else {
+            return;
+        };
+        let outcome = test.result.lock().unwrap().take();
+        match outcome {
+            // The status bar animates the spinner itself while the test runs.
+            None => {}
+            Some(Ok(())) => {
+                self.set_status(format!("Verbindungstest '{}' erfolgreich", test.profile_name));
+                self.connection_test = None;
+            }
+            Some(Err(e)) => {
+                self.set_status(format!("Verbindungstest '{}' fehlgeschlagen: {}", test.profile_name, e));
+                self.connection_test = None;
+            }
+        }
+    }
+
+    /// Start the SFTP connect on a background thread (called after the
+    /// password is entered or for key auth). `poll_connect` finishes it.
     pub fn do_connect(&mut self, profile: Profile, password: Option<&str>) {
-        match SftpConnection::connect(&profile, password) {
+        if self.pending_connect.is_some() {
+            return;
+        }
+        let result: ConnectSlot = Arc::new(Mutex::new(None));
+        let result_clone = Arc::clone(&result);
+        let password = password.map(|p| Zeroizing::new(p.to_string()));
+        let worker_profile = profile.clone();
+        let worker_password = password.clone();
+        self.last_connect_attempt = Some(profile.clone());
+        std::thread::spawn(move || {
+            let outcome = SftpConnection::connect(
+                &worker_profile,
+                worker_password.as_ref().map(|z| z.as_str()),
+            );
+            *result_clone.lock().unwrap() = Some(outcome);
+        });
+        self.pending_connect = Some(PendingConnect {
+            profile,
+            password,
+            started: Instant::now(),
+            result,
+        });
+    }
+
+    /// Hand a finished background connect over to the panels.
+    /// Should be called once per render frame.
+    pub fn poll_connect(&mut self) {
+        let outcome = match self.pending_connect.as_ref() {
+            Some(p) => p.result.lock().unwrap().take(),
+            None => return,
+        };
+        let Some(outcome) = outcome else {
+            return;
+        };
+        if let Some(PendingConnect { profile, password, .. }) = self.pending_connect.take() {
+            self.finish_connect(profile, password.as_ref().map(|z| z.as_str()), outcome);
+        }
+    }
+
+    /// Abandon the running connect (Esc on the overlay).
+    pub fn cancel_connect(&mut self) {
+        if let Some(pending) = self.pending_connect.take() {
+            self.set_status(format!("Verbindungsaufbau zu {} abgebrochen", pending.target()));
+        }
+    }
+
+    /// Apply the outcome of a connect: load the listings on success, show the
+    /// host key dialog or the error otherwise.
+    fn finish_connect(
+        &mut self,
+        profile: Profile,
+        password: Option<&str>,
+        outcome: Result<SftpConnection, SftpError>,
+    ) {
+        match outcome {
             Ok(mut conn) => {
-                // If the profile specifies a start directory, navigate there first.
+                record_profile_use(&profile.name);
+                self.listing_cache.clear();
+                self.last_profile = Some(profile.clone());
+                let remember = self.password_dialog.as_ref().is_some_and(|d| d.remember);
+                // If the profile specifies a start directory, navigate there first;
+                // otherwise resume in the directory of the last session.
                 // change_to_absolute returns the new listing directly — use it to
                 // avoid a second round-trip and correctly set the panel path.
+                let start_path = profile
+                    .remote_path
+                    .clone()
+                    .filter(|p| !p.trim().is_empty())
+                    .or_else(|| SessionState::load().remote_paths.get(&profile.name).cloned());
                 let (list_result, connected_msg) =
-                    if let Some(ref start_path) = profile.remote_path {
+                    if let Some(ref start_path) = start_path {
                         let trimmed = start_path.trim();
                         if !trimmed.is_empty() {
                             match conn.change_to_absolute(trimmed) {
@@ -979,46 +2941,47 @@ impl App {
                 match list_result {
                     Ok(entries) => {
                         let path = conn.remote_path.clone();
-                        self.right.load_remote(path, entries);
-                        self.status_message = Some(connected_msg);
+                        self.remote_mut().load_remote(path, entries);
+                        self.set_status(connected_msg);
                         self.sftp = Some(conn);
                         self.password_dialog = None;
+                        self.update_remote_free_space();
                     }
                     Err(e) => {
-                        self.status_message =
-                            Some(format!("Verbindung ok, Listing fehlgeschlagen: {}", e));
+                        self.set_status(format!("Verbindung ok, Listing fehlgeschlagen: {}", e));
                         self.sftp = Some(conn);
                         self.password_dialog = None;
                     }
                 }
 
                 // If the profile specifies a local start directory, navigate
-                // the left panel there (only if the path exists).
+                // the local panel there (only if the path exists).
                 if let Some(ref local_path) = profile.local_start_path {
                     let trimmed = local_path.trim();
                     if !trimmed.is_empty() {
-                        let expanded = if trimmed == "~" || trimmed.starts_with("~/") {
-                            let home = dirs_or_cwd();
-                            if trimmed == "~" {
-                                home
-                            } else {
-                                home.join(&trimmed[2..])
-                            }
-                        } else {
-                            PathBuf::from(trimmed)
-                        };
+                        let expanded = expand_local_path(trimmed);
                         if expanded.is_dir() {
-                            self.left.path = expanded;
-                            self.left.selected = 0;
-                            if let Err(e) = self.left.load_local() {
-                                if let Some(ref mut msg) = self.status_message {
-                                    msg.push_str(&format!(" | Lok. Startpfad fehlgeschlagen: {}", e));
+                            self.local_mut().path = expanded;
+                            self.local_mut().selected = 0;
+                            if let Err(e) = self.local_mut().load_local() {
+                                if self.status_message.is_some() {
+                                    self.append_status(&format!(" | Lok. Startpfad fehlgeschlagen: {}", e));
                                 }
                             }
                         }
                         // Path doesn't exist → silently keep the current local directory.
                     }
                 }
+
+                self.active = if profile.focus_remote {
+                    self.remote_side
+                } else {
+                    self.local_side()
+                };
+
+                if let (true, Some(pw)) = (remember, password) {
+                    self.remember_password(&profile.name, pw);
+                }
             }
             Err(SftpError::UnknownHostKey { host, port, fingerprint, key_type, key_bytes }) => {
                 self.host_key_dialog = Some(HostKeyDialog {
@@ -1035,8 +2998,45 @@ impl App {
                 if let Some(ref mut dlg) = self.password_dialog {
                     dlg.error = Some(e.to_string());
                 } else {
-                    self.status_message = Some(format!("Verbindung fehlgeschlagen: {}", e));
+                    self.set_status(format!("Verbindung fehlgeschlagen: {}", e));
+                }
+            }
+        }
+    }
+
+    /// Store `password` in the keychain and flag the profile, appending the
+    /// outcome to the status message.
+    fn remember_password(&mut self, profile_name: &str, password: &str) {
+        let note = match crate::config::profiles::save_password(profile_name, password) {
+            Ok(()) => {
+                if let Ok(mut store) = ProfileStore::load() {
+                    store.set_saved_password(profile_name, true);
+                    let _ = store.save();
+                }
+                " — Passwort im Keychain gespeichert".to_string()
+            }
+            Err(e) => format!(" — Keychain-Fehler: {}", e),
+        };
+        self.append_status(&note);
+    }
+
+    /// Remove the keychain password of `profile_name` ('F' in the profile list).
+    pub fn forget_password(&mut self, profile_name: &str) {
+        match crate::config::profiles::delete_password(profile_name) {
+            Ok(()) => {
+                if let Some(d) = self.profile_dialog.as_mut() {
+                    if let Some(p) = d.store.profiles.iter_mut().find(|p| p.name == profile_name) {
+                        p.has_saved_password = false;
+                    }
+                    if let Err(e) = d.save() {
+                        self.set_status(format!("Speichern fehlgeschlagen: {}", e));
+                        return;
+                    }
                 }
+                self.set_status(format!("Gespeichertes Passwort für '{}' entfernt", profile_name));
+            }
+            Err(e) => {
+                self.set_status(format!("Keychain-Fehler: {}", e));
             }
         }
     }
@@ -1049,7 +3049,7 @@ impl App {
                     self.do_connect(dlg.profile, dlg.password.as_deref());
                 }
                 Err(e) => {
-                    self.status_message = Some(format!("known_hosts schreiben fehlgeschlagen: {}", e));
+                    self.set_status(format!("known_hosts schreiben fehlgeschlagen: {}", e));
                 }
             }
         }
@@ -1058,15 +3058,77 @@ impl App {
     /// Dismiss the host key dialog without connecting.
     pub fn abort_host_key(&mut self) {
         self.host_key_dialog = None;
-        self.status_message = Some("Verbindung abgebrochen (unbekannter Host-Key)".to_string());
+        self.set_status("Verbindung abgebrochen (unbekannter Host-Key)".to_string());
     }
 
-    /// Disconnect the active SFTP session and clear the right panel.
+    /// Disconnect the active SFTP session and clear the remote panel.
     pub fn disconnect(&mut self) {
+        self.remember_session();
         self.sftp = None;
+        self.pending_listing = None;
+        self.pending_free_space = None;
+        self.listing_cache.clear();
+        self.clipboard = None;
         let home = dirs_or_cwd();
-        self.right = PanelState::new(home);
-        self.status_message = Some("Verbindung getrennt".to_string());
+        let show_hidden = self.remote().show_hidden;
+        *self.remote_mut() = PanelState::new(home);
+        self.remote_mut().set_show_hidden(show_hidden);
+        // The session just updated `last_used` of its profile.
+        self.quick_selected = 0;
+        self.refresh_quick_connect();
+        self.latency = None;
+        self.latency_failed = false;
+        self.last_latency_check = None;
+        let dropped = std::mem::take(&mut self.transfer_queue).len();
+        self.set_status(if dropped > 0 {
+            format!(
+                "Verbindung getrennt — {} wartende Übertragungen verworfen — Shift+F3 verbindet erneut",
+                dropped
+            )
+        } else {
+            "Verbindung getrennt — Shift+F3 verbindet erneut".to_string()
+        });
+    }
+
+    /// Connect again with the profile of the last session (Shift+F3).
+    pub fn reconnect_last(&mut self) {
+        if self.is_connected() || self.pending_connect.is_some() {
+            return;
+        }
+        match self.last_profile.clone() {
+            Some(profile) => self.begin_connect(profile),
+            None => {
+                self.set_status("Noch keine Verbindung in dieser Sitzung".to_string());
+            }
+        }
+    }
+
+    /// Type-ahead jump in the active panel; shows the prefix in the status bar.
+    pub fn type_ahead(&mut self, c: char) {
+        let prefix = self.active_panel_mut().type_ahead(c).to_string();
+        self.set_status(format!("Springe zu: {}", prefix));
+    }
+
+    /// Persist the local directory and, when connected, the remote directory
+    /// of the current profile so the next session starts there.
+    /// Write `settings` back to settings.toml. A file that failed to load is
+    /// left alone; the status bar says so instead.
+    pub fn save_settings(&mut self) {
+        if !self.settings.save() {
+            self.set_status("Einstellungen nicht gespeichert: settings.toml ist fehlerhaft".to_string());
+        }
+    }
+
+    fn remember_session(&self) {
+        let mut state = SessionState::load();
+        state.local_path = Some(self.local().path.clone());
+        if let Some(conn) = self.sftp.as_ref() {
+            state.remote_paths.insert(
+                conn.profile.name.clone(),
+                conn.remote_path.to_string_lossy().to_string(),
+            );
+        }
+        state.save();
     }
 
     pub fn is_connected(&self) -> bool {
@@ -1083,82 +3145,202 @@ impl App {
         self.download_progress.is_some()
     }
 
-    /// Returns true if any transfer (upload or download) is running.
+    /// Returns true if any transfer (upload, download, remote copy or
+    /// background delete) is running.
     pub fn is_transferring(&self) -> bool {
-        self.is_uploading() || self.is_downloading()
+        self.is_uploading()
+            || self.is_downloading()
+            || self.copy_progress.is_some()
+            || self.delete_progress.is_some()
     }
 
-    /// Start uploading the marked left-panel entries (or the highlighted entry
-    /// when nothing is marked) to the current remote directory.
-    /// Does nothing when not connected or an upload is already running.
+    /// Start uploading the marked local-panel entries (or the highlighted entry
+    /// when nothing is marked) to the current remote directory; queued behind
+    /// a running transfer. Does nothing when not connected.
     pub fn start_upload(&mut self) {
-        if !self.is_connected() || self.is_uploading() {
+        if !self.is_connected() {
             return;
         }
 
         // Build the list of entries to upload.
-        let entries: Vec<FileEntry> = if self.left.marked.is_empty() {
-            // No marks → upload the single highlighted entry.
-            match self.left.entries.get(self.left.selected) {
-                Some(e) if e.name != ".." => vec![e.clone()],
-                _ => return,
-            }
-        } else {
-            // Upload all marked entries (sorted by index for consistency).
-            let mut indices: Vec<usize> = self.left.marked.iter().cloned().collect();
-            indices.sort_unstable();
-            indices
-                .iter()
-                .filter_map(|&i| self.left.entries.get(i))
-                .filter(|e| e.name != "..")
-                .cloned()
-                .collect()
-        };
+        // Marked entries, or the single highlighted entry when nothing is marked.
+        let entries: Vec<FileEntry> =
+            self.local().target_entries().into_iter().cloned().collect();
 
         if entries.is_empty() {
             return;
         }
+        self.upload_entries(entries, None);
+    }
 
-        let remote_dir = self.right.path.clone();
-        let base_path = self.left.path.clone();
+    /// Open the upload-as dialog for the single local file the upload would
+    /// apply to. Directories and multiple marked entries are rejected.
+    pub fn open_upload_as_dialog(&mut self) {
+        if !self.is_connected() {
+            return;
+        }
+        if self.is_remote_active() {
+            self.set_status("Upload unter Namen nur im lokalen Panel".to_string());
+            return;
+        }
+        let name = match self.local().target_entries().as_slice() {
+            [entry] if !entry.is_dir => entry.name.clone(),
+            _ => {
+                self.set_status("Upload unter Namen nur für eine einzelne Datei".to_string());
+                return;
+            }
+        };
+        self.upload_as_dialog = Some(UploadAsDialog::new(name));
+    }
 
-        let (profile, saved_pw) = match &self.sftp {
-            Some(conn) => (conn.profile.clone(), conn.saved_password.clone()),
+    /// Upload the file from the upload-as dialog under the entered name.
+    pub fn confirm_upload_as(&mut self) {
+        let dlg = match self.upload_as_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let dest = dlg.input.buf.trim().to_string();
+        if dest.is_empty() {
+            return;
+        }
+        if dest.contains('/') || dest == "." || dest == ".." {
+            self.set_status(format!("Ungültiger Dateiname: '{}'", dest));
+            return;
+        }
+        let entry = match self.local().entries.iter().find(|e| e.name == dlg.original) {
+            Some(e) => e.clone(),
             None => return,
         };
+        self.upload_entries(vec![entry], Some(dest));
+    }
 
-        // Count total files across all entries for the progress bar.
-        let total_files: usize = entries
-            .iter()
-            .map(|e| count_files(&base_path.join(&e.name)))
-            .sum::<usize>()
-            .max(1);
+    /// Upload `entries` of the local directory to the current remote
+    /// directory, now or after the running transfer. A `dest_name` renames
+    /// the (single, regular) file on the server.
+    fn upload_entries(&mut self, entries: Vec<FileEntry>, dest_name: Option<String>) {
+        let job = TransferJob::Upload {
+            entries,
+            local_dir: self.local().path.clone(),
+            remote_dir: self.remote().path.clone(),
+            dest_name,
+        };
+        self.submit_transfer(job);
+        // Clear marks once the upload is started or queued.
+        self.local_mut().clear_marks();
+    }
 
-        let handle: ProgressHandle =
-            Arc::new(Mutex::new(UploadProgress::new(total_files)));
-        let handle_clone = Arc::clone(&handle);
+    /// Start `job` right away, or queue it while another transfer runs.
+    fn submit_transfer(&mut self, job: TransferJob) {
+        if self.is_transferring() {
+            let label = job.label();
+            let (files, open_ended) = match &job {
+                TransferJob::Upload { entries, local_dir, .. } => (
+                    entries.iter().map(|e| count_files(&local_dir.join(&e.raw_name))).sum(),
+                    false,
+                ),
+                TransferJob::Download { entries, .. } => (
+                    entries.len(),
+                    entries.iter().any(|e| e.is_dir),
+                ),
+            };
+            self.transfer_queue.push_back(QueuedJob { job, files, open_ended });
+            self.set_status(format!(
+                "{} in Warteschlange ({} wartend)",
+                label,
+                self.transfer_queue.len()
+            ));
+            return;
+        }
+        self.start_transfer(job);
+    }
 
-        let label = if entries.len() == 1 {
-            format!("'{}'", entries[0].name)
-        } else {
-            format!("{} Dateien", entries.len())
-        };
+    /// Start the next queued transfer once nothing else is running.
+    /// Should be called once per render frame.
+    pub fn poll_transfer_queue(&mut self) {
+        if self.is_transferring() {
+            return;
+        }
+        match self.transfer_queue.pop_front() {
+            Some(queued) => self.start_transfer(queued.job),
+            None => {
+                self.queue_jobs_done = 0;
+                self.queue_files_done = 0;
+            }
+        }
+    }
 
-        std::thread::spawn(move || {
-            upload_batch(
-                profile,
-                saved_pw,
-                entries,
-                base_path,
-                remote_dir,
-                handle_clone,
-            );
-        });
+    /// Overall position of the queue run for the transfer bar.
+    pub fn queue_progress(&self) -> QueueProgress {
+        QueueProgress {
+            jobs_done: self.queue_jobs_done,
+            jobs_waiting: self.transfer_queue.len(),
+            files_done: self.queue_files_done,
+            files_waiting: self.transfer_queue.iter().map(|q| q.files).sum(),
+            open_ended: self.transfer_queue.iter().any(|q| q.open_ended),
+        }
+    }
 
-        self.upload_progress = Some(handle);
-        self.status_message = Some(format!("Uploading {}…", label));
-        // Clear marks after starting the upload.
-        self.left.clear_marks();
+    /// Spawn the worker thread for `job` on a session of its own.
+    fn start_transfer(&mut self, job: TransferJob) {
+        let (profile, saved_pw) = match &self.sftp {
+            Some(conn) => (conn.profile.clone(), conn.saved_password.clone()),
+            None => return,
+        };
+        let options = TransferOptions::from_settings(&self.settings);
+        let label = job.label();
+        let waiting = match self.transfer_queue.len() {
+            0 => String::new(),
+            n => format!(" ({} wartend)", n),
+        };
+        match job {
+            TransferJob::Upload { entries, local_dir, remote_dir, dest_name } => {
+                // Count total files across all entries for the progress bar.
+                let total_files: usize = entries
+                    .iter()
+                    .map(|e| count_files(&local_dir.join(&e.raw_name)))
+                    .sum::<usize>()
+                    .max(1);
+                let handle: ProgressHandle =
+                    Arc::new(Mutex::new(UploadProgress::new(total_files)));
+                let handle_clone = Arc::clone(&handle);
+                self.upload_remote_dir = Some(remote_dir.clone());
+                std::thread::spawn(move || {
+                    upload_batch(
+                        profile,
+                        saved_pw,
+                        entries,
+                        local_dir,
+                        remote_dir,
+                        dest_name,
+                        options,
+                        handle_clone,
+                    );
+                });
+                self.upload_progress = Some(handle);
+                self.set_status(format!("Uploading {}…{}", label, waiting));
+            }
+            TransferJob::Download { entries, remote_dir, local_dir } => {
+                // Start with files_total = 1 so the bar shows activity immediately.
+                // download_batch will update files_total once it has counted via the
+                // same session (no extra connection needed).
+                let handle: TransferHandle =
+                    Arc::new(Mutex::new(TransferProgress::new(1)));
+                let handle_clone = Arc::clone(&handle);
+                std::thread::spawn(move || {
+                    download_batch(
+                        profile,
+                        saved_pw,
+                        entries,
+                        remote_dir,
+                        local_dir,
+                        options,
+                        handle_clone,
+                    );
+                });
+                self.download_progress = Some(handle);
+                self.set_status(format!("Downloading {}…{}", label, waiting));
+            }
+        }
     }
 
     /// Handle a bracketed-paste event: if the pasted text contains valid local
@@ -1169,11 +3351,7 @@ impl App {
             return;
         }
         if !self.is_connected() {
-            self.status_message = Some("Kein Server verbunden — Drag & Drop nicht möglich".to_string());
-            return;
-        }
-        if self.is_transferring() {
-            self.status_message = Some("Transfer läuft bereits".to_string());
+            self.set_status("Kein Server verbunden — Drag & Drop nicht möglich".to_string());
             return;
         }
         self.start_upload_from_paths(paths);
@@ -1183,16 +3361,10 @@ impl App {
     /// Reuses the existing upload infrastructure; the remote filename is the
     /// basename of each dropped path.
     pub fn start_upload_from_paths(&mut self, paths: Vec<PathBuf>) {
-        if paths.is_empty() || !self.is_connected() || self.is_uploading() {
+        if paths.is_empty() || !self.is_connected() {
             return;
         }
 
-        let remote_dir = self.right.path.clone();
-        let (profile, saved_pw) = match &self.sftp {
-            Some(conn) => (conn.profile.clone(), conn.saved_password.clone()),
-            None => return,
-        };
-
         // Build FileEntry list. We set name = full absolute path and base = "/".
         // upload_batch does `base.join(name)`; on Unix joining an absolute path
         // replaces the base, so the result is the original full path.
@@ -1201,157 +3373,187 @@ impl App {
             .iter()
             .map(|p| FileEntry {
                 name: p.to_string_lossy().to_string(),
+                raw_name: p.clone().into_os_string(),
                 is_dir: p.is_dir(),
                 size: None,
                 modified: None,
                 permissions: None,
+                is_symlink: false,
+                link_target: None,
             })
             .collect();
 
-        let total_files = paths.iter().map(|p| count_files(p)).sum::<usize>().max(1);
-        let handle: ProgressHandle = Arc::new(Mutex::new(UploadProgress::new(total_files)));
-        let handle_clone = Arc::clone(&handle);
-
-        let label = if paths.len() == 1 {
-            paths[0]
-                .file_name()
-                .map(|n| format!("'{}'", n.to_string_lossy()))
-                .unwrap_or_else(|| paths[0].to_string_lossy().to_string())
-        } else {
-            format!("{} Dateien", paths.len())
-        };
-
-        std::thread::spawn(move || {
-            upload_batch(profile, saved_pw, entries, base_path, remote_dir, handle_clone);
+        self.submit_transfer(TransferJob::Upload {
+            entries,
+            local_dir: base_path,
+            remote_dir: self.remote().path.clone(),
+            dest_name: None,
         });
-
-        self.upload_progress = Some(handle);
-        self.status_message = Some(format!("Uploading {}…", label));
     }
 
     /// Poll the upload handle; refresh remote listing on completion.
     /// Should be called once per render frame.
     pub fn poll_upload(&mut self) {
-        let state = match &self.upload_progress {
-            Some(h) => h.lock().unwrap().state.clone(),
+        let (state, verified, skipped, done, total) = match &self.upload_progress {
+            Some(h) => {
+                let prog = h.lock().unwrap();
+                (prog.state.clone(), prog.files_verified, prog.files_skipped, prog.files_done, prog.files_total)
+            }
             None => return,
         };
+        if state == UploadState::Running {
+            return;
+        }
+        self.upload_progress = None;
+        self.queue_jobs_done += 1;
+        self.queue_files_done += done;
+        // Even a failed upload may have written part of the tree.
+        if let Some(dir) = self.upload_remote_dir.take() {
+            self.listing_cache.invalidate_tree(&dir);
+        }
         match state {
             UploadState::Running => {}
             UploadState::Done => {
-                self.upload_progress = None;
-                self.status_message = Some("Upload abgeschlossen".to_string());
-                // Refresh the remote listing
-                if let Some(conn) = self.sftp.as_mut() {
-                    match conn.list_dir() {
-                        Ok(entries) => {
-                            let path = conn.remote_path.clone();
-                            self.right.load_remote(path, entries);
-                        }
-                        Err(e) => {
-                            self.status_message =
-                                Some(format!("Remote-Aktualisierung fehlgeschlagen: {}", e));
-                        }
-                    }
+                let mut msg = if verified > 0 {
+                    format!("Upload abgeschlossen — {} verifiziert", verified)
+                } else {
+                    "Upload abgeschlossen".to_string()
+                };
+                if skipped > 0 {
+                    msg.push_str(&format!(" — {} Spezialdateien übersprungen", skipped));
                 }
+                announce_transfer(&self.settings, &format!("Upload abgeschlossen: {} Dateien", done));
+                self.set_status(msg);
+                self.reload_side(PanelSide::Remote);
+                self.update_remote_free_space();
             }
             UploadState::Failed(msg) => {
-                self.upload_progress = None;
-                self.status_message = Some(format!("Upload fehlgeschlagen: {}", msg));
+                announce_transfer(&self.settings, &format!("Upload fehlgeschlagen nach {}/{} Dateien: {}", done, total, msg));
+                self.set_status(format!("Upload fehlgeschlagen: {}", msg));
             }
         }
     }
 
-    /// Start downloading the marked right-panel entries (or the highlighted entry
-    /// when nothing is marked) to the local directory.
-    /// Does nothing when not connected or a transfer is already running.
+    /// Start downloading the marked remote-panel entries (or the highlighted entry
+    /// when nothing is marked) to the local directory; queued behind a running
+    /// transfer. Does nothing when not connected.
     pub fn start_download(&mut self) {
-        if !self.is_connected() || self.is_transferring() {
+        if !self.is_connected() {
             return;
         }
 
         // Build the list of entries to download.
-        let entries: Vec<FileEntry> = if self.right.marked.is_empty() {
-            match self.right.entries.get(self.right.selected) {
-                Some(e) if e.name != ".." => vec![e.clone()],
-                _ => return,
-            }
-        } else {
-            let mut indices: Vec<usize> = self.right.marked.iter().cloned().collect();
-            indices.sort_unstable();
-            indices
-                .iter()
-                .filter_map(|&i| self.right.entries.get(i))
-                .filter(|e| e.name != "..")
-                .cloned()
-                .collect()
-        };
+        let entries: Vec<FileEntry> =
+            self.remote().target_entries().into_iter().cloned().collect();
 
         if entries.is_empty() {
             return;
         }
 
-        let local_dir = self.left.path.clone();
-        let remote_dir = self.right.path.clone();
-
-        let (profile, saved_pw) = match &self.sftp {
-            Some(conn) => (conn.profile.clone(), conn.saved_password.clone()),
-            None => return,
-        };
-
-        // Start with files_total = 1 so the bar shows activity immediately.
-        // download_batch will update files_total once it has counted via the
-        // same session (no extra connection needed).
-        let handle: TransferHandle =
-            Arc::new(Mutex::new(TransferProgress::new(1)));
-        let handle_clone = Arc::clone(&handle);
-
-        let label = if entries.len() == 1 {
-            format!("'{}'", entries[0].name)
-        } else {
-            format!("{} Dateien", entries.len())
-        };
-
-        std::thread::spawn(move || {
-            download_batch(
-                profile,
-                saved_pw,
-                entries,
-                remote_dir,
-                local_dir,
-                handle_clone,
-            );
-        });
-
-        self.download_progress = Some(handle);
-        self.status_message = Some(format!("Downloading {}…", label));
-        // Clear marks after starting the download.
-        self.right.clear_marks();
-    }
+        let job = TransferJob::Download {
+            entries,
+            remote_dir: self.remote().path.clone(),
+            local_dir: self.local().path.clone(),
+        };
+        self.submit_transfer(job);
+        // Clear marks once the download is started or queued.
+        self.remote_mut().clear_marks();
+    }
 
     /// Poll the download handle; refresh local listing on completion.
     /// Should be called once per render frame.
     pub fn poll_download(&mut self) {
-        let state = match &self.download_progress {
-            Some(h) => h.lock().unwrap().state.clone(),
+        let (state, resumed, done, total) = match &self.download_progress {
+            Some(h) => {
+                let prog = h.lock().unwrap();
+                (prog.state.clone(), prog.files_resumed, prog.files_done, prog.files_total)
+            }
             None => return,
         };
         match state {
             TransferState::Running => {}
             TransferState::Done => {
                 self.download_progress = None;
-                self.status_message = Some("Download abgeschlossen".to_string());
+                self.queue_jobs_done += 1;
+                self.queue_files_done += done;
+                announce_transfer(&self.settings, &format!("Download abgeschlossen: {} Dateien", done));
+                self.set_status(if resumed > 0 {
+                    format!("Download abgeschlossen ({} fortgesetzt)", resumed)
+                } else {
+                    "Download abgeschlossen".to_string()
+                });
                 // Refresh local listing so the new file appears immediately
-                if let Err(e) = self.left.load_local() {
-                    self.status_message =
-                        Some(format!("Lokale Aktualisierung fehlgeschlagen: {}", e));
+                let (keep, pos) = self.local().cursor_snapshot();
+                if let Err(e) = self.local_mut().load_local() {
+                    self.set_status(format!("Lokale Aktualisierung fehlgeschlagen: {}", e));
                 }
+                self.local_mut().reselect_by_name(keep.as_deref(), pos);
             }
             TransferState::Failed(msg) => {
                 self.download_progress = None;
-                self.status_message = Some(format!("Download fehlgeschlagen: {}", msg));
+                self.queue_jobs_done += 1;
+                self.queue_files_done += done;
+                announce_transfer(&self.settings, &format!("Download fehlgeschlagen nach {}/{} Dateien: {}", done, total, msg));
+                self.set_status(format!("Download fehlgeschlagen: {}", msg));
+            }
+        }
+    }
+
+    /// Map the focused panel to the listing it shows.
+    /// Returns None for the remote side while disconnected.
+    fn active_side(&self) -> Option<PanelSide> {
+        if !self.is_remote_active() {
+            Some(PanelSide::Local)
+        } else if self.is_connected() {
+            Some(PanelSide::Remote)
+        } else {
+            None
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Refresh (Ctrl+R)
+    // -----------------------------------------------------------------------
+
+    /// Reload the active panel. The cursor stays on the same entry; marks
+    /// survive only when the set of names did not change.
+    pub fn refresh_active_panel(&mut self) {
+        let side = match self.active_side() {
+            Some(side) => side,
+            None => return,
+        };
+        let panel = self.panel(self.active);
+        let names_before: HashSet<String> = panel.entries.iter().map(|e| e.name.clone()).collect();
+        let marked_names = panel.marked.clone();
+        let (keep, pos) = panel.cursor_snapshot();
+
+        let reloaded = match side {
+            PanelSide::Local => self.local_mut().load_local().map_err(|e| e.to_string()),
+            PanelSide::Remote => {
+                // This listing is newer than any still in flight.
+                self.pending_listing = None;
+                let listing = match self.sftp.as_mut() {
+                    Some(conn) => conn.list_dir().map(|entries| (conn.remote_path.clone(), entries)),
+                    None => return,
+                };
+                listing
+                    .map(|(path, entries)| self.remote_mut().load_remote(path, entries))
+                    .map_err(|e| e.to_string())
             }
+        };
+        if let Err(e) = reloaded {
+            self.set_status(format!("Aktualisierung fehlgeschlagen: {}", e));
+            return;
         }
+
+        let panel = self.active_panel_mut();
+        panel.reselect_by_name(keep.as_deref(), pos);
+        let unchanged = panel.entries.len() == names_before.len()
+            && panel.entries.iter().all(|e| names_before.contains(&e.name));
+        if unchanged {
+            panel.marked = marked_names;
+        }
+        self.set_status("Aktualisiert".to_string());
     }
 
     // -----------------------------------------------------------------------
@@ -1359,22 +3561,22 @@ impl App {
     // -----------------------------------------------------------------------
 
     /// Open the rename dialog for the currently selected entry.
+    /// With marked entries the pattern-based batch rename dialog opens instead.
     pub fn open_rename_dialog(&mut self) {
-        let side = self.active;
-        let panel_side = match side {
-            ActivePanel::Left => PanelSide::Left,
-            ActivePanel::Right => {
-                if !self.is_connected() {
-                    return;
-                }
-                PanelSide::Right
-            }
-        };
-        let panel = match side {
-            ActivePanel::Left => &self.left,
-            ActivePanel::Right => &self.right,
+        let panel_side = match self.active_side() {
+            Some(side) => side,
+            None => return,
         };
-        let entry = match panel.entries.get(panel.selected) {
+        let panel = self.panel(self.active);
+        if !panel.marked.is_empty() {
+            let names: Vec<String> =
+                panel.target_entries().iter().map(|e| e.name.clone()).collect();
+            if !names.is_empty() {
+                self.batch_rename_dialog = Some(BatchRenameDialog::new(panel_side, names));
+            }
+            return;
+        }
+        let entry = match panel.selected_entry() {
             Some(e) if e.name != ".." => e.clone(),
             _ => return,
         };
@@ -1383,51 +3585,46 @@ impl App {
 
     /// Confirm the rename and apply it.
     pub fn confirm_rename(&mut self) {
-        let dlg = match self.rename_dialog.take() {
+        let mut dlg = match self.rename_dialog.take() {
             Some(d) => d,
             None => return,
         };
-        let new_name = dlg.input.trim().to_string();
+        let new_name = dlg.input.buf.trim().to_string();
         if new_name.is_empty() || new_name == dlg.original {
             return;
         }
+        if let Some(msg) = name_error(&new_name, false) {
+            // Keep the dialog open so the user can fix the name.
+            dlg.error = Some(msg);
+            self.rename_dialog = Some(dlg);
+            return;
+        }
         match dlg.side {
-            PanelSide::Left => {
-                let old = self.left.path.join(&dlg.original);
-                let new = self.left.path.join(&new_name);
+            PanelSide::Local => {
+                let old = self.local().path.join(self.local().raw_name(&dlg.original));
+                let new = self.local().path.join(&new_name);
                 match std::fs::rename(&old, &new) {
                     Ok(()) => {
-                        self.status_message =
-                            Some(format!("Umbenannt: {} → {}", dlg.original, new_name));
-                        let _ = self.left.load_local();
+                        self.set_status(format!("Umbenannt: {} → {}", dlg.original, new_name));
+                        let pos = self.local().selected;
+                        let _ = self.local_mut().load_local();
+                        self.local_mut().reselect_by_name(Some(&new_name), pos);
                     }
                     Err(e) => {
-                        self.status_message = Some(format!("Umbenennen fehlgeschlagen: {}", e));
+                        self.set_status(format!("Umbenennen fehlgeschlagen: {}", e));
                     }
                 }
             }
-            PanelSide::Right => {
+            PanelSide::Remote => {
                 if let Some(conn) = self.sftp.as_ref() {
-                    match conn.rename(&dlg.original, &new_name) {
+                    match conn.rename(&self.remote().raw_name(&dlg.original), &new_name) {
                         Ok(()) => {
-                            self.status_message =
-                                Some(format!("Umbenannt: {} → {}", dlg.original, new_name));
-                            if let Some(conn) = self.sftp.as_mut() {
-                                match conn.list_dir() {
-                                    Ok(entries) => {
-                                        let path = conn.remote_path.clone();
-                                        self.right.load_remote(path, entries);
-                                    }
-                                    Err(e) => {
-                                        self.status_message =
-                                            Some(format!("Listing fehlgeschlagen: {}", e));
-                                    }
-                                }
-                            }
+                            self.set_status(format!("Umbenannt: {} → {}", dlg.original, new_name));
+                            let pos = self.remote().selected;
+                            self.reload_remote((Some(new_name), pos));
                         }
                         Err(e) => {
-                            self.status_message =
-                                Some(format!("Umbenennen fehlgeschlagen: {}", e));
+                            self.set_status(format!("Umbenennen fehlgeschlagen: {}", e));
                         }
                     }
                 }
@@ -1435,70 +3632,158 @@ impl App {
         }
     }
 
+    /// Validate the batch rename pattern and apply it to all marked entries.
+    /// On a validation error the dialog stays open and shows the message.
+    pub fn confirm_batch_rename(&mut self) {
+        let mut dlg = match self.batch_rename_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let panel = match dlg.side {
+            PanelSide::Local => self.local(),
+            PanelSide::Remote => self.remote(),
+        };
+        let existing: Vec<String> = panel.entries.iter().map(|e| e.name.clone()).collect();
+        let plan = match plan_batch_rename(&dlg.input.buf, &dlg.names, &existing) {
+            Ok(plan) => plan,
+            Err(msg) => {
+                // Keep the dialog open so the user can fix the pattern.
+                dlg.error = Some(msg);
+                self.batch_rename_dialog = Some(dlg);
+                return;
+            }
+        };
+        let side = dlg.side;
+        let total = plan.len();
+        let mut renamed = 0usize;
+        let mut last_error: Option<String> = None;
+        for (old, new) in &plan {
+            let result = match side {
+                PanelSide::Local => {
+                    let dir = &self.local().path;
+                    std::fs::rename(dir.join(self.local().raw_name(old)), dir.join(new))
+                        .map_err(|e| e.to_string())
+                }
+                PanelSide::Remote => match self.sftp.as_ref() {
+                    Some(conn) => conn.rename(&self.remote().raw_name(old), new).map_err(|e| e.to_string()),
+                    None => Err("Nicht verbunden".to_string()),
+                },
+            };
+            match result {
+                Ok(()) => renamed += 1,
+                Err(e) => last_error = Some(format!("'{}': {}", old, e)),
+            }
+        }
+        self.reload_side(side);
+        self.set_status(match last_error {
+            Some(err) => format!("{}/{} umbenannt — Fehler: {}", renamed, total, err),
+            None => format!("{} Einträge umbenannt", renamed),
+        });
+    }
+
+    /// Reload the listing of the given side after a file operation, keeping
+    /// the cursor on the same entry when it still exists.
+    fn reload_side(&mut self, side: PanelSide) {
+        match side {
+            PanelSide::Local => {
+                let (keep, pos) = self.local().cursor_snapshot();
+                let _ = self.local_mut().load_local();
+                self.local_mut().reselect_by_name(keep.as_deref(), pos);
+            }
+            PanelSide::Remote => {
+                let snapshot = self.remote().cursor_snapshot();
+                self.reload_remote(snapshot);
+            }
+        }
+    }
+
+    /// Re-list the current remote directory in the background and put the
+    /// cursor back on `reselect` (name, else position) afterwards. Called
+    /// after operations that changed it, so cached listings of it and of the
+    /// directories below it are dropped.
+    fn reload_remote(&mut self, reselect: (Option<String>, usize)) {
+        if let Some(path) = self.sftp.as_ref().map(|c| c.remote_path.clone()) {
+            self.listing_cache.invalidate_tree(&path);
+            self.start_remote_listing(path, Some(reselect), ListingKind::Load);
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Mkdir (F7)
     // -----------------------------------------------------------------------
 
     /// Open the mkdir dialog for the active panel.
     pub fn open_mkdir_dialog(&mut self) {
-        let panel_side = match self.active {
-            ActivePanel::Left => PanelSide::Left,
-            ActivePanel::Right => {
-                if !self.is_connected() {
-                    return;
-                }
-                PanelSide::Right
-            }
+        let panel_side = match self.active_side() {
+            Some(side) => side,
+            None => return,
         };
         self.mkdir_dialog = Some(MkdirDialog::new(panel_side));
     }
 
-    /// Confirm directory creation.
+    /// Confirm directory creation. Nested paths (`a/b/c`) create missing
+    /// parents on both sides; the cursor lands on the new top-level entry.
     pub fn confirm_mkdir(&mut self) {
-        let dlg = match self.mkdir_dialog.take() {
+        let mut dlg = match self.mkdir_dialog.take() {
             Some(d) => d,
             None => return,
         };
-        let name = dlg.input.trim().to_string();
+        let name = dlg.input.buf.trim().trim_end_matches('/').to_string();
         if name.is_empty() {
             return;
         }
+        // Locally `~` and `$VAR` are expanded before the checks, so they see
+        // the real path. Nested paths are created relative to the panel; an
+        // absolute path is almost always a typo for a relative one, unless it
+        // comes from an explicit leading `~` or `$VAR`. `..` is refused
+        // wherever it comes from.
+        let (target, explicit) = match dlg.side {
+            PanelSide::Local => (
+                expand_local_path(&name).to_string_lossy().into_owned(),
+                name.starts_with('~') || name.starts_with('$'),
+            ),
+            PanelSide::Remote => (name.clone(), false),
+        };
+        let error = if target.starts_with('/') && !explicit {
+            Some("Absoluter Pfad nicht erlaubt".to_string())
+        } else {
+            name_error(&target, true)
+        };
+        if let Some(msg) = error {
+            dlg.error = Some(msg);
+            self.mkdir_dialog = Some(dlg);
+            return;
+        }
+        // Entry of the current listing that leads to the new directory.
+        let top = Some(&target)
+            .filter(|t| !t.starts_with('/'))
+            .and_then(|t| t.split('/').find(|p| !p.is_empty() && *p != "."))
+            .map(str::to_string);
         match dlg.side {
-            PanelSide::Left => {
-                let path = self.left.path.join(&name);
-                match std::fs::create_dir(&path) {
+            PanelSide::Local => {
+                let path = self.local().path.join(&target);
+                match std::fs::create_dir_all(&path) {
                     Ok(()) => {
-                        self.status_message = Some(format!("Verzeichnis '{}' erstellt", name));
-                        let _ = self.left.load_local();
+                        self.set_status(format!("Verzeichnis '{}' erstellt", name));
+                        let (_, pos) = self.local().cursor_snapshot();
+                        let _ = self.local_mut().load_local();
+                        self.local_mut().reselect_by_name(top.as_deref(), pos);
                     }
                     Err(e) => {
-                        self.status_message =
-                            Some(format!("Verzeichnis erstellen fehlgeschlagen: {}", e));
+                        self.set_status(format!("Verzeichnis erstellen fehlgeschlagen: {}", e));
                     }
                 }
             }
-            PanelSide::Right => {
+            PanelSide::Remote => {
                 if let Some(conn) = self.sftp.as_ref() {
-                    match conn.mkdir(&name) {
+                    match conn.mkdir_p(&name) {
                         Ok(()) => {
-                            self.status_message =
-                                Some(format!("Verzeichnis '{}' erstellt", name));
-                            if let Some(conn) = self.sftp.as_mut() {
-                                match conn.list_dir() {
-                                    Ok(entries) => {
-                                        let path = conn.remote_path.clone();
-                                        self.right.load_remote(path, entries);
-                                    }
-                                    Err(e) => {
-                                        self.status_message =
-                                            Some(format!("Listing fehlgeschlagen: {}", e));
-                                    }
-                                }
-                            }
+                            self.set_status(format!("Verzeichnis '{}' erstellt", name));
+                            let (_, pos) = self.remote().cursor_snapshot();
+                            self.reload_remote((top, pos));
                         }
                         Err(e) => {
-                            self.status_message =
-                                Some(format!("Verzeichnis erstellen fehlgeschlagen: {}", e));
+                            self.set_status(format!("Verzeichnis erstellen fehlgeschlagen: {}", e));
                         }
                     }
                 }
@@ -1507,342 +3792,1587 @@ impl App {
     }
 
     // -----------------------------------------------------------------------
-    // Delete (F8)
+    // Go to path (Ctrl+G)
     // -----------------------------------------------------------------------
 
-    /// Open the delete confirmation dialog.
-    /// If entries are marked, all marked entries are queued for deletion.
-    /// Otherwise the single highlighted entry is used.
-    pub fn open_delete_dialog(&mut self) {
-        let panel_side = match self.active {
-            ActivePanel::Left => PanelSide::Left,
-            ActivePanel::Right => {
-                if !self.is_connected() {
+    /// Open the go-to-path prompt for the active panel.
+    pub fn open_goto_dialog(&mut self) {
+        let side = match self.active_side() {
+            Some(side) => side,
+            None => return,
+        };
+        self.goto_dialog = Some(GotoDialog::new(side, &self.panel(self.active).path));
+    }
+
+    /// Tab in the go-to prompt: complete the last path component before the
+    /// cursor against the directories of its parent. A unique match is
+    /// completed with a trailing `/`; several are extended to their common
+    /// prefix and listed. Hidden directories only match a prefix with a dot.
+    pub fn complete_goto_path(&mut self) {
+        let Some(dlg) = self.goto_dialog.as_ref() else {
+            return;
+        };
+        let before = dlg.input.before_cursor().to_string();
+        let (dir_part, prefix) = match before.rfind('/') {
+            Some(i) => (&before[..=i], &before[i + 1..]),
+            None => ("", before.as_str()),
+        };
+        let names = match dlg.side {
+            PanelSide::Local => {
+                let dir = self.local().path.join(expand_local_path(dir_part));
+                match fs::read_dir(&dir) {
+                    Ok(read) => read
+                        .filter_map(|e| e.ok())
+                        .filter(|e| e.path().is_dir())
+                        .map(|e| e.file_name().to_string_lossy().into_owned())
+                        .collect(),
+                    Err(_) => return,
+                }
+            }
+            PanelSide::Remote => {
+                let Some(conn) = self.sftp.as_ref() else {
                     return;
+                };
+                let dir = conn.remote_path.join(conn.expand_tilde(dir_part));
+                match conn.subdir_names(&dir) {
+                    Ok(names) => names,
+                    Err(_) => return,
                 }
-                PanelSide::Right
             }
         };
-        let panel = match self.active {
-            ActivePanel::Left => &self.left,
-            ActivePanel::Right => &self.right,
+        let mut matches: Vec<String> = names
+            .into_iter()
+            .filter(|n| n.starts_with(prefix) && (!n.starts_with('.') || prefix.starts_with('.')))
+            .collect();
+        matches.sort();
+
+        let completion = match matches.as_slice() {
+            [] => return,
+            [name] => format!("{}/", &name[prefix.len()..]),
+            [first, rest @ ..] => {
+                let mut common = rest.iter().fold(first.len(), |acc, name| {
+                    first.bytes().zip(name.bytes()).take_while(|(a, b)| a == b).count().min(acc)
+                });
+                while !first.is_char_boundary(common) {
+                    common -= 1;
+                }
+                first[prefix.len()..common].to_string()
+            }
+        };
+        let Some(dlg) = self.goto_dialog.as_mut() else {
+            return;
         };
+        if matches.len() > 1 {
+            dlg.candidates = matches.iter().map(|n| format!("{}/", n)).collect();
+        }
+        dlg.input.insert_str(&completion);
+    }
 
-        let to_delete: Vec<(String, bool)> = if panel.marked.is_empty() {
-            // Single entry — the currently highlighted one
-            match panel.entries.get(panel.selected) {
-                Some(e) if e.name != ".." => vec![(e.name.clone(), e.is_dir)],
-                _ => return,
-            }
+    /// Jump to the entered directory. Relative paths start at the panel's
+    /// directory, `~` is the (local or remote login) home. A missing path or
+    /// a file keeps the prompt open with the error.
+    pub fn confirm_goto(&mut self) {
+        let mut dlg = match self.goto_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let raw = dlg.input.buf.trim().to_string();
+        if raw.is_empty() {
+            return;
+        }
+        let result = match dlg.side {
+            PanelSide::Local => self.goto_local(&raw),
+            PanelSide::Remote => self.goto_remote(&raw),
+        };
+        if let Err(msg) = result {
+            dlg.error = Some(msg);
+            dlg.candidates.clear();
+            self.goto_dialog = Some(dlg);
+        }
+    }
+
+    fn goto_local(&mut self, raw: &str) -> Result<(), String> {
+        let target = self.local().path.join(expand_local_path(raw));
+        let canonical =
+            fs::canonicalize(&target).map_err(|_| format!("Pfad nicht gefunden: {}", raw))?;
+        if !canonical.is_dir() {
+            return Err(format!("'{}' ist kein Verzeichnis", raw));
+        }
+        let previous = self.local().path.clone();
+        self.local_mut().path = canonical;
+        self.local_mut().selected = 0;
+        if let Err(e) = self.local_mut().load_local() {
+            self.local_mut().path = previous;
+            let _ = self.local_mut().load_local();
+            return Err(e.to_string());
+        }
+        Ok(())
+    }
+
+    fn goto_remote(&mut self, raw: &str) -> Result<(), String> {
+        let conn = self.sftp.as_mut().ok_or_else(|| "Nicht verbunden".to_string())?;
+        let target = if raw.starts_with('/') || raw.starts_with('~') {
+            raw.to_string()
         } else {
-            // All marked entries, sorted by index
-            let mut indices: Vec<usize> = panel.marked.iter().cloned().collect();
-            indices.sort_unstable();
-            indices
-                .iter()
-                .filter_map(|&i| panel.entries.get(i))
-                .filter(|e| e.name != "..")
-                .map(|e| (e.name.clone(), e.is_dir))
-                .collect()
+            conn.remote_path.join(raw).to_string_lossy().to_string()
+        };
+        match conn.change_to_absolute(&target) {
+            Ok(entries) => {
+                let path = conn.remote_path.clone();
+                self.listing_cache.insert(path.clone(), entries.clone());
+                self.remote_mut().load_remote(path, entries);
+                self.update_remote_free_space();
+                Ok(())
+            }
+            Err(SftpError::Path(msg)) => Err(msg),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Touch ('n')
+    // -----------------------------------------------------------------------
+
+    /// Open the create-empty-file dialog for the active panel; with `edit`
+    /// the new file is opened in the editor afterwards.
+    pub fn open_touch_dialog(&mut self, edit: bool) {
+        let panel_side = match self.active_side() {
+            Some(side) => side,
+            None => return,
         };
+        self.touch_dialog = Some(TouchDialog::new(panel_side, edit));
+    }
 
-        if to_delete.is_empty() {
+    /// Confirm creation of an empty file. Existing files are left untouched.
+    /// In edit mode the new file goes straight to the editor (F4 machinery).
+    pub fn confirm_touch(&mut self) {
+        let mut dlg = match self.touch_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let name = dlg.input.buf.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        if let Some(msg) = name_error(&name, false) {
+            dlg.error = Some(msg);
+            self.touch_dialog = Some(dlg);
+            return;
+        }
+        let exists = self.panel(self.active).entries.iter().any(|e| e.name == name);
+        if exists {
+            self.set_status(format!("'{}' existiert bereits", name));
             return;
         }
+        let result = match dlg.side {
+            // create_new: never truncate a file the (possibly stale) listing misses.
+            PanelSide::Local => fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.local().path.join(&name))
+                .map(|_| ())
+                .map_err(|e| match e.kind() {
+                    std::io::ErrorKind::AlreadyExists => "existiert bereits".to_string(),
+                    _ => e.to_string(),
+                }),
+            PanelSide::Remote => match self.sftp.as_ref() {
+                Some(conn) => conn.touch(&name).map_err(|e| e.to_string()),
+                None => return,
+            },
+        };
+        match result {
+            Ok(()) => {
+                self.set_status(format!("Datei '{}' erstellt", name));
+                self.reload_side(dlg.side);
+                if dlg.edit {
+                    self.prepare_edit_file(dlg.side, &name);
+                }
+            }
+            Err(e) => {
+                self.set_status(format!("Datei erstellen fehlgeschlagen: {}", e));
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Mark by pattern ('+' / '-')
+    // -----------------------------------------------------------------------
 
-        self.delete_dialog = Some(DeleteDialog::new_multi(panel_side, to_delete));
+    /// Open the pattern dialog; `mark` selects marking ('+') or unmarking ('-').
+    pub fn open_mark_pattern_dialog(&mut self, mark: bool) {
+        if self.active_side().is_none() {
+            return;
+        }
+        self.mark_pattern_dialog = Some(MarkPatternDialog::new(mark));
     }
 
-    /// Confirm and execute the delete for all entries in the dialog.
-    pub fn confirm_delete(&mut self) {
-        let dlg = match self.delete_dialog.take() {
+    /// Apply the entered pattern to the active panel.
+    pub fn confirm_mark_pattern(&mut self) {
+        let dlg = match self.mark_pattern_dialog.take() {
             Some(d) => d,
             None => return,
         };
+        let pattern = dlg.input.buf.trim();
+        if pattern.is_empty() {
+            return;
+        }
+        let count = self.active_panel_mut().mark_by_pattern(pattern, dlg.mark);
+        self.set_status(if dlg.mark {
+            format!("{} Einträge markiert ({})", count, pattern)
+        } else {
+            format!("{} Markierungen entfernt ({})", count, pattern)
+        });
+    }
 
-        let total = dlg.entries.len();
-        let mut deleted = 0usize;
-        let mut last_error: Option<String> = None;
+    // -----------------------------------------------------------------------
+    // Mark by age ('a')
+    // -----------------------------------------------------------------------
 
-        match dlg.side {
-            PanelSide::Left => {
-                for (name, is_dir) in &dlg.entries {
-                    let path = self.left.path.join(name);
-                    let result = if *is_dir {
-                        std::fs::remove_dir_all(&path)
-                    } else {
-                        std::fs::remove_file(&path)
-                    };
-                    match result {
-                        Ok(()) => deleted += 1,
-                        Err(e) => last_error = Some(format!("'{}': {}", name, e)),
-                    }
-                }
-                let _ = self.left.load_local();
-            }
-            PanelSide::Right => {
-                if self.sftp.is_none() {
-                    return;
-                }
-                // Delete each entry individually, collecting errors.
-                for (name, is_dir) in &dlg.entries {
-                    let result = if *is_dir {
-                        self.sftp.as_ref().unwrap().delete_dir(name)
-                    } else {
-                        self.sftp.as_ref().unwrap().delete_file(name)
-                    };
-                    match result {
-                        Ok(()) => deleted += 1,
-                        Err(e) => {
-                            last_error = Some(format!("'{}': {}", name, e));
-                        }
-                    }
-                }
-                // Refresh remote listing after all deletions.
-                match self.sftp.as_mut().unwrap().list_dir() {
-                    Ok(entries) => {
-                        let path = self.sftp.as_ref().unwrap().remote_path.clone();
-                        self.right.load_remote(path, entries);
-                    }
-                    Err(e) => {
-                        self.status_message =
-                            Some(format!("Listing fehlgeschlagen: {}", e));
-                        return;
-                    }
-                }
+    /// Open the mark-by-age dialog for the active panel.
+    pub fn open_mark_age_dialog(&mut self) {
+        if self.active_side().is_none() {
+            return;
+        }
+        self.mark_age_dialog = Some(MarkAgeDialog::new());
+    }
+
+    /// Mark the entries older (or newer) than the entered age or date.
+    /// An unreadable input keeps the dialog open with the error.
+    pub fn confirm_mark_age(&mut self) {
+        let mut dlg = match self.mark_age_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let cutoff = match parse_age_cutoff(&dlg.input.buf, SystemTime::now()) {
+            Ok(t) => t,
+            Err(msg) => {
+                dlg.error = Some(msg);
+                self.mark_age_dialog = Some(dlg);
+                return;
             }
+        };
+        let count = self.active_panel_mut().mark_by_age(cutoff, dlg.older);
+        let relation = if dlg.older { "älter als" } else { "neuer als" };
+        self.set_status(format!("{} Einträge markiert ({} {})", count, relation, dlg.input.buf.trim()));
+    }
+
+    // -----------------------------------------------------------------------
+    // Chown ('o')
+    // -----------------------------------------------------------------------
+
+    /// Open the owner dialog for the remote selection, pre-filled with the
+    /// current uid/gid of the highlighted entry.
+    pub fn open_chown_dialog(&mut self) {
+        if !self.is_remote_active() {
+            self.set_status("Besitzer ändern nur im Remote-Panel".to_string());
+            return;
         }
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let names: Vec<String> = self
+            .remote()
+            .target_entries()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect();
+        let Some(first) = names.first() else {
+            return;
+        };
+        let (uid, gid) = match conn.sftp().lstat(&conn.remote_path.join(self.remote().raw_name(first))) {
+            Ok(stat) => (stat.uid, stat.gid),
+            Err(_) => (None, None),
+        };
+        self.chown_dialog = Some(ChownDialog::new(names, uid, gid));
+    }
 
-        // Status message: show how many were deleted, and the last error if any
-        self.status_message = Some(if let Some(err) = last_error {
-            format!("{}/{} gelöscht — Fehler: {}", deleted, total, err)
-        } else if total == 1 {
-            format!("'{}' gelöscht", dlg.entries[0].0)
+    /// Apply the uid/gid from the chown dialog and reload the remote listing.
+    pub fn confirm_chown(&mut self) {
+        let Some((uid, gid)) = self.chown_dialog.as_ref().and_then(|d| d.parse()) else {
+            self.set_status("Format: UID:GID (numerisch, z.B. 33:33)".to_string());
+            return;
+        };
+        let dlg = match self.chown_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let mut errors: Vec<String> = Vec::new();
+        for name in &dlg.names {
+            match conn.chown(&self.remote().raw_name(name), uid, gid) {
+                Ok(()) => {}
+                Err(SftpError::PermissionDenied(_)) => errors.push(format!(
+                    "'{}': Keine Berechtigung (nur root darf den Besitzer ändern)",
+                    name
+                )),
+                Err(e) => errors.push(format!("'{}': {}", name, e)),
+            }
+        }
+        self.set_status(if errors.is_empty() {
+            format!("Besitzer auf {}:{} gesetzt ({} Einträge)", uid, gid, dlg.names.len())
         } else {
-            format!("{} Einträge gelöscht", deleted)
+            format!("Besitzer ändern fehlgeschlagen — {}", errors.join("; "))
         });
+        self.reload_side(PanelSide::Remote);
+    }
 
-        // Clear marks on the relevant panel
-        match dlg.side {
-            PanelSide::Left => self.left.clear_marks(),
-            PanelSide::Right => self.right.clear_marks(),
+    // -----------------------------------------------------------------------
+    // Search ('/')
+    // -----------------------------------------------------------------------
+
+    /// Open the search dialog for the current remote directory.
+    pub fn open_search_dialog(&mut self) {
+        if !self.is_remote_active() {
+            self.set_status("Suche nur im Remote-Panel".to_string());
+            return;
+        }
+        if let Some(conn) = self.sftp.as_ref() {
+            self.search_dialog = Some(SearchDialog::new(conn.remote_path.clone()));
         }
     }
 
-    /// Navigate into the selected remote entry (right panel, connected).
-    pub fn remote_enter_selected(&mut self) {
-        let selected = self.right.selected;
-        let entry = match self.right.entries.get(selected) {
-            Some(e) => e.clone(),
-            None => return,
+    /// Start walking the remote tree on a separate connection.
+    pub fn start_search(&mut self) {
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let dlg = match self.search_dialog.as_mut() {
+            Some(d) if d.handle.is_none() => d,
+            _ => return,
+        };
+        let pattern = dlg.input.buf.trim().to_string();
+        if pattern.is_empty() {
+            return;
+        }
+        let handle: SearchHandle = Arc::new(Mutex::new(SearchProgress::default()));
+        let (profile, saved_pw) = (conn.profile.clone(), conn.saved_password.clone());
+        let (root, handle_clone, cancel) = (dlg.root.clone(), Arc::clone(&handle), Arc::clone(&dlg.cancel));
+        std::thread::spawn(move || search_remote(profile, saved_pw, root, pattern, handle_clone, cancel));
+        dlg.handle = Some(handle);
+    }
+
+    /// Close the search dialog, stopping a running search.
+    pub fn close_search(&mut self) {
+        if let Some(dlg) = self.search_dialog.take() {
+            dlg.cancel();
+        }
+    }
+
+    /// Navigate the remote panel to the directory of the selected result and
+    /// highlight the match.
+    pub fn search_jump(&mut self) {
+        let target = match self.search_dialog.as_ref().and_then(|d| {
+            let h = d.handle.as_ref()?.lock().unwrap();
+            h.matches.get(d.selected).cloned()
+        }) {
+            Some(p) => p,
+            None => return,
+        };
+        let (Some(dir), Some(name)) = (target.parent(), target.file_name()) else {
+            return;
+        };
+        let name = name.to_string_lossy().to_string();
+        self.close_search();
+        let conn = match self.sftp.as_mut() {
+            Some(c) => c,
+            None => return,
+        };
+        match conn.change_to_absolute(&dir.to_string_lossy()) {
+            Ok(entries) => {
+                let path = conn.remote_path.clone();
+                self.remote_mut().load_remote(path, entries);
+                self.remote_mut().select_name(&name);
+                self.update_remote_free_space();
+                self.active = self.remote_side;
+            }
+            Err(e) => {
+                self.set_status(format!("Verzeichnis wechseln fehlgeschlagen: {}", e));
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Grep ('g')
+    // -----------------------------------------------------------------------
+
+    /// Open the content search for the local panel directory.
+    pub fn open_grep_dialog(&mut self) {
+        self.grep_dialog = Some(GrepDialog::new(self.local().path.clone()));
+    }
+
+    /// Compile the pattern and start searching in a background thread.
+    pub fn start_grep(&mut self) {
+        let dlg = match self.grep_dialog.as_mut() {
+            Some(d) if d.handle.is_none() => d,
+            _ => return,
+        };
+        if dlg.input.buf.is_empty() {
+            return;
+        }
+        let source = if dlg.regex {
+            dlg.input.buf.clone()
+        } else {
+            regex::escape(&dlg.input.buf)
+        };
+        let pattern = match regex::Regex::new(&source) {
+            Ok(p) => p,
+            Err(e) => {
+                dlg.error = Some(e.to_string());
+                return;
+            }
+        };
+        dlg.error = None;
+        let handle: GrepHandle = Arc::new(Mutex::new(GrepProgress::default()));
+        let (root, handle_clone, cancel) = (dlg.root.clone(), Arc::clone(&handle), Arc::clone(&dlg.cancel));
+        std::thread::spawn(move || run_grep(root, pattern, handle_clone, cancel));
+        dlg.handle = Some(handle);
+    }
+
+    /// Close the grep dialog, stopping a running search.
+    pub fn close_grep(&mut self) {
+        if let Some(dlg) = self.grep_dialog.take() {
+            dlg.cancel();
+        }
+    }
+
+    /// Open the directory of the selected hit in the local panel and
+    /// highlight the file.
+    pub fn grep_jump(&mut self) {
+        let target = match self.grep_dialog.as_ref().and_then(|d| {
+            let h = d.handle.as_ref()?.lock().unwrap();
+            h.hits.get(d.selected).map(|hit| hit.path.clone())
+        }) {
+            Some(p) => p,
+            None => return,
+        };
+        let (Some(dir), Some(name)) = (target.parent(), target.file_name()) else {
+            return;
+        };
+        let name = name.to_string_lossy().to_string();
+        self.close_grep();
+        let panel = self.local_mut();
+        let previous = std::mem::replace(&mut panel.path, dir.to_path_buf());
+        match panel.load_local() {
+            Ok(()) => {
+                panel.select_name(&name);
+                self.active = self.remote_side.toggle();
+            }
+            Err(e) => {
+                panel.path = previous;
+                let _ = panel.load_local();
+                self.set_status(format!("Verzeichnis wechseln fehlgeschlagen: {}", e));
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Delete (F8)
+    // -----------------------------------------------------------------------
+
+    /// Open the delete confirmation dialog.
+    /// If entries are marked, all marked entries are queued for deletion.
+    /// Otherwise the single highlighted entry is used.
+    pub fn open_delete_dialog(&mut self) {
+        let panel_side = match self.active_side() {
+            Some(side) => side,
+            None => return,
+        };
+        let panel = self.panel(self.active);
+
+        // Marked entries, or the single highlighted entry when nothing is marked.
+        // Symlinks are removed as links, never recursed into.
+        let to_delete: Vec<(String, bool)> = panel
+            .target_entries()
+            .iter()
+            .map(|e| (e.name.clone(), e.is_dir && !e.is_symlink))
+            .collect();
+
+        if to_delete.is_empty() {
+            return;
+        }
+
+        let mut dlg = DeleteDialog::new_multi(panel_side, to_delete);
+        dlg.trash = match panel_side {
+            PanelSide::Local => self.safe_delete,
+            PanelSide::Remote => self.remote_trash,
+        };
+        if dlg.entries.iter().any(|(_, is_dir)| *is_dir) {
+            dlg.counting = self.count_delete_tree(panel_side, &dlg.entries, Arc::clone(&dlg.cancel));
+        }
+        self.delete_dialog = Some(dlg);
+    }
+
+    /// Start the preflight walk for the delete dialog on a worker thread:
+    /// everything below `entries`, including the entries themselves.
+    /// Symlinks are counted, not followed. The remote walk opens a session
+    /// of its own; setting `cancel` stops either walk.
+    fn count_delete_tree(
+        &self,
+        side: PanelSide,
+        entries: &[(String, bool)],
+        cancel: Arc<AtomicBool>,
+    ) -> Option<TreeCountSlot> {
+        let slot: TreeCountSlot = Arc::new(Mutex::new(None));
+        let slot_clone = Arc::clone(&slot);
+        match side {
+            PanelSide::Local => {
+                let paths: Vec<PathBuf> = entries
+                    .iter()
+                    .map(|(name, _)| self.local().path.join(self.local().raw_name(name)))
+                    .collect();
+                std::thread::spawn(move || count_local_paths(paths, slot_clone, cancel));
+            }
+            PanelSide::Remote => {
+                let conn = self.sftp.as_ref()?;
+                let (profile, saved_pw) = (conn.profile.clone(), conn.saved_password.clone());
+                let paths = entries
+                    .iter()
+                    .map(|(name, _)| conn.remote_path.join(self.remote().raw_name(name)))
+                    .collect();
+                std::thread::spawn(move || count_remote_paths(profile, saved_pw, paths, slot_clone, cancel));
+            }
+        }
+        Some(slot)
+    }
+
+    /// Take the finished preflight walk of the open delete dialog, if any.
+    pub fn poll_delete_count(&mut self) {
+        let Some(dlg) = self.delete_dialog.as_mut() else {
+            return;
+        };
+        let count = match dlg.counting.as_ref() {
+            Some(slot) => slot.lock().unwrap().take(),
+            None => return,
+        };
+        if let Some(count) = count {
+            dlg.summary = count.ok();
+            dlg.counting = None;
+        }
+    }
+
+    /// Confirm and execute the delete for all entries in the dialog.
+    pub fn confirm_delete(&mut self) {
+        let dlg = match self.delete_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        dlg.cancel();
+
+        let total = dlg.entries.len();
+        let mut deleted = 0usize;
+        let mut last_error: Option<String> = None;
+
+        match dlg.side {
+            PanelSide::Local => {
+                for (name, is_dir) in &dlg.entries {
+                    let path = self.local().path.join(self.local().raw_name(name));
+                    let result = if dlg.trash {
+                        crate::trash::move_to_trash(&path)
+                    } else if *is_dir {
+                        std::fs::remove_dir_all(&path)
+                    } else {
+                        std::fs::remove_file(&path)
+                    };
+                    match result {
+                        Ok(()) => deleted += 1,
+                        Err(e) => last_error = Some(format!("'{}': {}", name, e)),
+                    }
+                }
+                let _ = self.local_mut().load_local();
+            }
+            PanelSide::Remote => {
+                if self.sftp.is_none() {
+                    return;
+                }
+                // Directory trees can take long — remove them in the background.
+                if !dlg.trash && dlg.entries.iter().any(|(_, is_dir)| *is_dir) {
+                    let entries =
+                        dlg.entries.iter().map(|(name, is_dir)| (self.remote().raw_name(name), *is_dir)).collect();
+                    self.start_remote_delete(entries);
+                    return;
+                }
+                // Delete each entry individually, collecting errors.
+                for (name, is_dir) in &dlg.entries {
+                    let raw = self.remote().raw_name(name);
+                    let result = if dlg.trash {
+                        self.sftp.as_ref().unwrap().move_to_trash(&raw)
+                    } else if *is_dir {
+                        self.sftp.as_ref().unwrap().delete_dir(&raw)
+                    } else {
+                        self.sftp.as_ref().unwrap().delete_file(&raw)
+                    };
+                    match result {
+                        Ok(()) => deleted += 1,
+                        Err(e) => {
+                            last_error = Some(format!("'{}': {}", name, e));
+                        }
+                    }
+                }
+                // Refresh remote listing after all deletions.
+                self.reload_remote((None, 0));
+            }
+        }
+
+        // Status message: show how many were deleted, and the last error if any
+        let done = if dlg.trash { "in den Papierkorb verschoben" } else { "gelöscht" };
+        self.set_status(if let Some(err) = last_error {
+            format!("{}/{} {} — Fehler: {}", deleted, total, done, err)
+        } else if total == 1 {
+            format!("'{}' {}", dlg.entries[0].0, done)
+        } else {
+            format!("{} Einträge {}", deleted, done)
+        });
+
+        // Clear marks on the relevant panel
+        match dlg.side {
+            PanelSide::Local => self.local_mut().clear_marks(),
+            PanelSide::Remote => self.remote_mut().clear_marks(),
+        }
+    }
+
+    /// Delete remote `entries` of the current directory on a background
+    /// connection; `poll_delete` reports the result and reloads the listing.
+    fn start_remote_delete(&mut self, entries: Vec<(OsString, bool)>) {
+        if self.is_transferring() {
+            self.set_status("Übertragung läuft bereits".to_string());
+            return;
+        }
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let (profile, saved_pw) = (conn.profile.clone(), conn.saved_password.clone());
+        let dir = conn.remote_path.clone();
+        let handle: TransferHandle = Arc::new(Mutex::new(TransferProgress::new(entries.len())));
+        let handle_clone = Arc::clone(&handle);
+        std::thread::spawn(move || delete_batch(profile, saved_pw, dir, entries, handle_clone));
+        self.delete_progress = Some(handle);
+        self.set_status("Lösche…".to_string());
+        self.remote_mut().clear_marks();
+    }
+
+    /// Check whether a background remote delete has finished.
+    pub fn poll_delete(&mut self) {
+        let (state, done) = match &self.delete_progress {
+            Some(h) => {
+                let prog = h.lock().unwrap();
+                (prog.state.clone(), prog.files_done)
+            }
+            None => return,
+        };
+        match state {
+            TransferState::Running => return,
+            TransferState::Done => {
+                self.set_status(format!("{} Einträge gelöscht", done));
+            }
+            TransferState::Failed(msg) => {
+                self.set_status(format!("{} Einträge gelöscht — erster Fehler: {}", done, msg));
+            }
+        }
+        self.delete_progress = None;
+        self.reload_side(PanelSide::Remote);
+        self.update_remote_free_space();
+    }
+
+    /// Re-query the free space of the current remote directory on a worker
+    /// thread; `poll_remote_free_space` shows the result. Failures (e.g. no
+    /// `df` on the server) just leave the indicator empty.
+    pub fn update_remote_free_space(&mut self) {
+        let Some(conn) = self.sftp.as_ref() else {
+            self.pending_free_space = None;
+            return;
+        };
+        let dir = conn.remote_path.clone();
+        let slot = conn.free_space_in_background(dir.clone());
+        self.pending_free_space = Some((dir, slot));
+    }
+
+    /// Show a finished `df` query in the remote panel title, unless the
+    /// panel has moved to another directory meanwhile.
+    /// Should be called once per render frame.
+    pub fn poll_remote_free_space(&mut self) {
+        let free = match self.pending_free_space.as_ref() {
+            Some((_, slot)) => slot.lock().unwrap().take(),
+            None => return,
+        };
+        let Some(free) = free else {
+            return;
+        };
+        let Some((dir, _)) = self.pending_free_space.take() else {
+            return;
+        };
+        if self.sftp.as_ref().is_some_and(|c| c.remote_path == dir) {
+            self.remote_mut().free_space = free;
+        }
+    }
+
+    /// Navigate into the selected remote entry (remote panel, connected).
+    pub fn remote_enter_selected(&mut self) {
+        let entry = match self.remote().selected_entry() {
+            Some(e) => e.clone(),
+            None => return,
+        };
+        if !entry.is_dir {
+            return;
+        }
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        match conn.child_path(&entry.raw_name) {
+            Ok(path) => self.navigate_remote(path, None),
+            Err(e) => {
+                self.set_status(format!("Verzeichnis öffnen fehlgeschlagen: {}", e));
+            }
+        }
+    }
+
+    /// Navigate to parent on the remote side.
+    pub fn remote_go_up(&mut self) {
+        if let Some(path) = self.sftp.as_ref().map(|c| c.parent_path()) {
+            self.navigate_remote(path, None);
+        }
+    }
+
+    /// Go one directory back (or forward) in the active panel's history
+    /// (Alt+Left / Alt+Right). Remote directories are listed again.
+    pub fn history_move(&mut self, forward: bool) {
+        let side = match self.active_side() {
+            Some(side) => side,
+            None => return,
+        };
+        let Some(target) = self.panel(self.active).history_target(forward).cloned() else {
+            self.set_status(if forward {
+                "Kein nächstes Verzeichnis im Verlauf".to_string()
+            } else {
+                "Kein vorheriges Verzeichnis im Verlauf".to_string()
+            });
+            return;
+        };
+        match side {
+            PanelSide::Local => {
+                let panel = self.local_mut();
+                panel.step_history(forward);
+                let previous = std::mem::replace(&mut panel.path, target);
+                panel.selected = 0;
+                if let Err(e) = panel.load_local() {
+                    // Undo the step so the history still matches the listing.
+                    panel.step_history(!forward);
+                    panel.path = previous;
+                    let _ = panel.load_local();
+                    self.set_status(format!("Verzeichnis wechseln fehlgeschlagen: {}", e));
+                }
+            }
+            PanelSide::Remote => {
+                if self.is_connected() {
+                    self.navigate_remote(target, Some(forward));
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Remote cut / copy / paste ('x' / 'y' / 'v')
+    // -----------------------------------------------------------------------
+
+    /// Put the marked (or highlighted) remote entries on the clipboard for moving.
+    pub fn cut_remote_selection(&mut self) {
+        self.clip_remote_selection(ClipboardOp::Cut);
+    }
+
+    /// Put the marked (or highlighted) remote entries on the clipboard for copying.
+    pub fn copy_remote_selection(&mut self) {
+        self.clip_remote_selection(ClipboardOp::Copy);
+    }
+
+    fn clip_remote_selection(&mut self, op: ClipboardOp) {
+        if self.active_side() != Some(PanelSide::Remote) {
+            self.set_status("Zwischenablage nur im Remote-Panel".to_string());
+            return;
+        }
+        let dir = match self.sftp.as_ref() {
+            Some(c) => c.remote_path.clone(),
+            None => return,
+        };
+        let paths: Vec<PathBuf> = self
+            .remote()
+            .target_entries()
+            .iter()
+            .map(|e| dir.join(&e.raw_name))
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        let verb = match op {
+            ClipboardOp::Cut => "ausgeschnitten",
+            ClipboardOp::Copy => "kopiert",
+        };
+        self.set_status(format!("{} Einträge {} — mit v einfügen", paths.len(), verb));
+        self.clipboard = Some(RemoteClipboard { op, paths });
+        self.remote_mut().marked.clear();
+    }
+
+    /// Paste the clipboard into the current remote directory:
+    /// cut entries are moved, copied entries are duplicated in the background.
+    pub fn paste_clipboard(&mut self) {
+        if self.active_side() != Some(PanelSide::Remote) {
+            self.set_status("Einfügen nur im Remote-Panel".to_string());
+            return;
+        }
+        let clip = match self.clipboard.take() {
+            Some(c) => c,
+            None => {
+                self.set_status("Zwischenablage ist leer".to_string());
+                return;
+            }
+        };
+        match clip.op {
+            ClipboardOp::Cut => self.move_clipboard_entries(&clip.paths),
+            ClipboardOp::Copy => {
+                self.start_remote_copy(&clip.paths);
+                // Copied entries stay on the clipboard for further pastes.
+                self.clipboard = Some(clip);
+            }
+        }
+    }
+
+    fn move_clipboard_entries(&mut self, paths: &[PathBuf]) {
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let dest_dir = conn.remote_path.clone();
+        let mut moved = 0usize;
+        let mut errors = Vec::new();
+        for from in paths {
+            let name = match from.file_name() {
+                Some(n) => n,
+                None => continue,
+            };
+            let to = dest_dir.join(name);
+            if to == *from {
+                moved += 1;
+                continue;
+            }
+            if dest_dir.starts_with(from) {
+                errors.push(format!("{}: Ziel liegt im Quellverzeichnis", name.to_string_lossy()));
+                continue;
+            }
+            match conn.rename_abs(from, &to) {
+                Ok(()) => moved += 1,
+                Err(e) => errors.push(format!("{}: {}", name.to_string_lossy(), e)),
+            }
+        }
+        // The source directories changed too; going back must not show the
+        // moved entries from the listing cache.
+        for parent in paths.iter().filter_map(|p| p.parent()) {
+            self.listing_cache.invalidate_tree(parent);
+        }
+        self.reload_side(PanelSide::Remote);
+        self.set_status(if errors.is_empty() {
+            format!("{} Einträge verschoben", moved)
+        } else {
+            format!("{}/{} verschoben — Fehler: {}", moved, paths.len(), errors.join("; "))
+        });
+    }
+
+    /// Copy `paths` into the current remote directory on a background
+    /// connection. Existing names get a " (Kopie)" suffix.
+    fn start_remote_copy(&mut self, paths: &[PathBuf]) {
+        if self.is_transferring() {
+            self.set_status("Übertragung läuft bereits".to_string());
+            return;
+        }
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let dest_dir = conn.remote_path.clone();
+        if let Some(src) = paths.iter().find(|p| dest_dir.starts_with(p)) {
+            self.set_status(format!("{}: Ziel liegt im Quellverzeichnis", src.display()));
+            return;
+        }
+        let pairs: Vec<(PathBuf, PathBuf)> = paths
+            .iter()
+            .filter_map(|src| {
+                let name = src.file_name()?.to_string_lossy().to_string();
+                Some((src.clone(), conn.unique_copy_target(&dest_dir, &name)))
+            })
+            .collect();
+        let (profile, saved_pw) = (conn.profile.clone(), conn.saved_password.clone());
+        let handle: TransferHandle = Arc::new(Mutex::new(TransferProgress::new(pairs.len())));
+        let handle_clone = Arc::clone(&handle);
+        std::thread::spawn(move || copy_batch(profile, saved_pw, pairs, handle_clone));
+        self.copy_progress = Some(handle);
+        self.set_status("Kopiere auf dem Server…".to_string());
+    }
+
+    /// Check whether a running remote copy has finished.
+    pub fn poll_copy(&mut self) {
+        let state = match &self.copy_progress {
+            Some(h) => h.lock().unwrap().state.clone(),
+            None => return,
+        };
+        match state {
+            TransferState::Running => {}
+            TransferState::Done => {
+                self.copy_progress = None;
+                self.set_status("Kopieren abgeschlossen".to_string());
+                self.reload_side(PanelSide::Remote);
+                self.update_remote_free_space();
+            }
+            TransferState::Failed(msg) => {
+                self.copy_progress = None;
+                self.set_status(format!("Kopieren fehlgeschlagen: {}", msg));
+                self.reload_side(PanelSide::Remote);
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Edit (F4)
+    // -----------------------------------------------------------------------
+
+    /// Prepare an editor launch for the selected file.
+    /// For local files the path is returned directly.
+    /// For remote files the file is downloaded synchronously to a temp dir;
+    /// files above `EDIT_SIZE_LIMIT` open a size warning first.
+    /// The result is stored in `self.pending_edit`; the main loop performs the
+    /// actual terminal suspend and process spawn.
+    pub fn prepare_edit(&mut self) {
+        let panel_side = match self.active_side() {
+            Some(side) => side,
+            None => return,
+        };
+        let panel = self.panel(self.active);
+        let name = match panel.selected_entry() {
+            Some(e) if !e.is_dir && e.name != ".." => e.name.clone(),
+            _ => {
+                self.set_status("Kein bearbeitbarer Eintrag ausgewählt".to_string());
+                return;
+            }
+        };
+        self.prepare_edit_file(panel_side, &name);
+    }
+
+    /// Prepare the editor launch for file `name` in the current directory of
+    /// `panel_side` (see `prepare_edit`).
+    fn prepare_edit_file(&mut self, panel_side: PanelSide, name: &str) {
+        match panel_side {
+            PanelSide::Local => {
+                let path = self.local().path.join(self.local().raw_name(name));
+                self.pending_edit = Some(EditRequest::Local { path });
+            }
+            PanelSide::Remote => {
+                let conn = match self.sftp.as_ref() {
+                    Some(c) => c,
+                    None => return,
+                };
+                // The download below blocks the UI, so ask first for big files.
+                let size = conn
+                    .sftp()
+                    .stat(&conn.remote_path.join(self.remote().raw_name(name)))
+                    .ok()
+                    .and_then(|s| s.size)
+                    .unwrap_or(0);
+                if size > EDIT_SIZE_LIMIT {
+                    self.edit_size_dialog = Some(EditSizeDialog { name: name.to_string(), size });
+                    return;
+                }
+                self.download_for_edit(name);
+            }
+        }
+    }
+
+    /// Resolve the size warning: download and edit anyway, or cancel.
+    pub fn resolve_edit_size(&mut self, proceed: bool) {
+        let dlg = match self.edit_size_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        if proceed {
+            self.download_for_edit(&dlg.name);
+        } else {
+            self.set_status("Bearbeiten abgebrochen".to_string());
+        }
+    }
+
+    /// Download remote file `name` into a fresh temp dir and queue the editor.
+    fn download_for_edit(&mut self, name: &str) {
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        let remote_path = conn.remote_path.join(self.remote().raw_name(name));
+        let temp_dir = match edit_temp_root().and_then(|root| {
+            tempfile::Builder::new().prefix("edit-").tempdir_in(&root)
+        }) {
+            Ok(d) => d,
+            Err(e) => {
+                self.set_status(format!("Temp-Verzeichnis: {}", e));
+                return;
+            }
+        };
+        let temp_dir_path = temp_dir.path().to_path_buf();
+        match download_file_to_dir(
+            conn.sftp(),
+            &remote_path,
+            &temp_dir_path,
+            self.settings.chunk_bytes(),
+        ) {
+            Ok(temp_path) => {
+                let mtime_before = std::fs::metadata(&temp_path)
+                    .and_then(|m| m.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                let remote_mtime_before =
+                    conn.sftp().stat(&remote_path).ok().and_then(|s| s.mtime);
+                self.pending_edit = Some(EditRequest::Remote {
+                    temp_path,
+                    remote_path,
+                    mtime_before,
+                    remote_mtime_before,
+                    _temp_dir: temp_dir,
+                });
+            }
+            Err(e) => {
+                self.set_status(format!("Download für Bearbeitung fehlgeschlagen: {}", e));
+            }
+        }
+    }
+
+    /// Called by the main loop after the editor process has exited.
+    /// Checks for changes (remote case), uploads if needed, refreshes listings.
+    pub fn finish_edit(&mut self, req: EditRequest) -> Result<(), AppError> {
+        let (temp_path, mtime_before) = match &req {
+            EditRequest::Local { .. } => {
+                self.local_mut().load_local()?;
+                self.set_status("Editor geschlossen".to_string());
+                return Ok(());
+            }
+            EditRequest::Remote { temp_path, mtime_before, .. } => (temp_path, *mtime_before),
+        };
+        let changed = std::fs::metadata(temp_path)
+            .and_then(|m| m.modified())
+            .map(|t| t > mtime_before)
+            .unwrap_or(false);
+        if !changed {
+            self.set_status("Keine Änderungen, kein Upload".to_string());
+            self.release_edit_temp(req);
+            return Ok(());
+        }
+        if self.settings.confirm_edit_upload {
+            self.edit_upload_dialog = Some(EditUploadDialog { req });
+            return Ok(());
+        }
+        self.upload_edit_checked(req);
+        Ok(())
+    }
+
+    /// Upload a changed remote edit, unless the server copy changed while the
+    /// editor was open: then ask first (edit conflict dialog).
+    fn upload_edit_checked(&mut self, req: EditRequest) {
+        let (remote_path, remote_mtime_before) = match &req {
+            EditRequest::Remote { remote_path, remote_mtime_before, .. } => {
+                (remote_path, *remote_mtime_before)
+            }
+            EditRequest::Local { .. } => return,
+        };
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => return,
+        };
+        // Ask before overwriting when someone else saved the file meanwhile.
+        // The panel session answers unless it timed out while the editor was
+        // open; then a fresh session stats the file, like the upload itself.
+        let remote_mtime_now = match conn.sftp().stat(remote_path) {
+            Ok(stat) => Ok(stat.mtime),
+            Err(_) => {
+                let pw = conn.saved_password.as_ref().map(|z| z.as_str());
+                remote_mtime_fresh(&conn.profile, pw, remote_path)
+            }
+        };
+        let conflict = match (remote_mtime_before, remote_mtime_now) {
+            // Neither session could stat it (removed, unreachable): ask.
+            (_, Err(_)) => Some(true),
+            (Some(before), Ok(Some(now))) if now != before => Some(false),
+            _ => None,
+        };
+        if let Some(unverified) = conflict {
+            self.edit_conflict_dialog = Some(EditConflictDialog { req, unverified });
+            return;
+        }
+        self.upload_edited_file(&req);
+        self.release_edit_temp(req);
+    }
+
+    /// Resolve the upload confirmation: upload the edit, or keep the edited
+    /// copy on disk (cleanable with 'c') and leave the server file alone.
+    pub fn resolve_edit_upload(&mut self, upload: bool) {
+        let dlg = match self.edit_upload_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        if upload {
+            self.upload_edit_checked(dlg.req);
+        } else {
+            self.keep_edit_copy(dlg.req);
+        }
+    }
+
+    /// Skip the upload of a remote edit but keep its temp copy, telling the
+    /// user where it is.
+    fn keep_edit_copy(&mut self, req: EditRequest) {
+        if let EditRequest::Remote { temp_path, _temp_dir: temp_dir, .. } = req {
+            let _ = temp_dir.keep();
+            self.set_status(format!(
+                "Upload abgebrochen — Ihre Version liegt unter {}",
+                temp_path.display()
+            ));
+        }
+    }
+
+    /// Drop a finished remote edit. `_temp_dir` auto-deletes the temp
+    /// directory, unless `keep_edit_temp` is set: then it stays on disk and
+    /// the status message gets its path.
+    fn release_edit_temp(&mut self, req: EditRequest) {
+        if !self.settings.keep_edit_temp {
+            return;
+        }
+        if let EditRequest::Remote { temp_path, _temp_dir: temp_dir, .. } = req {
+            let _ = temp_dir.keep();
+            self.append_status(&format!(" — Temp behalten: {}", temp_path.display()));
+        }
+    }
+
+    /// Upload the edited temp copy of a remote edit and refresh the listing.
+    fn upload_edited_file(&mut self, req: &EditRequest) {
+        let (temp_path, remote_path) = match req {
+            EditRequest::Remote { temp_path, remote_path, .. } => (temp_path, remote_path),
+            EditRequest::Local { .. } => return,
+        };
+        let (profile, saved_pw) = match self.sftp.as_ref() {
+            Some(c) => (c.profile.clone(), c.saved_password.clone()),
+            None => return,
+        };
+        // Use a fresh session: the existing one may have timed out
+        // while the editor was open (SSH2 error -13).
+        let options = TransferOptions::from_settings(&self.settings);
+        match upload_file_fresh(&profile, saved_pw.as_ref().map(|z| z.as_str()), temp_path, remote_path, options) {
+            Ok(()) => {
+                let name = remote_path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.set_status(format!("'{}' hochgeladen", name));
+            }
+            Err(e) => {
+                self.set_status(format!("Upload fehlgeschlagen: {}", e));
+            }
+        }
+        self.reload_side(PanelSide::Remote);
+    }
+
+    /// Resolve the edit conflict dialog: overwrite the server copy, or keep
+    /// the edited copy on disk (cleanable with 'c') and skip the upload.
+    pub fn resolve_edit_conflict(&mut self, overwrite: bool) {
+        let dlg = match self.edit_conflict_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        if overwrite {
+            self.upload_edited_file(&dlg.req);
+            self.release_edit_temp(dlg.req);
+            return;
+        }
+        self.keep_edit_copy(dlg.req);
+    }
+
+    // -----------------------------------------------------------------------
+    // Remote shell ('s')
+    // -----------------------------------------------------------------------
+
+    /// Request an interactive login shell on the connected host, started in
+    /// the current remote directory. The main loop hands the terminal to `ssh`.
+    pub fn open_remote_shell(&mut self) {
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => {
+                self.set_status("Nicht verbunden".to_string());
+                return;
+            }
+        };
+        match ssh_shell_args(&conn.profile, &conn.remote_path.to_string_lossy()) {
+            Ok(args) => self.pending_remote_shell = Some(args),
+            Err(e) => self.set_status(format!("Remote-Shell: {}", e)),
+        }
+    }
+
+    /// Called by the main loop after the `ssh` process has exited.
+    /// `error` is set when `ssh` could not be started at all.
+    pub fn finish_remote_shell(&mut self, error: Option<String>) {
+        self.set_status(match error {
+            Some(e) => format!("Remote-Shell fehlgeschlagen: {}", e),
+            None => "Remote-Shell beendet".to_string(),
+        });
+        // Commands in the shell may have changed the directory contents.
+        self.reload_side(PanelSide::Remote);
+    }
+
+    // -----------------------------------------------------------------------
+    // Shell command ('!')
+    // -----------------------------------------------------------------------
+
+    /// Toggle the visual panel swap (Ctrl+U / Ctrl+S). `active` is left
+    /// alone, so focus stays on the same listing and moves across the screen
+    /// with it; Tab keeps toggling between the same two logical panels.
+    pub fn swap_panels(&mut self) {
+        self.panels_swapped = !self.panels_swapped;
+    }
+
+    /// Toggle single-panel mode (Ctrl+W): only the active panel is drawn,
+    /// using the full width.
+    pub fn toggle_single_panel(&mut self) {
+        self.single_panel = !self.single_panel;
+        self.set_status(if self.single_panel {
+            "Einzelpanel: Tab wechselt das angezeigte Panel".to_string()
+        } else {
+            "Zwei Panels".to_string()
+        });
+    }
+
+    /// Show or hide dotfiles in both panels ('.').
+    pub fn toggle_hidden(&mut self) {
+        let show = !self.panel(self.active).show_hidden;
+        self.left.set_show_hidden(show);
+        self.right.set_show_hidden(show);
+        self.set_status(if show {
+            "Versteckte Dateien: eingeblendet".to_string()
+        } else {
+            "Versteckte Dateien: ausgeblendet".to_string()
+        });
+    }
+
+    /// Switch the size column between rounded units and exact bytes (Ctrl+Z).
+    pub fn toggle_exact_sizes(&mut self) {
+        self.exact_sizes = !self.exact_sizes;
+        self.set_status(if self.exact_sizes {
+            "Größen: exakte Bytes".to_string()
+        } else {
+            "Größen: gerundet".to_string()
+        });
+    }
+
+    /// Show or hide the permissions column of the local panel (Ctrl+O).
+    pub fn toggle_local_permissions(&mut self) {
+        self.local_permissions = !self.local_permissions;
+        self.set_status(if self.local_permissions {
+            "Lokale Rechte: eingeblendet".to_string()
+        } else {
+            "Lokale Rechte: ausgeblendet".to_string()
+        });
+    }
+
+    /// Toggle compare mode ('='): entries are marked as only here, newer,
+    /// older, different or identical relative to the other panel.
+    pub fn toggle_compare(&mut self) {
+        if !self.compare_mode && !self.is_connected() {
+            self.set_status("Vergleich braucht eine Verbindung".to_string());
+            return;
+        }
+        self.compare_mode = !self.compare_mode;
+        self.set_status(if self.compare_mode {
+            "Vergleich: + nur hier  > neuer  < älter  ≠ abweichend  = gleich".to_string()
+        } else {
+            "Vergleich aus".to_string()
+        });
+        self.update_comparison();
+    }
+
+    /// Recompute the compare status of both panels (called every frame, so
+    /// reloads and navigation are picked up). Clears it when compare mode is
+    /// off; a lost connection ends compare mode.
+    pub fn update_comparison(&mut self) {
+        if self.compare_mode && !self.is_connected() {
+            self.compare_mode = false;
+        }
+        if !self.compare_mode {
+            self.left.compare.clear();
+            self.right.compare.clear();
+            return;
+        }
+        let local = compare_entries(&self.local().entries, &self.remote().entries);
+        let remote = compare_entries(&self.remote().entries, &self.local().entries);
+        self.local_mut().compare = local;
+        self.remote_mut().compare = remote;
+    }
+
+    /// Switch the date column between timestamps and relative ages (Ctrl+D).
+    pub fn toggle_relative_dates(&mut self) {
+        self.relative_dates = !self.relative_dates;
+        self.set_status(if self.relative_dates {
+            "Datum: relativ".to_string()
+        } else {
+            "Datum: absolut".to_string()
+        });
+    }
+
+    /// Logical panel drawn in the left half of the screen.
+    ///
+    /// Three pieces of state meet here: `remote_side` (which logical panel
+    /// holds the remote listing), `panels_swapped` (visual flip) and `active`
+    /// (focus). Only this mapping knows about screen positions — rendering and
+    /// mouse hit-testing go through it, everything else works on logical
+    /// panels — so the focus border is always drawn where the focused listing
+    /// actually is, swapped or not.
+    pub fn screen_left(&self) -> ActivePanel {
+        if self.panels_swapped {
+            ActivePanel::Right
+        } else {
+            ActivePanel::Left
+        }
+    }
+
+    /// Open the `!` dialog. Commands run on the remote host when the remote
+    /// panel is active and connected, otherwise in the local directory.
+    pub fn open_shell_dialog(&mut self) {
+        let remote = self.is_remote_active() && self.is_connected();
+        self.shell_dialog = Some(ShellDialog::new(remote));
+    }
+
+    /// Tab in the shell input: complete the whitespace-delimited word before
+    /// the cursor against local entries, relative to the local panel's
+    /// directory. A unique match is completed fully (with `/` for
+    /// directories); several matches are extended to their common prefix and
+    /// listed. Remote commands get no completion.
+    pub fn complete_shell_path(&mut self) {
+        let cwd = self.local().path.clone();
+        let dlg = match self.shell_dialog.as_mut() {
+            Some(d) if d.output.is_none() && !d.remote => d,
+            _ => return,
         };
-        if !entry.is_dir {
-            return;
-        }
-        let conn = match self.sftp.as_mut() {
-            Some(c) => c,
-            None => return,
+        dlg.candidates.clear();
+        let before = dlg.input.before_cursor();
+        let word = &before[before.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0)..];
+        let (dir_part, prefix) = match word.rfind('/') {
+            Some(i) => (&word[..=i], &word[i + 1..]),
+            None => ("", word),
         };
-        match conn.enter_dir(&entry.name) {
-            Ok(entries) => {
-                let path = conn.remote_path.clone();
-                self.right.load_remote(path, entries);
+        let dir = if dir_part.is_empty() {
+            cwd
+        } else {
+            cwd.join(expand_local_path(dir_part))
+        };
+        let read = match fs::read_dir(&dir) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        // Dotfiles only when asked for, as in a shell.
+        let mut matches: Vec<(String, bool)> = read
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                    return None;
+                }
+                let is_dir = e.path().is_dir();
+                Some((name, is_dir))
+            })
+            .collect();
+        matches.sort();
+
+        let completion = match matches.as_slice() {
+            [] => return,
+            [(name, is_dir)] => {
+                let suffix = if *is_dir { "/" } else { " " };
+                format!("{}{}", &name[prefix.len()..], suffix)
             }
-            Err(e) => {
-                self.status_message = Some(format!("Verzeichnis öffnen fehlgeschlagen: {}", e));
+            [(first, _), rest @ ..] => {
+                let mut common = rest.iter().fold(first.len(), |acc, (name, _)| {
+                    first.bytes().zip(name.bytes()).take_while(|(a, b)| a == b).count().min(acc)
+                });
+                while !first.is_char_boundary(common) {
+                    common -= 1;
+                }
+                dlg.candidates = matches
+                    .iter()
+                    .map(|(name, is_dir)| if *is_dir { format!("{}/", name) } else { name.clone() })
+                    .collect();
+                first[prefix.len()..common].to_string()
             }
-        }
+        };
+        dlg.input.insert_str(&completion);
     }
 
-    /// Navigate to parent on the remote side.
-    pub fn remote_go_up(&mut self) {
-        let conn = match self.sftp.as_mut() {
-            Some(c) => c,
+    /// Show full metadata of the highlighted entry in the active panel.
+    pub fn open_info_dialog(&mut self) {
+        let side = match self.active_side() {
+            Some(side) => side,
             None => return,
         };
-        match conn.go_up() {
-            Ok(entries) => {
-                let path = conn.remote_path.clone();
-                self.right.load_remote(path, entries);
-            }
-            Err(e) => {
-                self.status_message = Some(format!("Verzeichnis wechseln fehlgeschlagen: {}", e));
+        let entry = match self.panel(self.active).selected_entry() {
+            Some(e) if e.name != ".." => e.clone(),
+            _ => return,
+        };
+        let result = match side {
+            PanelSide::Local => {
+                local_info(&self.local().path.join(&entry.raw_name), &entry).map_err(|e| e.to_string())
             }
+            PanelSide::Remote => match self.sftp.as_ref() {
+                Some(conn) => remote_info(conn, &entry).map_err(|e| e.to_string()),
+                None => return,
+            },
+        };
+        match result {
+            Ok(dlg) => self.info_dialog = Some(dlg),
+            Err(e) => self.set_status(format!("Details fehlgeschlagen: {}", e)),
         }
     }
 
-    // -----------------------------------------------------------------------
-    // Edit (F4)
-    // -----------------------------------------------------------------------
-
-    /// Prepare an editor launch for the selected file.
-    /// For local files the path is returned directly.
-    /// For remote files the file is downloaded synchronously to a temp dir.
-    /// The result is stored in `self.pending_edit`; the main loop performs the
-    /// actual terminal suspend and process spawn.
-    pub fn prepare_edit(&mut self) {
-        let (panel_side, entry) = match self.active {
-            ActivePanel::Left => {
-                let e = match self.left.entries.get(self.left.selected) {
-                    Some(e) if !e.is_dir && e.name != ".." => e.clone(),
-                    _ => {
-                        self.status_message = Some("Kein bearbeitbarer Eintrag ausgewählt".into());
-                        return;
-                    }
-                };
-                (ActivePanel::Left, e)
-            }
-            ActivePanel::Right => {
-                if !self.is_connected() { return; }
-                let e = match self.right.entries.get(self.right.selected) {
-                    Some(e) if !e.is_dir && e.name != ".." => e.clone(),
-                    _ => {
-                        self.status_message = Some("Kein bearbeitbarer Eintrag ausgewählt".into());
-                        return;
-                    }
-                };
-                (ActivePanel::Right, e)
-            }
+    /// Re-list the remote directory, then plan a one-way sync of the local
+    /// directory onto it and show it as a preview ('m'). The panel listing
+    /// may come from the cache and be up to `listing_cache_ttl_secs` old.
+    pub fn open_sync_dialog(&mut self) {
+        let Some(path) = self.sftp.as_ref().map(|c| c.remote_path.clone()) else {
+            return;
         };
-
-        match panel_side {
-            ActivePanel::Left => {
-                let path = self.left.path.join(&entry.name);
-                self.pending_edit = Some(EditRequest::Local { path });
-            }
-            ActivePanel::Right => {
-                let conn = match self.sftp.as_ref() {
-                    Some(c) => c,
-                    None => return,
-                };
-                let remote_path = conn.remote_path.join(&entry.name);
-                let temp_dir = match tempfile::TempDir::new() {
-                    Ok(d) => d,
-                    Err(e) => {
-                        self.status_message = Some(format!("Temp-Verzeichnis: {}", e));
-                        return;
-                    }
-                };
-                let temp_dir_path = temp_dir.path().to_path_buf();
-                match download_file_to_dir(conn.sftp(), &remote_path, &temp_dir_path) {
-                    Ok(temp_path) => {
-                        let mtime_before = std::fs::metadata(&temp_path)
-                            .and_then(|m| m.modified())
-                            .unwrap_or(SystemTime::UNIX_EPOCH);
-                        self.pending_edit = Some(EditRequest::Remote {
-                            temp_path,
-                            remote_path,
-                            mtime_before,
-                            _temp_dir: temp_dir,
-                        });
-                    }
-                    Err(e) => {
-                        self.status_message =
-                            Some(format!("Download für Bearbeitung fehlgeschlagen: {}", e));
-                    }
-                }
-            }
+        if self.is_transferring() {
+            self.set_status("Übertragung läuft bereits".to_string());
+            return;
         }
+        self.start_remote_listing(path, None, ListingKind::Sync);
+        self.set_status("Sync: lese entferntes Verzeichnis…".to_string());
     }
 
-    /// Called by the main loop after the editor process has exited.
-    /// Checks for changes (remote case), uploads if needed, refreshes listings.
-    pub fn finish_edit(&mut self, req: EditRequest) -> Result<(), AppError> {
-        match req {
-            EditRequest::Local { .. } => {
-                self.left.load_local()?;
-                self.status_message = Some("Editor geschlossen".to_string());
-            }
-            EditRequest::Remote { temp_path, remote_path, mtime_before, .. } => {
-                let changed = std::fs::metadata(&temp_path)
-                    .and_then(|m| m.modified())
-                    .map(|t| t > mtime_before)
-                    .unwrap_or(false);
-
-                if changed {
-                    let (profile, saved_pw) = match self.sftp.as_ref() {
-                        Some(c) => (c.profile.clone(), c.saved_password.clone()),
-                        None => return Ok(()),
-                    };
-                    // Use a fresh session: the existing one may have timed out
-                    // while the editor was open (SSH2 error -13).
-                    match upload_file_fresh(&profile, saved_pw.as_ref().map(|z| z.as_str()), &temp_path, &remote_path) {
-                        Ok(()) => {
-                            let name = remote_path.file_name()
-                                .map(|n| n.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            self.status_message =
-                                Some(format!("'{}' hochgeladen", name));
-                        }
-                        Err(e) => {
-                            self.status_message =
-                                Some(format!("Upload fehlgeschlagen: {}", e));
-                        }
-                    }
-                    if let Some(conn) = self.sftp.as_mut() {
-                        if let Ok(entries) = conn.list_dir() {
-                            let path = conn.remote_path.clone();
-                            self.right.load_remote(path, entries);
-                        }
-                    }
-                } else {
-                    self.status_message = Some("Keine Änderungen, kein Upload".to_string());
-                }
-                // _temp_dir drops here and auto-deletes the temp directory.
-            }
+    /// Open the sync preview for the freshly listed remote directory.
+    fn show_sync_plan(&mut self) {
+        if self.is_transferring() {
+            self.set_status("Übertragung läuft bereits".to_string());
+            return;
         }
-        Ok(())
+        let plan = plan_sync(&self.local().entries, &self.remote().entries);
+        if plan.upload.is_empty() && plan.extra.is_empty() {
+            self.set_status("Nichts zu synchronisieren — Verzeichnisse gleich".to_string());
+            return;
+        }
+        self.status_message = None;
+        self.sync_dialog = Some(SyncDialog { plan, delete_extra: false, selected: 0 });
     }
 
-    // -----------------------------------------------------------------------
-    // Shell command ('!')
-    // -----------------------------------------------------------------------
+    /// Run the previewed sync: delete the remote extras on a background
+    /// session when requested, then upload the planned entries in one batch,
+    /// queued behind the delete.
+    pub fn confirm_sync(&mut self) {
+        let dlg = match self.sync_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        if dlg.delete_extra && !dlg.plan.extra.is_empty() {
+            let extras = dlg
+                .plan
+                .extra
+                .iter()
+                .map(|(name, is_dir)| (self.remote().raw_name(name), *is_dir))
+                .collect();
+            self.start_remote_delete(extras);
+        }
+        let uploads: Vec<FileEntry> = dlg.plan.upload.into_iter().map(|(e, _)| e).collect();
+        if !uploads.is_empty() {
+            self.upload_entries(uploads, None);
+        }
+    }
 
-    /// Toggle the visual panel swap (Ctrl+U / Ctrl+S).
-    pub fn swap_panels(&mut self) {
-        self.panels_swapped = !self.panels_swapped;
+    /// List leftover remote-edit temp copies and offer to delete them.
+    pub fn open_temp_cleanup_dialog(&mut self) {
+        let mut entries: Vec<(String, u64)> = match edit_temp_root().and_then(fs::read_dir) {
+            Ok(rd) => rd
+                .filter_map(|e| e.ok())
+                .map(|e| (e.file_name().to_string_lossy().to_string(), path_size(&e.path())))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        if entries.is_empty() {
+            self.set_status("Keine temporären Dateien vorhanden".to_string());
+            return;
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        self.temp_cleanup_dialog = Some(TempCleanupDialog { entries });
     }
 
-    pub fn open_shell_dialog(&mut self) {
-        self.shell_dialog = Some(ShellDialog::new());
+    /// Delete everything listed in the cleanup dialog and report the reclaimed space.
+    pub fn confirm_temp_cleanup(&mut self) {
+        let dlg = match self.temp_cleanup_dialog.take() {
+            Some(d) => d,
+            None => return,
+        };
+        let root = match edit_temp_root() {
+            Ok(r) => r,
+            Err(e) => {
+                self.set_status(format!("Temp-Verzeichnis: {}", e));
+                return;
+            }
+        };
+        let mut freed = 0u64;
+        let mut errors = Vec::new();
+        for (name, size) in &dlg.entries {
+            let path = root.join(name);
+            let result = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            match result {
+                Ok(()) => freed += size,
+                Err(e) => errors.push(format!("{}: {}", name, e)),
+            }
+        }
+        let freed_str = format_size(freed).trim_start().to_string();
+        self.set_status(if errors.is_empty() {
+            format!("Temp-Dateien entfernt — {} freigegeben", freed_str)
+        } else {
+            format!("{} freigegeben — Fehler: {}", freed_str, errors.join("; "))
+        });
     }
 
     /// Open a shell dialog showing the last 50 lines of the selected remote file.
     /// Uses the existing authenticated SFTP connection — no password prompt.
     pub fn open_tail_dialog(&mut self) {
-        if self.active != ActivePanel::Right {
-            self.status_message = Some("Tail nur für Remote-Dateien (rechtes Panel)".to_string());
+        if !self.is_remote_active() {
+            self.set_status("Tail nur für Remote-Dateien (Remote-Panel)".to_string());
             return;
         }
         let conn = match self.sftp.as_ref() {
             Some(c) => c,
             None => {
-                self.status_message = Some("Nicht verbunden".to_string());
+                self.set_status("Nicht verbunden".to_string());
                 return;
             }
         };
-        let entry = match self.right.entries.get(self.right.selected) {
-            Some(e) if !e.is_dir && e.name != ".." => e,
+        let entry = match self.remote().selected_entry() {
+            Some(e) if !e.is_dir && e.name != ".." => e.clone(),
             _ => {
-                self.status_message = Some("Keine Datei ausgewählt".to_string());
+                self.set_status("Keine Datei ausgewählt".to_string());
                 return;
             }
         };
-        let remote_path = conn.remote_path.join(&entry.name);
+        let remote_path = conn.remote_path.join(&entry.raw_name);
         match conn.tail_remote_file(&remote_path, 50) {
             Ok(lines) => {
-                let mut dlg = ShellDialog::new();
+                let mut dlg = ShellDialog::new(true);
                 dlg.output = Some(lines);
                 dlg.exit_code = Some(0);
                 self.shell_dialog = Some(dlg);
-                self.status_message = Some(format!("Tail – {}", entry.name));
+                self.set_status(format!("Tail – {}", entry.name));
             }
             Err(e) => {
-                let mut dlg = ShellDialog::new();
+                let mut dlg = ShellDialog::new(true);
                 dlg.output = Some(vec![format!("Fehler: {}", e)]);
                 dlg.exit_code = Some(1);
                 self.shell_dialog = Some(dlg);
-                self.status_message = Some("Tail fehlgeschlagen".to_string());
+                self.set_status("Tail fehlgeschlagen".to_string());
             }
         }
     }
@@ -1850,15 +5380,19 @@ impl App {
     /// Execute the command currently typed in the shell dialog.
     /// Captures stdout+stderr and switches the dialog to output phase.
     pub fn run_shell_command(&mut self) {
-        let cmd = match self.shell_dialog.as_ref() {
-            Some(d) if d.output.is_none() => d.input.trim().to_string(),
+        let (cmd, remote) = match self.shell_dialog.as_ref() {
+            Some(d) if d.output.is_none() && d.running.is_none() => (d.input.buf.trim().to_string(), d.remote),
             _ => return,
         };
         if cmd.is_empty() {
             self.shell_dialog = None;
             return;
         }
-        let cwd = self.left.path.clone();
+        if remote {
+            self.run_remote_shell_command(&cmd);
+            return;
+        }
+        let cwd = self.local().path.clone();
         let result = std::process::Command::new("sh")
             .arg("-c")
             .arg(&cmd)
@@ -1885,10 +5419,286 @@ impl App {
             dlg.scroll = 0;
             dlg.exit_code = exit_code;
         }
-        let _ = self.left.load_local();
+        let (keep, pos) = self.local().cursor_snapshot();
+        let _ = self.local_mut().load_local();
+        self.local_mut().reselect_by_name(keep.as_deref(), pos);
+        let code_str = exit_code.map(|c| c.to_string()).unwrap_or_else(|| "?".into());
+        self.set_status(format!("! {} — Exit {}", cmd, code_str));
+    }
+
+    /// Remote variant of `run_shell_command`: starts `cmd` in the current
+    /// remote directory on a connection of its own; `poll_shell_command`
+    /// shows the result. Esc in the dialog gives up on it.
+    fn run_remote_shell_command(&mut self, cmd: &str) {
+        let conn = match self.sftp.as_ref() {
+            Some(c) => c,
+            None => {
+                self.shell_dialog = None;
+                self.set_status("Nicht verbunden".to_string());
+                return;
+            }
+        };
+        let Some(dlg) = self.shell_dialog.as_mut() else {
+            return;
+        };
+        let (profile, saved_pw, dir) = (conn.profile.clone(), conn.saved_password.clone(), conn.remote_path.clone());
+        let slot: ExecSlot = Arc::new(Mutex::new(None));
+        let (slot_clone, cancel, cmd_owned) = (Arc::clone(&slot), Arc::clone(&dlg.cancel), cmd.to_string());
+        std::thread::spawn(move || exec_remote(profile, saved_pw, dir, cmd_owned, slot_clone, cancel));
+        dlg.running = Some(slot);
+    }
+
+    /// Show the output of a finished remote shell command.
+    /// Should be called once per render frame.
+    pub fn poll_shell_command(&mut self) {
+        let Some(dlg) = self.shell_dialog.as_mut() else {
+            return;
+        };
+        let outcome = match dlg.running.as_ref() {
+            Some(slot) => slot.lock().unwrap().take(),
+            None => return,
+        };
+        let Some(outcome) = outcome else {
+            return;
+        };
+        dlg.running = None;
+        let cmd = dlg.input.buf.trim().to_string();
+        let (lines, exit_code) = match outcome {
+            Ok((text, code)) => {
+                let lines: Vec<String> = if text.is_empty() {
+                    vec!["(keine Ausgabe)".to_string()]
+                } else {
+                    text.lines().map(|l| l.to_string()).collect()
+                };
+                (lines, Some(code))
+            }
+            Err(e) => (vec![format!("Fehler: {}", e)], None),
+        };
+
+        if let Some(dlg) = self.shell_dialog.as_mut() {
+            dlg.output = Some(lines);
+            dlg.scroll = 0;
+            dlg.exit_code = exit_code;
+        }
+        self.reload_side(PanelSide::Remote);
         let code_str = exit_code.map(|c| c.to_string()).unwrap_or_else(|| "?".into());
-        self.status_message = Some(format!("! {} — Exit {}", cmd, code_str));
+        self.set_status(format!("! {} (remote) — Exit {}", cmd, code_str));
+    }
+}
+
+/// Command line for `ssh` that opens a login shell in `remote_dir`.
+/// Mirrors the profile: port, key file, and jump host (`-J`). Users and hosts
+/// starting with `-` are refused so they cannot pass as ssh options.
+fn ssh_shell_args(profile: &Profile, remote_dir: &str) -> Result<Vec<String>, String> {
+    let no_option = |what: &str, value: &str| {
+        if value.trim_start().starts_with('-') {
+            Err(format!("{} darf nicht mit '-' beginnen: {}", what, value))
+        } else {
+            Ok(())
+        }
+    };
+    no_option("Benutzer", &profile.user)?;
+    no_option("Host", &profile.host)?;
+    let mut args = vec!["-t".to_string(), "-p".to_string(), profile.port.to_string()];
+    if profile.auth == AuthMethod::Key {
+        if let Some(key) = profile.key_path.as_deref().filter(|k| !k.trim().is_empty()) {
+            args.push("-i".to_string());
+            args.push(expand_local_path(key).to_string_lossy().to_string());
+        }
+    }
+    if let Some(jump) = profile.jump_host.as_deref().filter(|h| !h.trim().is_empty()) {
+        let user = profile.jump_user.as_deref().unwrap_or(&profile.user);
+        let port = profile.jump_port.unwrap_or(22);
+        no_option("Jump-Benutzer", user)?;
+        no_option("Jump-Host", jump)?;
+        args.push("-J".to_string());
+        args.push(format!("{}@{}", user, host_port(jump, port)));
+    }
+    args.push("--".to_string());
+    args.push(format!("{}@{}", profile.user, bare_host(&profile.host)));
+    // The command is parsed by the user's login shell, which may be csh or
+    // fish, so the sh syntax goes through `sh -c`. Fall back to the login
+    // directory if the current one is gone.
+    let script = format!(
+        "cd -- {} 2>/dev/null; exec \"${{SHELL:-/bin/sh}}\" -l",
+        shell_words::quote(remote_dir)
+    );
+    args.push(format!("sh -c {}", shell_words::quote(&script)));
+    Ok(args)
+}
+
+/// Bell or desktop notification for a finished upload or download
+/// (settings `transfer_notify`).
+fn announce_transfer(settings: &Settings, text: &str) {
+    crate::transfer::notify::transfer_finished(settings.transfer_notify, text);
+}
+
+/// Persist the connect time of a profile for the "last used" sort.
+/// Best-effort: a failing load or save must not affect the connection.
+fn record_profile_use(name: &str) {
+    if let Ok(mut store) = ProfileStore::load() {
+        store.touch(name);
+        let _ = store.save();
+    }
+}
+
+/// Remote files above this size need a confirmation before F4 downloads them
+/// for editing; the download runs on the UI thread and freezes it meanwhile.
+/// Meant as the limit for any other feature that pulls a whole remote file
+/// into memory or a viewer, too.
+pub const EDIT_SIZE_LIMIT: u64 = 5 * 1024 * 1024;
+
+/// Longest name of a single entry most filesystems accept (`NAME_MAX`).
+const MAX_NAME_BYTES: usize = 255;
+
+/// Why `name` cannot be used for a new or renamed entry, or None when it can.
+/// With `nested` (mkdir) `/` separates path parts, each checked on its own;
+/// otherwise a `/` would move the entry elsewhere and is rejected.
+pub fn name_error(name: &str, nested: bool) -> Option<String> {
+    if name.contains('\0') {
+        return Some("Nullzeichen im Namen nicht erlaubt".to_string());
+    }
+    if !nested && name.contains('/') {
+        return Some("'/' im Namen nicht erlaubt".to_string());
+    }
+    for part in name.split('/').filter(|p| !p.is_empty()) {
+        if part == "." || part == ".." {
+            return Some(format!("'{}' ist kein gültiger Name", part));
+        }
+        if part.len() > MAX_NAME_BYTES {
+            return Some(format!(
+                "Name zu lang: {} Bytes (max. {})",
+                part.len(),
+                MAX_NAME_BYTES
+            ));
+        }
+    }
+    None
+}
+
+/// Private per-user directory for remote-edit temp copies:
+/// `$XDG_RUNTIME_DIR/vela`, else `$TMPDIR/vela-edit-<uid>`. Created with mode
+/// 0700; an existing path is only used if it is a real directory owned by us
+/// that nobody else can access, so a pre-created dir or symlink is refused.
+fn edit_temp_root() -> std::io::Result<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    // SAFETY: getuid takes no arguments, cannot fail and touches no memory.
+    let uid = unsafe { libc::getuid() };
+    let root = match std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => dir.join("vela"),
+        _ => std::env::temp_dir().join(format!("vela-edit-{}", uid)),
+    };
+    match fs::DirBuilder::new().mode(0o700).create(&root) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    let meta = fs::symlink_metadata(&root)?;
+    if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} ist nicht privat (Eigentümer/Rechte prüfen)", root.display()),
+        ));
+    }
+    Ok(root)
+}
+
+/// Details for a local entry; symlinks report their target's metadata.
+fn local_info(path: &std::path::Path, entry: &FileEntry) -> std::io::Result<InfoDialog> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = fs::metadata(path).or_else(|_| fs::symlink_metadata(path))?;
+    Ok(InfoDialog {
+        path: path.display().to_string(),
+        is_dir: meta.is_dir(),
+        is_symlink: entry.is_symlink,
+        link_target: entry.link_target.clone(),
+        size: Some(meta.len()),
+        mtime: u64::try_from(meta.mtime()).ok(),
+        mode: Some(meta.mode() & 0o7777),
+        uid: Some(meta.uid()),
+        gid: Some(meta.gid()),
+    })
+}
+
+/// Details for a remote entry from a fresh `stat` (falling back to `lstat`
+/// for dangling links).
+fn remote_info(conn: &SftpConnection, entry: &FileEntry) -> Result<InfoDialog, SftpError> {
+    let path = conn.remote_path.join(&entry.raw_name);
+    let stat = conn
+        .sftp()
+        .stat(&path)
+        .or_else(|_| conn.sftp().lstat(&path))
+        .map_err(|e| SftpError::Path(e.to_string()))?;
+    Ok(InfoDialog {
+        path: path.display().to_string(),
+        is_dir: stat.file_type().is_dir(),
+        is_symlink: entry.is_symlink,
+        link_target: entry.link_target.clone(),
+        size: stat.size,
+        mtime: stat.mtime,
+        mode: stat.perm.map(|p| p & 0o7777),
+        uid: stat.uid,
+        gid: stat.gid,
+    })
+}
+
+/// `count_local_tree` over `paths`, run on a worker thread; the result
+/// goes to `slot` unless `cancel` was set meanwhile.
+fn count_local_paths(paths: Vec<PathBuf>, slot: TreeCountSlot, cancel: Arc<AtomicBool>) {
+    let mut count = TreeCount::default();
+    for path in &paths {
+        count_local_tree(path, &mut count, &cancel);
+        if count.truncated || cancel.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+    if !cancel.load(Ordering::Relaxed) {
+        *slot.lock().unwrap() = Some(Ok(count));
+    }
+}
+
+/// Add `path` and, for directories, everything below it to `count`.
+/// Stops as soon as the count reaches its limit or `cancel` is set.
+/// Symlinks are not followed.
+fn count_local_tree(path: &std::path::Path, count: &mut TreeCount, cancel: &AtomicBool) {
+    let is_dir = fs::symlink_metadata(path).map(|m| m.is_dir()).unwrap_or(false);
+    if !count.add(is_dir) || !is_dir || cancel.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(rd) = fs::read_dir(path) {
+        for entry in rd.filter_map(|e| e.ok()) {
+            count_local_tree(&entry.path(), count, cancel);
+            if count.truncated || cancel.load(Ordering::Relaxed) {
+                return;
+            }
+        }
+    }
+}
+
+/// Total size of a file or directory tree in bytes (symlinks are not followed).
+fn path_size(path: &std::path::Path) -> u64 {
+    let meta = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|rd| rd.filter_map(|e| e.ok()).map(|e| path_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Free bytes available to unprivileged users on the filesystem holding `path`.
+fn local_free_space(path: &std::path::Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // Safe: c_path is a valid NUL-terminated string and stat is a valid out-pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
     }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
 }
 
 fn dirs_or_cwd() -> PathBuf {
@@ -1954,3 +5764,240 @@ fn parse_dropped_paths(text: &str) -> Vec<PathBuf> {
     }
     paths
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, is_dir: bool) -> FileEntry {
+        FileEntry {
+            name: name.to_string(),
+            raw_name: OsString::from(name),
+            size: if is_dir { None } else { Some(10) },
+            modified: None,
+            is_dir,
+            permissions: None,
+            is_symlink: false,
+            link_target: None,
+        }
+    }
+
+    /// Panel listing "..", ".config/", "src/", ".env", "a.txt", "b.txt".
+    fn panel() -> PanelState {
+        let mut panel = PanelState::new(PathBuf::from("/home/u"));
+        panel.load_remote(
+            PathBuf::from("/home/u"),
+            vec![
+                entry("..", true),
+                entry(".config", true),
+                entry("src", true),
+                entry(".env", false),
+                entry("a.txt", false),
+                entry("b.txt", false),
+            ],
+        );
+        panel
+    }
+
+    fn names(panel: &PanelState) -> Vec<&str> {
+        panel.visible_entries().map(|(_, e)| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn hiding_dotfiles_keeps_parent_entry() {
+        let mut panel = panel();
+        panel.set_show_hidden(false);
+        assert_eq!(names(&panel), ["..", "src", "a.txt", "b.txt"]);
+        assert_eq!(panel.visible_len(), 4);
+        assert_eq!(panel.hidden_count(), 2);
+    }
+
+    #[test]
+    fn selection_resolves_through_filtered_view() {
+        let mut panel = panel();
+        panel.set_show_hidden(false);
+        panel.select(2);
+        assert_eq!(panel.selected_index(), Some(4));
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("a.txt"));
+        panel.select(99);
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("b.txt"));
+    }
+
+    #[test]
+    fn toggling_keeps_cursor_on_same_entry() {
+        let mut panel = panel();
+        panel.select_name("a.txt");
+        panel.set_show_hidden(false);
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("a.txt"));
+        panel.set_show_hidden(true);
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("a.txt"));
+    }
+
+    #[test]
+    fn hiding_the_selected_entry_keeps_the_row() {
+        let mut panel = panel();
+        panel.select_name(".env");
+        panel.set_show_hidden(false);
+        assert_eq!(panel.selected, 3);
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("b.txt"));
+    }
+
+    #[test]
+    fn hiding_drops_marks_on_hidden_entries() {
+        let mut panel = panel();
+        panel.mark_all();
+        assert_eq!(panel.marked.len(), 5);
+        panel.set_show_hidden(false);
+        let targets: Vec<&str> = panel.target_entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(targets, ["src", "a.txt", "b.txt"]);
+        assert!(!panel.marked.contains(".env"));
+    }
+
+    #[test]
+    fn marking_applies_to_visible_entries_only() {
+        let mut panel = panel();
+        panel.set_show_hidden(false);
+        panel.mark_all();
+        let mut marked: Vec<&str> = panel.marked.iter().map(String::as_str).collect();
+        marked.sort_unstable();
+        assert_eq!(marked, ["a.txt", "b.txt", "src"]);
+        assert_eq!(panel.mark_by_pattern("*", false), 3);
+        panel.invert_marks();
+        assert_eq!(panel.marked.len(), 3);
+        assert_eq!(panel.mark_by_pattern(".e*", true), 0);
+    }
+
+    #[test]
+    fn type_ahead_skips_hidden_entries() {
+        let mut panel = panel();
+        panel.set_show_hidden(false);
+        panel.type_ahead('b');
+        assert_eq!(panel.selected, 3);
+        assert_eq!(panel.selected_entry().map(|e| e.name.as_str()), Some("b.txt"));
+    }
+
+    #[test]
+    fn refresh_applies_filter_to_new_entries() {
+        let mut panel = panel();
+        panel.set_show_hidden(false);
+        panel.refresh_remote(
+            PathBuf::from("/home/u"),
+            vec![entry("..", true), entry(".cache", true), entry("c.txt", false)],
+        );
+        assert_eq!(names(&panel), ["..", "c.txt"]);
+    }
+
+    fn shell_profile() -> Profile {
+        Profile {
+            name: "web".to_string(),
+            host: "example.org".to_string(),
+            port: 2222,
+            user: "deploy".to_string(),
+            auth: AuthMethod::Password,
+            key_path: None,
+            remote_path: None,
+            local_start_path: None,
+            has_saved_password: false,
+            last_used: None,
+            verify_uploads: false,
+            jump_host: None,
+            jump_user: None,
+            jump_port: None,
+            focus_remote: false,
+        }
+    }
+
+    #[test]
+    fn remote_shell_runs_through_sh_after_option_end() {
+        let args = ssh_shell_args(&shell_profile(), "/srv/it's").unwrap();
+        let n = args.len();
+        assert_eq!(args[..n - 1], ["-t", "-p", "2222", "--", "deploy@example.org"]);
+        let words = shell_words::split(&args[n - 1]).unwrap();
+        assert_eq!(words[..2], ["sh", "-c"]);
+        assert_eq!(shell_words::split(&words[2]).unwrap()[..3], ["cd", "--", "/srv/it's"]);
+    }
+
+    #[test]
+    fn remote_shell_refuses_option_like_names() {
+        let mut profile = shell_profile();
+        profile.host = "-oProxyCommand=x".to_string();
+        assert!(ssh_shell_args(&profile, "/").is_err());
+        let mut profile = shell_profile();
+        profile.user = "-l".to_string();
+        assert!(ssh_shell_args(&profile, "/").is_err());
+        let mut profile = shell_profile();
+        profile.jump_host = Some("bastion".to_string());
+        profile.jump_user = Some("-oProxyCommand=x".to_string());
+        assert!(ssh_shell_args(&profile, "/").is_err());
+        profile.jump_user = None;
+        assert!(ssh_shell_args(&profile, "/").unwrap().contains(&"deploy@bastion:22".to_string()));
+    }
+
+    fn target_names(panel: &PanelState) -> Vec<&str> {
+        panel.target_entries().iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parent_entry_is_never_a_target_in_subdirectory() {
+        let mut panel = panel();
+        panel.select(0);
+        panel.toggle_mark();
+        assert!(panel.marked.is_empty());
+        assert!(target_names(&panel).is_empty());
+        panel.mark_all();
+        panel.marked.insert("..".to_string());
+        assert!(!target_names(&panel).contains(&".."));
+        panel.refresh_remote(
+            PathBuf::from("/home/u"),
+            vec![entry("..", true), entry("a.txt", false), entry("b.txt", false)],
+        );
+        assert!(!panel.marked.contains(".."));
+        assert_eq!(target_names(&panel), ["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn marks_survive_parent_entry_appearing_and_vanishing() {
+        let mut panel = PanelState::new(PathBuf::from("/"));
+        panel.load_remote(PathBuf::from("/"), vec![entry("a.txt", false), entry("b.txt", false)]);
+        panel.select(0);
+        panel.toggle_mark();
+        assert_eq!(target_names(&panel), ["a.txt"]);
+        // ".." shifts every index by one; marks are by name and stay put.
+        panel.refresh_remote(
+            PathBuf::from("/"),
+            vec![entry("..", true), entry("a.txt", false), entry("b.txt", false)],
+        );
+        assert_eq!(target_names(&panel), ["a.txt"]);
+        panel.invert_marks();
+        assert_eq!(target_names(&panel), ["b.txt"]);
+        panel.refresh_remote(PathBuf::from("/"), vec![entry("a.txt", false), entry("b.txt", false)]);
+        assert_eq!(target_names(&panel), ["b.txt"]);
+        panel.clear_marks();
+        panel.select(0);
+        assert_eq!(target_names(&panel), ["a.txt"]);
+    }
+
+    #[test]
+    fn queue_label_counts_files_over_the_whole_run() {
+        let queue = QueueProgress {
+            jobs_done: 1,
+            jobs_waiting: 2,
+            files_done: 5,
+            files_waiting: 7,
+            open_ended: false,
+        };
+        assert!(!queue.is_single());
+        assert_eq!(queue.label(2, 4), "Auftrag 2/4 — gesamt 7/16 Dateien");
+        let queue = QueueProgress { open_ended: true, ..queue };
+        assert_eq!(queue.label(2, 4), "Auftrag 2/4 — gesamt 7/16+ Dateien");
+        assert!(QueueProgress::default().is_single());
+    }
+
+    #[test]
+    fn rename_counter_width_is_capped() {
+        assert_eq!(expand_rename_pattern("IMG_{n:03}.{ext}", "a.jpg", 7).unwrap(), "IMG_007.jpg");
+        assert_eq!(expand_rename_pattern("{n:32}", "a", 1).unwrap().len(), 32);
+        assert!(expand_rename_pattern("{n:33}", "a", 1).is_err());
+        assert!(expand_rename_pattern("{n:999999999}", "a", 1).is_err());
+    }
+}
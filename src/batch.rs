@@ -0,0 +1,227 @@
+//! Non-interactive transfer mode for scripts and cron jobs:
+//!
+//!   vela --get <profile> <remote-path> <local-dir>
+//!   vela --put <profile> <local-path> <remote-dir>
+//!
+//! Connects with the given profile, runs a single transfer through the same
+//! `download_batch` / `upload_batch` code the TUI uses, prints progress to
+//! stderr and exits with a status code.
+
+use std::ffi::OsString;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::app::FileEntry;
+use crate::config::profiles::{load_password, AuthMethod, ConfigError, Profile, ProfileStore};
+use crate::config::settings::Settings;
+use crate::connection::sftp::{count_files, download_batch, upload_batch, TransferOptions};
+use crate::startup::PASSWORD_ENV;
+use crate::transfer::queue::{TransferHandle, TransferProgress, TransferState};
+
+const USAGE: &str = "usage: vela --get <profile> <remote-path> <local-dir>\n       \
+                     vela --put <profile> <local-path> <remote-dir>";
+
+#[derive(Debug, Error)]
+pub enum BatchError {
+    #[error("{0}")]
+    Usage(&'static str),
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("Unknown profile: {0}")]
+    UnknownProfile(String),
+    #[error("No password: set {PASSWORD_ENV} or save the password in the keychain")]
+    NoPassword,
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+    #[error("Transfer failed: {0}")]
+    Transfer(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Get,
+    Put,
+}
+
+/// Parsed `--get` / `--put` invocation.
+struct BatchArgs {
+    direction: Direction,
+    profile: String,
+    source: PathBuf,
+    dest: PathBuf,
+}
+
+/// True when the command line asks for batch mode instead of the TUI.
+pub fn is_batch_invocation(args: &[String]) -> bool {
+    matches!(args.first().map(String::as_str), Some("--get") | Some("--put"))
+}
+
+/// Run batch mode and return the process exit code
+/// (0 = success, 1 = transfer/connection error, 2 = usage error).
+pub fn run(args: &[String]) -> i32 {
+    let result = parse_args(args).and_then(|parsed| transfer(&parsed));
+    match result {
+        Ok(()) => 0,
+        Err(BatchError::Usage(msg)) => {
+            eprintln!("{}", msg);
+            2
+        }
+        Err(e) => {
+            eprintln!("vela: {}", e);
+            1
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<BatchArgs, BatchError> {
+    let direction = match args.first().map(String::as_str) {
+        Some("--get") => Direction::Get,
+        Some("--put") => Direction::Put,
+        _ => return Err(BatchError::Usage(USAGE)),
+    };
+    match &args[1..] {
+        [profile, source, dest] => Ok(BatchArgs {
+            direction,
+            profile: profile.clone(),
+            source: PathBuf::from(source),
+            dest: PathBuf::from(dest),
+        }),
+        _ => Err(BatchError::Usage(USAGE)),
+    }
+}
+
+fn transfer(args: &BatchArgs) -> Result<(), BatchError> {
+    let store = ProfileStore::load()?;
+    let profile = store
+        .profiles
+        .into_iter()
+        .find(|p| p.name == args.profile)
+        .ok_or_else(|| BatchError::UnknownProfile(args.profile.clone()))?;
+    let password = resolve_password(&profile)?;
+
+    let (parent, name) = split_source(&args.source)?;
+    let entry = FileEntry {
+        is_dir: args.direction == Direction::Put && args.source.is_dir(),
+        name: name.to_string_lossy().to_string(),
+        raw_name: name,
+        size: None,
+        modified: None,
+        permissions: None,
+        is_symlink: false,
+        link_target: None,
+    };
+
+    let files_total = match args.direction {
+        Direction::Put => count_files(&args.source).max(1),
+        // download_batch counts the remote files itself.
+        Direction::Get => 1,
+    };
+    let handle: TransferHandle = Arc::new(Mutex::new(TransferProgress::new(files_total)));
+    let handle_clone = Arc::clone(&handle);
+    let dest = args.dest.clone();
+    let direction = args.direction;
+    let settings = Settings::load();
+    if let Some(e) = &settings.load_error {
+        eprintln!("vela: using default settings — {}", e);
+    }
+    let options = TransferOptions::from_settings(&settings);
+
+    let worker = std::thread::spawn(move || match direction {
+        Direction::Get => download_batch(profile, password, vec![entry], parent, dest, options, handle_clone),
+        Direction::Put => upload_batch(profile, password, vec![entry], parent, dest, None, options, handle_clone),
+    });
+
+    let state = wait_with_progress(&handle, &worker);
+    let _ = worker.join();
+    let skipped = handle.lock().map(|p| p.files_skipped).unwrap_or(0);
+    if skipped > 0 {
+        eprintln!("vela: skipped {} special file(s) (FIFOs, sockets, devices)", skipped);
+    }
+    match state {
+        TransferState::Failed(msg) => Err(BatchError::Transfer(msg)),
+        _ => Ok(()),
+    }
+}
+
+/// Key auth needs no password; password auth uses $VELA_PASSWORD, then the keychain.
+fn resolve_password(profile: &Profile) -> Result<Option<Zeroizing<String>>, BatchError> {
+    if profile.auth == AuthMethod::Key {
+        return Ok(None);
+    }
+    if let Ok(pw) = std::env::var(PASSWORD_ENV) {
+        return Ok(Some(Zeroizing::new(pw)));
+    }
+    if profile.has_saved_password {
+        if let Some(pw) = load_password(&profile.name)? {
+            return Ok(Some(Zeroizing::new(pw)));
+        }
+    }
+    Err(BatchError::NoPassword)
+}
+
+/// Split a source path into (parent directory, entry name) as the batch
+/// transfer functions expect. A bare name has the current directory as parent.
+fn split_source(path: &Path) -> Result<(PathBuf, OsString), BatchError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .ok_or_else(|| BatchError::InvalidPath(path.display().to_string()))?;
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    Ok((parent, name))
+}
+
+/// Print a progress line to stderr until the transfer thread finishes. On a
+/// terminal the line is redrawn in place; otherwise (logs, CI) each file gets
+/// one plain line when it starts, without control sequences.
+fn wait_with_progress(handle: &TransferHandle, worker: &JoinHandle<()>) -> TransferState {
+    let mut stderr = std::io::stderr();
+    let tty = stderr.is_terminal();
+    let mut last_file = String::new();
+    loop {
+        // Sample before reading the state so a finished thread's final state is seen.
+        let finished = worker.is_finished();
+        let (state, file, line) = match handle.lock() {
+            Ok(p) => (
+                p.state.clone(),
+                p.current_file.clone(),
+                format!(
+                    "[{}/{}] {:>3.0}%  {}",
+                    p.files_done,
+                    p.files_total,
+                    p.overall_fraction() * 100.0,
+                    p.current_file
+                ),
+            ),
+            Err(_) => return TransferState::Failed("progress state poisoned".to_string()),
+        };
+        if tty {
+            let _ = write!(stderr, "\r{}\x1b[K", line);
+        } else if !file.is_empty() && file != last_file {
+            let _ = writeln!(stderr, "{}", line);
+            last_file = file;
+        }
+        let _ = stderr.flush();
+        if state != TransferState::Running {
+            if tty {
+                let _ = writeln!(stderr);
+            }
+            return state;
+        }
+        if finished {
+            if tty {
+                let _ = writeln!(stderr);
+            }
+            return TransferState::Failed("transfer thread exited unexpectedly".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -1 +1,3 @@
 pub mod profiles;
+pub mod settings;
+pub mod state;
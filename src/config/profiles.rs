@@ -56,6 +56,26 @@ pub struct Profile {
     /// Whether a password is stored in the OS keychain for this profile.
     #[serde(default, skip_serializing_if = "is_false")]
     pub has_saved_password: bool,
+    /// Unix timestamp (seconds) of the last successful connection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u64>,
+    /// Compare SHA-256 checksums after every uploaded file (needs `sha256sum`
+    /// on the server). Only settable in profiles.toml.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub verify_uploads: bool,
+    /// Bastion to tunnel through (`ssh -J`). The target host/port are then
+    /// resolved from the bastion. Only settable in profiles.toml.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<String>,
+    /// Login on the bastion; defaults to `user`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_user: Option<String>,
+    /// SSH port of the bastion; defaults to 22.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_port: Option<u16>,
+    /// Focus the remote panel right after connecting (default: local panel).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub focus_remote: bool,
 }
 
 fn is_false(v: &bool) -> bool {
@@ -116,6 +136,24 @@ impl ProfileStore {
             self.profiles[index] = profile;
         }
     }
+
+    /// Record whether the named profile has a password in the keychain.
+    pub fn set_saved_password(&mut self, name: &str, saved: bool) {
+        if let Some(p) = self.profiles.iter_mut().find(|p| p.name == name) {
+            p.has_saved_password = saved;
+        }
+    }
+
+    /// Set `last_used` of the named profile to the current time.
+    pub fn touch(&mut self, name: &str) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        if let Some(p) = self.profiles.iter_mut().find(|p| p.name == name) {
+            p.last_used = Some(now);
+        }
+    }
 }
 
 fn config_path() -> Result<PathBuf, ConfigError> {
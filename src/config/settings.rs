@@ -0,0 +1,333 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Which logical panel holds a given listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelPosition {
+    Left,
+    #[default]
+    Right,
+}
+
+/// Display order of the profile list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileSort {
+    /// Order in profiles.toml.
+    #[default]
+    Insertion,
+    Name,
+    Host,
+    /// Most recently used first; never-used profiles last.
+    LastUsed,
+}
+
+impl ProfileSort {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Insertion => "Reihenfolge",
+            Self::Name => "Name",
+            Self::Host => "Host",
+            Self::LastUsed => "Zuletzt benutzt",
+        }
+    }
+
+    /// Cycle: Insertion → Name → Host → LastUsed → Insertion.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Insertion => Self::Name,
+            Self::Name => Self::Host,
+            Self::Host => Self::LastUsed,
+            Self::LastUsed => Self::Insertion,
+        }
+    }
+}
+
+/// How a finished upload or download is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferNotify {
+    #[default]
+    Off,
+    /// Terminal bell.
+    Bell,
+    /// Desktop notification (notify-send / osascript).
+    Desktop,
+}
+
+/// A file list column right of the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Size,
+    Date,
+    /// Permissions; shown on the remote panel and locally with Ctrl+O.
+    Perm,
+}
+
+/// Width of each optional column in characters. Longer values are cut
+/// off on the right, e.g. `date = 10` shows only the day.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnWidths {
+    pub size: u16,
+    pub date: u16,
+    pub perm: u16,
+}
+
+impl Default for ColumnWidths {
+    fn default() -> Self {
+        Self { size: 9, date: 16, perm: 9 }
+    }
+}
+
+/// Widest column `column_widths` may ask for; wider values are cut down
+/// on load so the layout arithmetic cannot overflow.
+const MAX_COLUMN_WIDTH: u16 = 64;
+
+impl ColumnWidths {
+    /// Every width clamped to 1 – `MAX_COLUMN_WIDTH` characters. To hide a
+    /// column, leave it out of `columns` instead.
+    fn clamped(self) -> Self {
+        let clamp = |w: u16| w.clamp(1, MAX_COLUMN_WIDTH);
+        Self { size: clamp(self.size), date: clamp(self.date), perm: clamp(self.perm) }
+    }
+}
+
+/// Application-wide settings stored in ~/.config/vela/settings.toml.
+/// Every field is optional in the file so older settings files keep working.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Selected theme name ("Auto", "Dark", "Light" or a custom theme).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Logical panel that a new connection populates (default: right).
+    #[serde(default)]
+    pub remote_panel: PanelPosition,
+    /// Sort order of the profile dialog list.
+    #[serde(default)]
+    pub profile_sort: ProfileSort,
+    /// Capture the mouse for clicks and scrolling (also `--mouse`). Off by
+    /// default so the terminal's own text selection keeps working;
+    /// `--no-mouse` overrides it for one run.
+    #[serde(default)]
+    pub mouse: bool,
+    /// Draw with the terminal's default colours only (also `--no-color` / `NO_COLOR`).
+    #[serde(default)]
+    pub no_color: bool,
+    /// Download into `.vela-part` sidecars and continue one left by an
+    /// interrupted download instead of starting over.
+    #[serde(default)]
+    pub resume_downloads: bool,
+    /// Move the cursor down after Space toggles a mark (default). When off,
+    /// Space only toggles the highlighted entry.
+    #[serde(default = "default_true")]
+    pub mark_advances: bool,
+    /// F8 on the local panel moves entries to the desktop trash instead of
+    /// deleting them permanently.
+    #[serde(default)]
+    pub safe_delete: bool,
+    /// F8 on the remote panel moves entries to `~/.vela_trash` on the server
+    /// instead of deleting them permanently.
+    #[serde(default)]
+    pub remote_trash: bool,
+    /// Append every transferred file to ~/.config/vela/transfers.log.
+    #[serde(default)]
+    pub transfer_log: bool,
+    /// Bell or desktop notification when an upload or download finishes.
+    #[serde(default)]
+    pub transfer_notify: TransferNotify,
+    /// Parallel sessions of a download; see `download_workers()`.
+    #[serde(default = "default_download_workers")]
+    pub download_workers: usize,
+    /// Read/write buffer per SFTP transfer call; see `chunk_bytes`.
+    #[serde(default = "default_chunk_bytes")]
+    pub transfer_chunk_bytes: usize,
+    /// Keep the temp copy of a remote F4 edit instead of deleting it, e.g. to
+    /// recover changes after a failed upload.
+    #[serde(default)]
+    pub keep_edit_temp: bool,
+    /// Ask before uploading a changed F4 edit; "no" keeps the temp copy.
+    #[serde(default)]
+    pub confirm_edit_upload: bool,
+    /// Preferred editor command for F4, tried before $EDITOR / $VISUAL.
+    /// May carry arguments, e.g. `"code --wait"`.
+    #[serde(default)]
+    pub editor: Option<String>,
+    /// Number of recently visited remote listings kept for instant
+    /// navigation; 0 turns the cache off.
+    #[serde(default = "default_listing_cache_size")]
+    pub listing_cache_size: usize,
+    /// Seconds a cached remote listing may be shown before it is re-fetched.
+    #[serde(default = "default_listing_cache_ttl_secs")]
+    pub listing_cache_ttl_secs: u64,
+    /// Columns right of the file name, in display order; leave one out to
+    /// hide it.
+    #[serde(default = "default_columns")]
+    pub columns: Vec<Column>,
+    #[serde(default)]
+    pub column_widths: ColumnWidths,
+    /// While disconnected, the remote panel lists the recently used profiles
+    /// for connecting with Enter (default). When off it stays empty.
+    #[serde(default = "default_true")]
+    pub quick_connect: bool,
+    /// List dotfiles (default). When off, `.` toggles them back in.
+    #[serde(default = "default_true")]
+    pub show_hidden: bool,
+    /// Why settings.toml could not be read or parsed; the defaults are used
+    /// instead and `save` leaves the file alone so no hand edit is lost.
+    #[serde(skip)]
+    pub load_error: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Default transfer buffer (64 KiB).
+pub const DEFAULT_CHUNK_BYTES: usize = 64 * 1024;
+const MIN_CHUNK_BYTES: usize = 4 * 1024;
+const MAX_CHUNK_BYTES: usize = 16 * 1024 * 1024;
+
+fn default_chunk_bytes() -> usize {
+    DEFAULT_CHUNK_BYTES
+}
+
+/// Many small files on a high-latency link are dominated by round trips,
+/// so spreading them over several sessions helps.
+const DEFAULT_DOWNLOAD_WORKERS: usize = 4;
+const MAX_DOWNLOAD_WORKERS: usize = 16;
+
+fn default_download_workers() -> usize {
+    DEFAULT_DOWNLOAD_WORKERS
+}
+
+fn default_listing_cache_size() -> usize {
+    16
+}
+
+fn default_listing_cache_ttl_secs() -> u64 {
+    30
+}
+
+fn default_columns() -> Vec<Column> {
+    vec![Column::Size, Column::Date, Column::Perm]
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: None,
+            remote_panel: PanelPosition::default(),
+            profile_sort: ProfileSort::default(),
+            mouse: false,
+            no_color: false,
+            resume_downloads: false,
+            mark_advances: true,
+            safe_delete: false,
+            remote_trash: false,
+            transfer_log: false,
+            transfer_notify: TransferNotify::default(),
+            download_workers: DEFAULT_DOWNLOAD_WORKERS,
+            transfer_chunk_bytes: DEFAULT_CHUNK_BYTES,
+            keep_edit_temp: false,
+            confirm_edit_upload: false,
+            editor: None,
+            listing_cache_size: default_listing_cache_size(),
+            listing_cache_ttl_secs: default_listing_cache_ttl_secs(),
+            columns: default_columns(),
+            column_widths: ColumnWidths::default(),
+            quick_connect: true,
+            show_hidden: true,
+            load_error: None,
+        }
+    }
+}
+
+impl Settings {
+    /// `transfer_chunk_bytes` clamped to 4 KiB – 16 MiB, so a typo cannot
+    /// make every transfer crawl or allocate gigabytes.
+    pub fn chunk_bytes(&self) -> usize {
+        self.transfer_chunk_bytes.clamp(MIN_CHUNK_BYTES, MAX_CHUNK_BYTES)
+    }
+
+    /// `download_workers` clamped to 1 – 16 sessions; 1 downloads serially.
+    pub fn download_workers(&self) -> usize {
+        self.download_workers.clamp(1, MAX_DOWNLOAD_WORKERS)
+    }
+
+    /// Load settings from disk. A missing file yields defaults; so does an
+    /// unreadable or malformed one, with the reason in `load_error`. Column
+    /// widths are clamped to a sane range.
+    pub fn load() -> Self {
+        Self::load_from(&settings_path())
+    }
+
+    fn load_from(path: &Path) -> Self {
+        let parsed = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        };
+        let mut settings = parsed.unwrap_or_else(|e| Self {
+            load_error: Some(format!("{}: {}", path.display(), e)),
+            ..Self::default()
+        });
+        settings.column_widths = settings.column_widths.clamped();
+        settings
+    }
+
+    /// Write settings to disk, except over a file that failed to load.
+    /// Returns false when the file was left alone for that reason; write
+    /// errors are ignored — settings are best-effort.
+    pub fn save(&self) -> bool {
+        if self.load_error.is_some() {
+            return false;
+        }
+        let path = settings_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = toml::to_string_pretty(self) {
+            let _ = fs::write(&path, content);
+        }
+        true
+    }
+}
+
+/// Return ~/.config/vela (falls back to /tmp when HOME is unset).
+pub fn config_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".config").join("vela")
+}
+
+fn settings_path() -> PathBuf {
+    config_dir().join("settings.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_file_falls_back_to_defaults_and_is_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        fs::write(&path, "show_hidden = nope\n").unwrap();
+        let settings = Settings::load_from(&path);
+        assert!(settings.load_error.is_some());
+        assert!(settings.show_hidden);
+        assert!(!settings.save());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "show_hidden = nope\n");
+    }
+
+    #[test]
+    fn missing_file_is_not_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = Settings::load_from(&dir.path().join("settings.toml"));
+        assert!(settings.load_error.is_none());
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::config_dir;
+
+/// Where the user left off, stored in ~/.config/vela/state.toml.
+/// Unlike `Settings` this is written by vela itself, not edited by hand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Last directory of the local panel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_path: Option<PathBuf>,
+    /// Last remote directory per profile name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub remote_paths: BTreeMap<String, String>,
+}
+
+impl SessionState {
+    /// Load the state file. Missing or unreadable files yield an empty state.
+    pub fn load() -> Self {
+        fs::read_to_string(state_path())
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the state file. Errors are ignored — the state is best-effort.
+    pub fn save(&self) {
+        let path = state_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = toml::to_string_pretty(self) {
+            let _ = fs::write(&path, content);
+        }
+    }
+}
+
+fn state_path() -> PathBuf {
+    config_dir().join("state.toml")
+}
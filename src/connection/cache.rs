@@ -0,0 +1,68 @@
+//! Recently visited remote directory listings (settings `listing_cache_size`,
+//! `listing_cache_ttl_secs`).
+//!
+//! Going up and back down shows the cached listing right away; the caller
+//! still re-lists the directory in the background and replaces it. Entries
+//! older than the TTL are never returned.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::app::FileEntry;
+
+struct CachedListing {
+    path: PathBuf,
+    fetched: Instant,
+    entries: Vec<FileEntry>,
+}
+
+/// Small LRU of remote listings keyed by absolute path; most recent first.
+pub struct ListingCache {
+    listings: VecDeque<CachedListing>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl ListingCache {
+    /// A cache holding up to `capacity` listings for `ttl` each.
+    /// A capacity of 0 disables caching.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { listings: VecDeque::new(), capacity, ttl }
+    }
+
+    /// The listing of `path` if it is younger than the TTL; marks it as
+    /// recently used. Expired listings are dropped.
+    pub fn get(&mut self, path: &Path) -> Option<Vec<FileEntry>> {
+        let pos = self.listings.iter().position(|l| l.path == path)?;
+        let listing = self.listings.remove(pos)?;
+        if listing.fetched.elapsed() > self.ttl {
+            return None;
+        }
+        let entries = listing.entries.clone();
+        self.listings.push_front(listing);
+        Some(entries)
+    }
+
+    /// Store a fresh listing of `path`, evicting the least recently used one
+    /// when full.
+    pub fn insert(&mut self, path: PathBuf, entries: Vec<FileEntry>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.listings.retain(|l| l.path != path);
+        self.listings.push_front(CachedListing { path, fetched: Instant::now(), entries });
+        self.listings.truncate(self.capacity);
+    }
+
+    /// Forget `dir` and every directory below it, e.g. after an operation
+    /// changed its contents.
+    pub fn invalidate_tree(&mut self, dir: &Path) {
+        self.listings.retain(|l| !l.path.starts_with(dir));
+    }
+
+    /// Forget everything (the cache belongs to one connection).
+    pub fn clear(&mut self) {
+        self.listings.clear();
+    }
+}
@@ -1,15 +1,24 @@
-use std::io::{Read, Write};
+use std::ffi::{OsStr, OsString};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, UNIX_EPOCH};
 
 use ssh2::{FileStat, KnownHostFileKind, OpenFlags, OpenType, Session, Sftp};
 use thiserror::Error;
 use zeroize::Zeroizing;
 
-use crate::app::FileEntry;
+use crate::app::{FileEntry, TreeCount, TreeCountSlot};
 use crate::config::profiles::{AuthMethod, Profile};
+use crate::paths::expand_local_path;
+use crate::config::settings::Settings;
+use crate::perms::format_permissions;
+use crate::transfer::log::{self as transfer_log, Direction};
 use crate::transfer::queue::{ProgressHandle, TransferHandle, TransferState, UploadState};
 
 #[derive(Debug, Error)]
@@ -24,6 +33,15 @@ pub enum SftpError {
     KeyNotFound(String),
     #[error("Remote path error: {0}")]
     Path(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    /// The server refused to create a file or directory in this directory.
+    #[error("No write permission in {0}")]
+    NoWritePermission(String),
+    #[error("Checksum mismatch after upload: {0}")]
+    ChecksumMismatch(String),
+    #[error("Jump host {host}: {reason}")]
+    JumpHost { host: String, reason: String },
     #[error("Unknown host key for {host}: {fingerprint}")]
     UnknownHostKey {
         host: String,
@@ -40,9 +58,11 @@ pub enum SftpError {
 
 /// An active SFTP session.
 pub struct SftpConnection {
-    // Session must be kept alive alongside Sftp.
-    _session: Session,
-    sftp: Sftp,
+    // Session must be kept alive alongside Sftp; also used for exec channels.
+    session: Session,
+    // Shared with background listings (see `list_dir_in_background`); ssh2
+    // serialises calls on the session, so both threads may use it.
+    sftp: Arc<Sftp>,
     pub remote_path: PathBuf,
     /// The login home directory — never changes after connect.
     /// Used by `change_to_absolute` to expand `~`.
@@ -59,26 +79,16 @@ impl SftpConnection {
     /// Establish an SFTP connection using a profile.
     /// `password` is only used when `profile.auth == AuthMethod::Password`.
     pub fn connect(profile: &Profile, password: Option<&str>) -> Result<Self, SftpError> {
-        let addr = format!("{}:{}", profile.host, profile.port);
-        let tcp = TcpStream::connect(&addr)?;
-        // 10-second connect + read timeout
-        tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
-
-        let mut session = Session::new()?;
-        session.set_tcp_stream(tcp);
-        session.handshake()?;
-
-        verify_host_key(&session, &profile.host, profile.port)?;
-        authenticate(&mut session, profile, password)?;
-
+        // 10-second read timeout
+        let session = open_session(profile, password, Duration::from_secs(10))?;
         let sftp = session.sftp()?;
 
         // Resolve the remote home directory (realpath of ".").
         let home = resolve_home(&sftp)?;
 
         Ok(Self {
-            _session: session,
-            sftp,
+            session,
+            sftp: Arc::new(sftp),
             remote_path: home.clone(),
             home,
             host: profile.host.clone(),
@@ -90,67 +100,49 @@ impl SftpConnection {
 
     /// List the current remote directory. Returns entries sorted: dirs first, then files.
     pub fn list_dir(&self) -> Result<Vec<FileEntry>, SftpError> {
-        let mut entries: Vec<FileEntry> = Vec::new();
-
-        // Always add ".." unless we are at the root "/"
-        if self.remote_path != PathBuf::from("/") {
-            entries.push(FileEntry {
-                name: "..".to_string(),
-                size: None,
-                modified: None,
-                is_dir: true,
-                permissions: None,
-            });
-        }
-
-        let raw = self
-            .sftp
-            .readdir(&self.remote_path)
-            .map_err(|e| SftpError::Path(e.to_string()))?;
-
-        let mut dir_entries: Vec<FileEntry> = raw
-            .into_iter()
-            .map(|(path, stat)| file_entry_from_stat(path, &stat))
-            .collect();
+        read_listing(&self.sftp, &self.remote_path)
+    }
 
-        dir_entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
-        entries.extend(dir_entries);
-        Ok(entries)
+    /// List `dir` on a worker thread sharing this session. The slot is filled
+    /// once the listing is done; `remote_path` is left alone, the caller
+    /// switches to `dir` when it takes the result.
+    pub fn list_dir_in_background(&self, dir: PathBuf) -> ListingSlot {
+        let slot: ListingSlot = Arc::new(Mutex::new(None));
+        let slot_clone = Arc::clone(&slot);
+        let sftp = Arc::clone(&self.sftp);
+        std::thread::spawn(move || {
+            let outcome = read_listing(&sftp, &dir);
+            *slot_clone.lock().unwrap() = Some(outcome);
+        });
+        slot
     }
 
-    /// Change into a subdirectory and return the new listing.
-    pub fn enter_dir(&mut self, name: &str) -> Result<Vec<FileEntry>, SftpError> {
+    /// Path of entry `name` below the current directory, ".." for the parent.
+    pub fn child_path(&self, name: &OsStr) -> Result<PathBuf, SftpError> {
         // Reject names containing '/' to prevent path-traversal via crafted server responses.
-        if name != ".." && name.contains('/') {
-            return Err(SftpError::Path(format!("Invalid entry name: '{}'", name)));
-        }
-        let new_path = if name == ".." {
-            self.remote_path
-                .parent()
-                .unwrap_or(&self.remote_path)
-                .to_path_buf()
+        if name != ".." && name.as_encoded_bytes().contains(&b'/') {
+            return Err(SftpError::Path(format!("Invalid entry name: '{}'", name.to_string_lossy())));
+        }
+        Ok(if name == ".." {
+            self.parent_path()
         } else {
             self.remote_path.join(name)
-        };
-        self.remote_path = new_path;
-        self.list_dir()
+        })
+    }
+
+    /// Parent of the current directory (the root stays the root).
+    pub fn parent_path(&self) -> PathBuf {
+        self.remote_path
+            .parent()
+            .unwrap_or(&self.remote_path)
+            .to_path_buf()
     }
 
     /// Switch to an absolute remote path and return the new listing.
     /// Expands a leading `~` to the login home directory that was resolved
     /// right after connecting (stored in `self.home`).
     pub fn change_to_absolute(&mut self, raw: &str) -> Result<Vec<FileEntry>, SftpError> {
-        let expanded = if raw == "~" || raw.starts_with("~/") {
-            let home_str = self.home.to_string_lossy().to_string();
-            if raw == "~" {
-                home_str
-            } else {
-                // raw starts with "~/" → replace prefix
-                format!("{}{}", home_str, &raw[1..])
-            }
-        } else {
-            raw.to_string()
-        };
+        let expanded = self.expand_tilde(raw);
 
         // Use realpath to canonicalise the path (resolves symlinks, "..", etc.)
         // and simultaneously verify that it exists on the server.
@@ -176,22 +168,44 @@ impl SftpConnection {
         self.list_dir()
     }
 
+    /// Replace a leading `~` (alone or followed by `/`) with the login home.
+    pub fn expand_tilde(&self, raw: &str) -> String {
+        let home_str = self.home.to_string_lossy().to_string();
+        if raw == "~" {
+            home_str
+        } else if raw.starts_with("~/") {
+            // raw starts with "~/" → replace prefix
+            format!("{}{}", home_str, &raw[1..])
+        } else {
+            raw.to_string()
+        }
+    }
+
+    /// Names of the directories (including links to directories) in `dir`.
+    pub fn subdir_names(&self, dir: &Path) -> Result<Vec<String>, SftpError> {
+        let raw = self
+            .sftp
+            .readdir(dir)
+            .map_err(|e| SftpError::Path(e.to_string()))?;
+        Ok(raw
+            .into_iter()
+            .filter(|(path, stat)| {
+                stat.file_type().is_dir()
+                    || (stat.file_type().is_symlink()
+                        && self.sftp.stat(path).map(|s| s.is_dir()).unwrap_or(false))
+            })
+            .filter_map(|(path, _)| path.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect())
+    }
+
     /// Return a reference to the inner SFTP handle for synchronous operations
     /// (e.g. the F4 edit flow that downloads/uploads without a separate session).
     pub fn sftp(&self) -> &Sftp {
         &self.sftp
     }
 
-    /// Navigate to the parent directory.
-    pub fn go_up(&mut self) -> Result<Vec<FileEntry>, SftpError> {
-        if let Some(parent) = self.remote_path.parent().map(|p| p.to_path_buf()) {
-            self.remote_path = parent;
-        }
-        self.list_dir()
-    }
-
     /// Rename (or move) an entry in the current remote directory.
-    pub fn rename(&self, old_name: &str, new_name: &str) -> Result<(), SftpError> {
+    pub fn rename(&self, old_name: &OsStr, new_name: &str) -> Result<(), SftpError> {
         let old = self.remote_path.join(old_name);
         let new = self.remote_path.join(new_name);
         self.sftp
@@ -199,30 +213,142 @@ impl SftpConnection {
             .map_err(|e| SftpError::Path(e.to_string()))
     }
 
-    /// Create a new directory in the current remote directory.
-    pub fn mkdir(&self, name: &str) -> Result<(), SftpError> {
+    /// Move an entry between two absolute remote paths on the same server.
+    pub fn rename_abs(&self, from: &Path, to: &Path) -> Result<(), SftpError> {
+        self.sftp
+            .rename(from, to, None)
+            .map_err(|e| SftpError::Path(e.to_string()))
+    }
+
+    /// Copy a remote file or directory tree to another remote path on the
+    /// same server. SFTP has no native copy, so the data is streamed through
+    /// this session; progress is reported through `handle`.
+    pub fn copy_remote(&self, src: &Path, dst: &Path, handle: &TransferHandle) -> Result<(), SftpError> {
+        copy_remote_recursive(&self.sftp, src, dst, true, handle)
+    }
+
+    /// First free path in `dir` for a copy of `name`: the name itself, then
+    /// "stem (Kopie).ext", "stem (Kopie 2).ext", …
+    pub fn unique_copy_target(&self, dir: &Path, name: &str) -> PathBuf {
+        let candidate = dir.join(name);
+        if self.sftp.lstat(&candidate).is_err() {
+            return candidate;
+        }
+        let (stem, ext) = match name.rfind('.') {
+            Some(pos) if pos > 0 => (&name[..pos], &name[pos..]),
+            _ => (name, ""),
+        };
+        (1..)
+            .map(|n| match n {
+                1 => dir.join(format!("{} (Kopie){}", stem, ext)),
+                n => dir.join(format!("{} (Kopie {}){}", stem, n, ext)),
+            })
+            .find(|p| self.sftp.lstat(p).is_err())
+            .unwrap_or(candidate)
+    }
+
+    /// Create `name` below the current remote directory, including missing
+    /// parents (`a/b/c`, like `mkdir -p`). Components that already exist as
+    /// directories are skipped.
+    pub fn mkdir_p(&self, name: &str) -> Result<(), SftpError> {
+        let mut path = self.remote_path.clone();
+        for part in name.split('/').filter(|p| !p.is_empty() && *p != ".") {
+            path.push(part);
+            match self.sftp.mkdir(&path, 0o755) {
+                Ok(()) => {}
+                // SSH_FX_FAILURE = already exists (or a real failure: check it is a directory)
+                Err(e) if e.code() == ssh2::ErrorCode::SFTP(4)
+                    && self.sftp.stat(&path).map(|s| s.is_dir()).unwrap_or(false) => {}
+                Err(e) => return Err(SftpError::Path(e.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    /// Create an empty file in the current remote directory.
+    /// Fails instead of touching an entry that already exists.
+    pub fn touch(&self, name: &str) -> Result<(), SftpError> {
         let path = self.remote_path.join(name);
         self.sftp
-            .mkdir(&path, 0o755)
+            .open_mode(&path, OpenFlags::CREATE | OpenFlags::EXCLUSIVE | OpenFlags::WRITE, 0o644, OpenType::File)
+            .map(drop)
             .map_err(|e| SftpError::Path(e.to_string()))
     }
 
+    /// Set owner and group (numeric ids) of `name` in the current directory.
+    /// Servers reject this with "permission denied" unless logged in as root.
+    pub fn chown(&self, name: &OsStr, uid: u32, gid: u32) -> Result<(), SftpError> {
+        let path = self.remote_path.join(name);
+        // SFTP sets uid and gid together, so both must be given.
+        let stat = FileStat {
+            size: None,
+            uid: Some(uid),
+            gid: Some(gid),
+            perm: None,
+            atime: None,
+            mtime: None,
+        };
+        self.sftp.setstat(&path, stat).map_err(|e| {
+            if e.code() == ssh2::ErrorCode::SFTP(3) {
+                // SSH_FX_PERMISSION_DENIED
+                SftpError::PermissionDenied(path.display().to_string())
+            } else {
+                SftpError::Path(format!("{}: {}", path.display(), e))
+            }
+        })
+    }
+
     /// Delete a file in the current remote directory.
-    pub fn delete_file(&self, name: &str) -> Result<(), SftpError> {
+    pub fn delete_file(&self, name: &OsStr) -> Result<(), SftpError> {
         let path = self.remote_path.join(name);
         self.sftp
             .unlink(&path)
             .map_err(|e| SftpError::Path(format!("{}: {}", path.display(), e)))
     }
 
+    /// Move the entry `name` of the current directory into `~/.vela_trash`
+    /// instead of deleting it (setting `remote_trash`). Name clashes in the
+    /// trash get a numeric suffix.
+    pub fn move_to_trash(&self, name: &OsStr) -> Result<(), SftpError> {
+        let trash = self.home.join(REMOTE_TRASH_DIR);
+        if let Err(e) = self.sftp.mkdir(&trash, 0o700) {
+            // Servers report an existing directory as a generic failure, so
+            // only a directory that is really there counts as success.
+            let exists = self.sftp.lstat(&trash).map(|st| st.is_dir()).unwrap_or(false);
+            if !exists {
+                return Err(SftpError::Path(format!("{}: {}", trash.display(), e)));
+            }
+        }
+        let src = self.remote_path.join(name);
+        let dest = (1..)
+            .map(|n| match n {
+                1 => trash.join(name),
+                n => {
+                    let mut numbered = name.to_os_string();
+                    numbered.push(format!(".{}", n));
+                    trash.join(numbered)
+                }
+            })
+            .find(|p| self.sftp.lstat(p).is_err())
+            .unwrap_or_else(|| trash.join(name));
+        self.sftp
+            .rename(&src, &dest, None)
+            .map_err(|e| SftpError::Path(format!("{}: {}", src.display(), e)))
+    }
+
     /// Recursively delete a directory and all its contents.
-    pub fn delete_dir(&self, name: &str) -> Result<(), SftpError> {
+    pub fn delete_dir(&self, name: &OsStr) -> Result<(), SftpError> {
         let path = self.remote_path.join(name);
-        self.rmdir_recursive(&path)
+        self.rmdir_recursive(&path, None)
     }
 
-    /// Internal recursive removal: depth-first, files before dirs.
-    fn rmdir_recursive(&self, path: &std::path::Path) -> Result<(), SftpError> {
+    /// Internal recursive removal: depth-first, files before dirs. With a
+    /// `progress` handle every removed entry counts as one finished file.
+    fn rmdir_recursive(
+        &self,
+        path: &std::path::Path,
+        progress: Option<&TransferHandle>,
+    ) -> Result<(), SftpError> {
         let entries = self
             .sftp
             .readdir(path)
@@ -230,19 +356,35 @@ impl SftpConnection {
 
         for (child, stat) in entries {
             if stat.file_type().is_dir() {
-                self.rmdir_recursive(&child)?;
+                self.rmdir_recursive(&child, progress)?;
             } else {
                 self.sftp
                     .unlink(&child)
                     .map_err(|e| SftpError::Path(e.to_string()))?;
+                count_removed(progress, &child);
             }
         }
         self.sftp
             .rmdir(path)
             .map_err(|e| SftpError::Path(e.to_string()))?;
+        count_removed(progress, path);
         Ok(())
     }
 
+    /// Query the free space of the filesystem holding `dir` on a worker
+    /// thread sharing this session, like `list_dir_in_background`. The slot
+    /// holds None (unknown) when `df` fails.
+    pub fn free_space_in_background(&self, dir: PathBuf) -> FreeSpaceSlot {
+        let slot: FreeSpaceSlot = Arc::new(Mutex::new(None));
+        let slot_clone = Arc::clone(&slot);
+        let session = self.session.clone();
+        std::thread::spawn(move || {
+            let free = free_space(&session, &dir).ok();
+            *slot_clone.lock().unwrap() = Some(free);
+        });
+        slot
+    }
+
     /// Read a remote text file over the existing SFTP connection and return the
     /// last `max_lines` lines as a Vec of strings.
     pub fn tail_remote_file(
@@ -279,29 +421,57 @@ impl SftpConnection {
 // Upload — runs inside a dedicated thread with its own SSH session
 // ---------------------------------------------------------------------------
 
+/// What a transfer worker takes from the settings. The caller reads them
+/// once, so a running transfer never goes back to settings.toml.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferOptions {
+    /// Buffer per SFTP read/write call (`Settings::chunk_bytes`).
+    pub chunk: usize,
+    /// Append every file to the transfer log.
+    pub log: bool,
+    /// Continue `.vela-part` sidecars; downloads only.
+    pub resume: bool,
+    /// Parallel sessions of a download (`Settings::download_workers`).
+    pub workers: usize,
+}
+
+impl TransferOptions {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            chunk: settings.chunk_bytes(),
+            log: settings.transfer_log,
+            resume: settings.resume_downloads,
+            workers: settings.download_workers(),
+        }
+    }
+}
+
 /// Open a **single** SSH+SFTP session and upload all `entries` from
 /// `local_dir` to `remote_dir`, reporting progress through `handle`.
+/// With `profile.verify_uploads` every file is checked against a remote
+/// `sha256sum` right after it was written.
 /// On success the state is set to `Done`; on failure to `Failed`.
+#[allow(clippy::too_many_arguments)]
 pub fn upload_batch(
     profile: Profile,
     password: Option<Zeroizing<String>>,
     entries: Vec<crate::app::FileEntry>,
     local_dir: PathBuf,
     remote_dir: PathBuf,
+    dest_name: Option<String>,
+    options: TransferOptions,
     handle: ProgressHandle,
 ) {
     let result = (|| -> Result<(), SftpError> {
-        let addr = format!("{}:{}", profile.host, profile.port);
-        let tcp = TcpStream::connect(&addr)?;
-        tcp.set_read_timeout(Some(Duration::from_secs(30)))?;
-
-        let mut session = Session::new()?;
-        session.set_tcp_stream(tcp);
-        session.handshake()?;
-        verify_host_key(&session, &profile.host, profile.port)?;
-        authenticate(&mut session, &profile, password.as_ref().map(|z| z.as_str()))?;
-
+        let session = open_session(
+            &profile,
+            password.as_ref().map(|z| z.as_str()),
+            Duration::from_secs(30),
+        )?;
         let sftp = session.sftp()?;
+        let verify = profile.verify_uploads.then_some(&session);
+        let chunk = options.chunk;
+        enable_transfer_log(&handle, &profile, options.log);
 
         for entry in &entries {
             // Abort if a previous entry already failed.
@@ -311,11 +481,14 @@ pub fn upload_batch(
                     return Ok(());
                 }
             }
-            let local = local_dir.join(&entry.name);
+            let local = local_dir.join(&entry.raw_name);
+            if skip_special_file(&local, &remote_dir, &handle) {
+                continue;
+            }
             if local.is_dir() {
-                upload_dir_recursive(&sftp, &local, &remote_dir, &handle)?;
+                upload_dir_recursive(&sftp, verify, &local, &remote_dir, chunk, &handle)?;
             } else {
-                upload_file(&sftp, &local, &remote_dir, &handle)?;
+                upload_file(&sftp, verify, &local, &remote_dir, dest_name.as_deref(), chunk, &handle)?;
             }
         }
         Ok(())
@@ -328,13 +501,223 @@ pub fn upload_batch(
                 prog.state = UploadState::Done;
             }
         }
+        Err(SftpError::NoWritePermission(dir)) => {
+            prog.state = UploadState::Failed(format!("Keine Schreibrechte in {}", dir));
+        }
         Err(e) => {
-            prog.state = UploadState::Failed(e.to_string());
+            prog.state = UploadState::Failed(prog.failure_message(&e.to_string()));
+        }
+    }
+}
+
+/// Map a failed create in `dir` to `NoWritePermission` when the server says
+/// permission denied, so the batch stops with one clear message.
+fn create_error(e: ssh2::Error, dir: &Path) -> SftpError {
+    if e.code() == ssh2::ErrorCode::SFTP(3) {
+        // SSH_FX_PERMISSION_DENIED
+        SftpError::NoWritePermission(dir.display().to_string())
+    } else {
+        SftpError::Path(e.to_string())
+    }
+}
+
+/// Result slot of `list_dir_in_background`: None while the listing runs.
+pub type ListingSlot = Arc<Mutex<Option<Result<Vec<FileEntry>, SftpError>>>>;
+
+/// Result slot of `free_space_in_background`: None while `df` runs.
+pub type FreeSpaceSlot = Arc<Mutex<Option<Option<u64>>>>;
+
+/// Open a second connection and count `paths` and everything below them
+/// (preflight for the delete dialog), like `delete_batch` does for the
+/// removal itself. Unreadable directories are counted but not descended
+/// into. Setting `cancel` stops the walk and drops the result.
+pub fn count_remote_paths(
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    paths: Vec<PathBuf>,
+    slot: TreeCountSlot,
+    cancel: Arc<AtomicBool>,
+) {
+    let result = open_fresh_sftp(&profile, password.as_ref().map(|z| z.as_str()))
+        .map(|(_session, sftp)| {
+            let mut count = TreeCount::default();
+            for path in &paths {
+                count_remote_tree(&sftp, path, &mut count, &cancel);
+                if count.truncated || cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+            count
+        })
+        .map_err(|e| e.to_string());
+    if !cancel.load(Ordering::Relaxed) {
+        *slot.lock().unwrap() = Some(result);
+    }
+}
+
+/// Add `path` and everything below it to `count`; symlinks are counted,
+/// not followed.
+fn count_remote_tree(sftp: &Sftp, path: &Path, count: &mut TreeCount, cancel: &AtomicBool) {
+    let is_dir = sftp
+        .lstat(path)
+        .map(|s| s.file_type().is_dir())
+        .unwrap_or(false);
+    if count.add(is_dir) && is_dir {
+        count_remote_dir_contents(sftp, path, count, cancel);
+    }
+}
+
+fn count_remote_dir_contents(sftp: &Sftp, path: &Path, count: &mut TreeCount, cancel: &AtomicBool) {
+    if cancel.load(Ordering::Relaxed) {
+        return;
+    }
+    let entries = match sftp.readdir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for (child, stat) in entries {
+        let is_dir = stat.file_type().is_dir();
+        if !count.add(is_dir) {
+            return;
+        }
+        if is_dir {
+            count_remote_dir_contents(sftp, &child, count, cancel);
+        }
+    }
+}
+
+/// Result slot of `exec_remote`: None while the command runs.
+pub type ExecSlot = Arc<Mutex<Option<Result<(String, i32), SftpError>>>>;
+
+/// Output kept from a remote command; the rest is still read (so the
+/// command never blocks on a full pipe) but dropped.
+const EXEC_OUTPUT_LIMIT: usize = 1024 * 1024;
+
+/// Open a second connection and run `cmd` through the remote shell inside
+/// `dir`, so a long command does not hold up the panel session. stdout and
+/// stderr are drained together, in the order they arrive, so neither pipe
+/// can fill up and stall the command. Setting `cancel` drops the connection,
+/// which hangs up the remote command, and leaves the slot empty.
+pub fn exec_remote(
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    dir: PathBuf,
+    cmd: String,
+    slot: ExecSlot,
+    cancel: Arc<AtomicBool>,
+) {
+    let result = (|| -> Result<Option<(String, i32)>, SftpError> {
+        let session = open_session(&profile, password.as_ref().map(|z| z.as_str()), Duration::from_secs(30))?;
+        let mut channel = session.channel_session()?;
+        let dir = dir.to_string_lossy();
+        channel.exec(&format!("cd -- {} && {}", shell_words::quote(&dir), cmd))?;
+        session.set_blocking(false);
+        let mut output = Vec::new();
+        let mut buf = [0u8; 8192];
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            let mut progressed = false;
+            for stream_id in [0, ssh2::EXTENDED_DATA_STDERR] {
+                match channel.stream(stream_id).read(&mut buf) {
+                    Ok(0) => {}
+                    Ok(n) => {
+                        progressed = true;
+                        let room = EXEC_OUTPUT_LIMIT.saturating_sub(output.len());
+                        output.extend_from_slice(&buf[..n.min(room)]);
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            if !progressed {
+                if channel.eof() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
         }
+        session.set_blocking(true);
+        channel.wait_close()?;
+        Ok(Some((String::from_utf8_lossy(&output).into_owned(), channel.exit_status()?)))
+    })();
+    match result {
+        Ok(None) => {}
+        Ok(Some(done)) => *slot.lock().unwrap() = Some(Ok(done)),
+        Err(e) => *slot.lock().unwrap() = Some(Err(e)),
+    }
+}
+
+/// Free space (in bytes) of the filesystem holding `dir`, by running
+/// `df -Pk` over an exec channel.
+fn free_space(session: &Session, dir: &Path) -> Result<u64, SftpError> {
+    let mut channel = session.channel_session()?;
+    let path = dir.to_string_lossy();
+    channel.exec(&format!("df -Pk -- {}", shell_words::quote(&path)))?;
+    let mut output = String::new();
+    channel.read_to_string(&mut output)?;
+    channel.wait_close()?;
+    parse_df_available(&output)
+        .map(|kib| kib * 1024)
+        .ok_or_else(|| SftpError::Path("df-Ausgabe nicht lesbar".to_string()))
+}
+
+/// Read remote directory `dir`. Returns entries sorted: dirs first, then
+/// files, led by ".." everywhere but at the root.
+fn read_listing(sftp: &Sftp, dir: &Path) -> Result<Vec<FileEntry>, SftpError> {
+    let mut entries: Vec<FileEntry> = Vec::new();
+
+    // Always add ".." unless we are at the root "/"
+    if dir != Path::new("/") {
+        entries.push(FileEntry {
+            name: "..".to_string(),
+            raw_name: "..".into(),
+            size: None,
+            modified: None,
+            is_dir: true,
+            permissions: None,
+            is_symlink: false,
+            link_target: None,
+        });
+    }
+
+    let raw = sftp
+        .readdir(dir)
+        .map_err(|e| SftpError::Path(e.to_string()))?;
+
+    let mut dir_entries: Vec<FileEntry> = raw
+        .into_iter()
+        .map(|(path, stat)| {
+            let mut entry = file_entry_from_stat(&path, &stat);
+            if stat.file_type().is_symlink() {
+                resolve_symlink(sftp, &path, &mut entry);
+            }
+            entry
+        })
+        .collect();
+
+    dir_entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+    entries.extend(dir_entries);
+    Ok(entries)
+}
+
+/// Fill in link details for a symlink entry. `readdir` reports the link
+/// itself; the target decides whether it can be entered like a directory.
+fn resolve_symlink(sftp: &Sftp, path: &Path, entry: &mut FileEntry) {
+    entry.is_symlink = true;
+    entry.link_target = sftp
+        .readlink(path)
+        .ok()
+        .map(|t| t.to_string_lossy().to_string());
+    if let Ok(target) = sftp.stat(path) {
+        entry.is_dir = target.file_type().is_dir();
+        entry.size = if entry.is_dir { None } else { target.size };
     }
 }
 
 /// Count the total number of regular files under a path (recursive).
+/// Symlinked directories below `path` are not followed, matching the upload walk.
 pub fn count_files(path: &Path) -> usize {
     if path.is_file() {
         return 1;
@@ -343,21 +726,94 @@ pub fn count_files(path: &Path) -> usize {
         return 0;
     };
     rd.filter_map(|e| e.ok())
+        .filter(|e| !is_linked_dir(e))
         .map(|e| count_files(&e.path()))
         .sum()
 }
 
-/// Upload a single file to `remote_dir/filename`.
+/// Kind of a local entry that is neither a regular file nor a directory
+/// (symlinks are followed), or None. Opening a FIFO would block forever and
+/// devices or sockets cannot be streamed, so uploads leave these out.
+fn special_file_kind(path: &Path) -> Option<&'static str> {
+    use std::os::unix::fs::FileTypeExt;
+    let file_type = std::fs::metadata(path).ok()?.file_type();
+    if file_type.is_file() || file_type.is_dir() {
+        None
+    } else if file_type.is_fifo() {
+        Some("fifo")
+    } else if file_type.is_socket() {
+        Some("socket")
+    } else if file_type.is_block_device() || file_type.is_char_device() {
+        Some("device")
+    } else {
+        Some("special file")
+    }
+}
+
+/// Count `local` as skipped and note it in the transfer log when it is a
+/// special file. Returns true if the caller must not upload it.
+fn skip_special_file(local: &Path, remote_dir: &Path, handle: &ProgressHandle) -> bool {
+    let Some(kind) = special_file_kind(local) else {
+        return false;
+    };
+    let profile = {
+        let mut h = handle.lock().unwrap();
+        h.files_skipped += 1;
+        h.log_profile.clone()
+    };
+    if let Some(profile) = profile {
+        let remote = remote_dir.join(local.file_name().unwrap_or_default());
+        transfer_log::record_skipped(&profile, Direction::Upload, &remote, local, kind);
+    }
+    true
+}
+
+/// True for a directory entry that is a symlink pointing at a directory.
+fn is_linked_dir(entry: &std::fs::DirEntry) -> bool {
+    entry.file_type().map(|t| t.is_symlink()).unwrap_or(false) && entry.path().is_dir()
+}
+
+/// Upload a single file to `remote_dir/filename`, or to `remote_dir/dest_name`
+/// when given. With a `verify` session the remote checksum is compared afterwards.
+/// The outcome goes to the transfer log when the batch enabled it.
 fn upload_file(
     sftp: &Sftp,
+    verify: Option<&Session>,
     local: &Path,
     remote_dir: &Path,
+    dest_name: Option<&str>,
+    chunk: usize,
     handle: &ProgressHandle,
 ) -> Result<(), SftpError> {
-    let name = local
-        .file_name()
-        .ok_or_else(|| SftpError::Path("no filename".into()))?;
+    let name = match dest_name {
+        Some(dest) => std::ffi::OsStr::new(dest),
+        None => local
+            .file_name()
+            .ok_or_else(|| SftpError::Path("no filename".into()))?,
+    };
     let remote_path = remote_dir.join(name);
+    // Only a file this upload creates may be removed again on failure.
+    let existed = sftp.lstat(&remote_path).is_ok();
+    let start = handle.lock().unwrap().bytes_done;
+    let result = write_remote_file(sftp, verify, local, &remote_path, chunk, handle);
+    let bytes = handle.lock().unwrap().bytes_done.saturating_sub(start);
+    log_file_result(handle, Direction::Upload, &remote_path, local, bytes, &result);
+    if result.is_err() && !existed && sftp.unlink(&remote_path).is_ok() {
+        handle.lock().unwrap().partial_removed = true;
+    }
+    result
+}
+
+/// Copy `local` to `remote_path` for `upload_file`, updating the progress.
+fn write_remote_file(
+    sftp: &Sftp,
+    verify: Option<&Session>,
+    local: &Path,
+    remote_path: &Path,
+    chunk: usize,
+    handle: &ProgressHandle,
+) -> Result<(), SftpError> {
+    let name = remote_path.file_name().unwrap_or_default();
 
     let metadata = std::fs::metadata(local)?;
     let total = metadata.len();
@@ -369,43 +825,177 @@ fn upload_file(
         prog.bytes_total = total;
     }
 
+    // Open the local file first: it has no side effects, so a refused
+    // remote create fails before anything is written on either side.
     let mut local_file = std::fs::File::open(local)?;
     let mut remote_file = sftp
         .open_mode(
-            &remote_path,
+            remote_path,
             OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE,
             0o644,
             OpenType::File,
         )
-        .map_err(|e| SftpError::Path(e.to_string()))?;
+        .map_err(|e| create_error(e, remote_path.parent().unwrap_or(remote_path)))?;
+
+    pipelined_write(&mut local_file, &mut remote_file, chunk, |n| {
+        let mut prog = handle.lock().unwrap();
+        prog.bytes_done = (prog.bytes_done + n).min(total);
+    })?;
+
+    // Close the handle so the server has flushed everything before hashing.
+    drop(remote_file);
+    if let Some(session) = verify {
+        verify_upload(session, local, remote_path)?;
+    }
+
+    {
+        let mut prog = handle.lock().unwrap();
+        prog.files_done += 1;
+        if verify.is_some() {
+            prog.files_verified += 1;
+        }
+    }
+
+    Ok(())
+}
 
-    let mut buf = vec![0u8; 64 * 1024]; // 64 KiB chunks
+/// Chunks kept in flight per file transfer. libssh2 splits a write into
+/// 30000-byte SFTP packets, sends them all and returns once the first are
+/// acknowledged, and it reads ahead four times the requested buffer. Handing
+/// it several chunks at once keeps the link busy while the acks travel back
+/// instead of stalling one round trip per chunk.
+const PIPELINE_DEPTH: usize = 8;
+
+/// Copy `reader` to the remote `file` with up to `PIPELINE_DEPTH` chunks
+/// unacknowledged. `on_acked` gets every acknowledged byte count; libssh2
+/// only reports the contiguous prefix, so the sum is exact even when the
+/// server answers out of order.
+fn pipelined_write(
+    reader: &mut impl Read,
+    file: &mut ssh2::File,
+    chunk: usize,
+    mut on_acked: impl FnMut(u64),
+) -> Result<(), SftpError> {
+    let window = chunk.saturating_mul(PIPELINE_DEPTH);
+    let mut pending: Vec<u8> = Vec::with_capacity(window);
+    let mut buf = vec![0u8; chunk];
+    let mut eof = false;
     loop {
-        let n = local_file.read(&mut buf)?;
+        while !eof && pending.len() + chunk <= window {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                eof = true;
+            } else {
+                pending.extend_from_slice(&buf[..n]);
+            }
+        }
+        if pending.is_empty() {
+            return Ok(());
+        }
+        // Unacknowledged bytes must be passed again unchanged: libssh2 skips
+        // what it already sent and only packs the newly appended tail.
+        let n = file
+            .write(&pending)
+            .map_err(|e| SftpError::Path(e.to_string()))?;
         if n == 0 {
-            break;
+            return Err(SftpError::Path("remote write made no progress".into()));
         }
-        remote_file
-            .write_all(&buf[..n])
+        pending.drain(..n);
+        on_acked(n as u64);
+    }
+}
+
+/// Copy the remote `file` to `writer`. The read buffer is a quarter of the
+/// pipeline window because libssh2 requests four buffers ahead, which puts
+/// `PIPELINE_DEPTH` chunks in flight as on upload. `on_read` gets each
+/// count in file order.
+fn pipelined_read(
+    file: &mut ssh2::File,
+    writer: &mut impl Write,
+    chunk: usize,
+    mut on_read: impl FnMut(u64),
+) -> Result<(), SftpError> {
+    let mut buf = vec![0u8; (chunk.saturating_mul(PIPELINE_DEPTH) / 4).max(chunk)];
+    loop {
+        let n = file
+            .read(&mut buf)
             .map_err(|e| SftpError::Path(e.to_string()))?;
+        if n == 0 {
+            return Ok(());
+        }
+        writer.write_all(&buf[..n])?;
+        on_read(n as u64);
+    }
+}
 
-        let mut prog = handle.lock().unwrap();
-        prog.bytes_done = (prog.bytes_done + n as u64).min(total);
+/// Turn on the transfer log for the batch behind `handle` when `enabled`
+/// (settings `transfer_log`); records are filed under the profile name.
+fn enable_transfer_log(handle: &TransferHandle, profile: &Profile, enabled: bool) {
+    if enabled {
+        handle.lock().unwrap().log_profile = Some(profile.name.clone());
     }
+}
 
-    {
-        let mut prog = handle.lock().unwrap();
-        prog.files_done += 1;
+/// Record a finished file in the transfer log, if the batch enabled it.
+/// `bytes` is what was transferred for this file, also on failure.
+fn log_file_result(
+    handle: &TransferHandle,
+    direction: Direction,
+    remote: &Path,
+    local: &Path,
+    bytes: u64,
+    result: &Result<(), SftpError>,
+) {
+    let profile = match handle.lock().unwrap().log_profile.clone() {
+        Some(p) => p,
+        None => return,
+    };
+    let error = result.as_ref().err().map(|e| e.to_string());
+    transfer_log::record(&profile, direction, remote, local, bytes, error.as_deref());
+}
+
+/// Compare the SHA-256 of `local` with `sha256sum` of `remote`, run over an
+/// exec channel on `session`.
+fn verify_upload(session: &Session, local: &Path, remote: &Path) -> Result<(), SftpError> {
+    let mut hasher = openssl::sha::Sha256::new();
+    let mut file = std::fs::File::open(local)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let local_sum: String = hasher.finish().iter().map(|b| format!("{:02x}", b)).collect();
+
+    let mut channel = session.channel_session()?;
+    let path = remote.to_string_lossy();
+    channel.exec(&format!("sha256sum -- {}", shell_words::quote(&path)))?;
+    let mut output = String::new();
+    channel.read_to_string(&mut output)?;
+    channel.wait_close()?;
+    if channel.exit_status()? != 0 {
+        return Err(SftpError::Path(format!("sha256sum fehlgeschlagen für {}", path)));
     }
+    let remote_sum = output
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| SftpError::Path("sha256sum-Ausgabe nicht lesbar".to_string()))?;
 
+    if !remote_sum.eq_ignore_ascii_case(&local_sum) {
+        return Err(SftpError::ChecksumMismatch(path.into_owned()));
+    }
     Ok(())
 }
 
 /// Recursively upload a directory tree.
 fn upload_dir_recursive(
     sftp: &Sftp,
+    verify: Option<&Session>,
     local_dir: &Path,
     remote_parent: &Path,
+    chunk: usize,
     handle: &ProgressHandle,
 ) -> Result<(), SftpError> {
     let dir_name = local_dir
@@ -417,29 +1007,53 @@ fn upload_dir_recursive(
     match sftp.mkdir(&remote_dir, 0o755) {
         Ok(()) => {}
         Err(e) if e.code() == ssh2::ErrorCode::SFTP(4) => {} // SSH_FX_FAILURE = already exists
-        Err(e) => return Err(SftpError::Path(e.to_string())),
+        Err(e) => return Err(create_error(e, remote_parent)),
     }
 
     let read_dir = std::fs::read_dir(local_dir)?;
     for entry in read_dir.filter_map(|e| e.ok()) {
         let child = entry.path();
+        if is_linked_dir(&entry) {
+            // Don't follow symlinked directories out of the selected tree.
+            continue;
+        }
+        if skip_special_file(&child, &remote_dir, handle) {
+            continue;
+        }
         if child.is_dir() {
-            upload_dir_recursive(sftp, &child, &remote_dir, handle)?;
+            upload_dir_recursive(sftp, verify, &child, &remote_dir, chunk, handle)?;
         } else {
-            upload_file(sftp, &child, &remote_dir, handle)?;
+            upload_file(sftp, verify, &child, &remote_dir, None, chunk, handle)?;
         }
     }
     Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// Download — runs inside a dedicated thread with its own SSH session
+// Download — runs inside a dedicated thread, fanning out to worker sessions
 // ---------------------------------------------------------------------------
 
-/// Open a **single** SSH+SFTP session and download all `entries` from
-/// `remote_dir` into `local_dir`, reporting progress through `handle`.
-/// After counting files the handle's `files_total` is updated so the
-/// progress bar shows accurate percentages from the start.
+/// Directory below the remote home that collects entries deleted with
+/// `remote_trash` enabled.
+pub const REMOTE_TRASH_DIR: &str = ".vela_trash";
+
+/// A single file of a download batch after directories were expanded.
+struct DownloadItem {
+    remote: PathBuf,
+    local: PathBuf,
+    size: u64,
+    /// Remote modification time (Unix seconds), used by the resume check.
+    mtime: Option<u64>,
+}
+
+/// Download all `entries` from `remote_dir` into `local_dir`, reporting
+/// progress through `handle`.
+/// Directories are first expanded into a flat file list (creating the local
+/// directory tree), then up to `options.workers` sessions fetch the files in
+/// parallel. `files_total` / `bytes_total` cover the whole batch, so the
+/// shared counters stay meaningful under concurrent updates.
+/// With `options.resume` set, partial local files left by an interrupted
+/// download are continued instead of fetched again (see `resume_offset`).
 /// On success the state is set to `Done`; on failure to `Failed`.
 pub fn download_batch(
     profile: Profile,
@@ -447,52 +1061,25 @@ pub fn download_batch(
     entries: Vec<crate::app::FileEntry>,
     remote_dir: PathBuf,
     local_dir: PathBuf,
+    options: TransferOptions,
     handle: TransferHandle,
 ) {
+    let password = password.as_ref().map(|z| z.as_str());
     let result = (|| -> Result<(), SftpError> {
-        let addr = format!("{}:{}", profile.host, profile.port);
-        let tcp = TcpStream::connect(&addr)?;
-        tcp.set_read_timeout(Some(Duration::from_secs(30)))?;
-
-        let mut session = Session::new()?;
-        session.set_tcp_stream(tcp);
-        session.handshake()?;
-        verify_host_key(&session, &profile.host, profile.port)?;
-        authenticate(&mut session, &profile, password.as_ref().map(|z| z.as_str()))?;
-
-        let sftp = session.sftp()?;
-
-        // Count total files upfront using the same session (no extra connection).
-        let total: usize = entries
-            .iter()
-            .map(|e| count_sftp_files(&sftp, &remote_dir.join(&e.name)))
-            .sum::<usize>()
-            .max(1);
+        let mut files = Vec::new();
+        // The listing session becomes the first download worker.
+        let (_session, sftp) = open_fresh_sftp(&profile, password)?;
+        for entry in &entries {
+            collect_download_files(&sftp, &remote_dir.join(&entry.raw_name), &local_dir, true, &mut files)?;
+        }
         {
             let mut h = handle.lock().unwrap();
-            h.files_total = total;
-        }
-
-        // Download all entries over the same session.
-        for entry in &entries {
-            // Abort if a previous entry already failed.
-            {
-                let h = handle.lock().unwrap();
-                if matches!(h.state, TransferState::Failed(_)) {
-                    return Ok(());
-                }
-            }
-            let remote = remote_dir.join(&entry.name);
-            let stat = sftp
-                .stat(&remote)
-                .map_err(|e| SftpError::Path(e.to_string()))?;
-            if stat.file_type().is_dir() {
-                download_dir_recursive(&sftp, &remote, &local_dir, &handle)?;
-            } else {
-                download_file(&sftp, &remote, &local_dir, &handle)?;
-            }
+            h.files_total = files.len().max(1);
+            h.bytes_total = files.iter().map(|f| f.size).sum();
+            h.bytes_done = 0;
         }
-        Ok(())
+        enable_transfer_log(&handle, &profile, options.log);
+        run_download_workers(&sftp, &profile, password, files, options, &handle)
     })();
 
     let mut prog = handle.lock().unwrap();
@@ -503,16 +1090,19 @@ pub fn download_batch(
             }
         }
         Err(e) => {
-            prog.state = TransferState::Failed(e.to_string());
+            prog.state = TransferState::Failed(prog.failure_message(&e.to_string()));
         }
     }
 }
 
-
 pub(crate) fn count_sftp_files(sftp: &Sftp, remote: &Path) -> usize {
-    let stat = match sftp.stat(remote) {
-        Ok(s) => s,
-        Err(_) => return 0,
+    count_sftp_tree(sftp, remote, true)
+}
+
+fn count_sftp_tree(sftp: &Sftp, remote: &Path, top_level: bool) -> usize {
+    let stat = match walk_stat(sftp, remote, top_level) {
+        Ok(Some(s)) => s,
+        _ => return 0,
     };
     if !stat.file_type().is_dir() {
         return 1;
@@ -523,98 +1113,510 @@ pub(crate) fn count_sftp_files(sftp: &Sftp, remote: &Path) -> usize {
     };
     entries
         .iter()
-        .map(|(p, _)| count_sftp_files(sftp, p))
+        .map(|(p, _)| count_sftp_tree(sftp, p, false))
         .sum()
 }
 
-/// Download a single remote file into `local_dir/filename`.
-fn download_file(
+/// Stat `path` for a recursive walk without wandering through symlinks.
+/// A link the user selected directly (`top_level`) is followed; inside a tree
+/// only links to files are followed, links to directories and dangling links
+/// are skipped (`Ok(None)`).
+fn walk_stat(sftp: &Sftp, path: &Path, top_level: bool) -> Result<Option<FileStat>, SftpError> {
+    let lstat = sftp.lstat(path).map_err(|e| SftpError::Path(e.to_string()))?;
+    if !lstat.file_type().is_symlink() {
+        return Ok(Some(lstat));
+    }
+    match sftp.stat(path) {
+        Ok(s) if s.file_type().is_dir() && !top_level => Ok(None),
+        Ok(s) => Ok(Some(s)),
+        Err(_) if !top_level => Ok(None),
+        Err(e) => Err(SftpError::Path(e.to_string())),
+    }
+}
+
+/// Expand `remote` into `out`: files are added as-is, directories are created
+/// under `local_parent` and walked recursively. Symlinked directories inside
+/// the tree are not followed (see `walk_stat`).
+fn collect_download_files(
     sftp: &Sftp,
     remote: &Path,
-    local_dir: &Path,
-    handle: &TransferHandle,
+    local_parent: &Path,
+    top_level: bool,
+    out: &mut Vec<DownloadItem>,
 ) -> Result<(), SftpError> {
     let name = remote
         .file_name()
         .ok_or_else(|| SftpError::Path("no filename".into()))?;
-    let local_path = local_dir.join(name);
-
-    // Get remote file size for progress (best-effort)
-    let total = sftp
-        .stat(remote)
-        .ok()
-        .and_then(|s| s.size)
-        .unwrap_or(0);
+    let local = local_parent.join(name);
+    let Some(stat) = walk_stat(sftp, remote, top_level)? else {
+        return Ok(());
+    };
+    if !stat.file_type().is_dir() {
+        out.push(DownloadItem {
+            remote: remote.to_path_buf(),
+            local,
+            size: stat.size.unwrap_or(0),
+            mtime: stat.mtime,
+        });
+        return Ok(());
+    }
 
-    {
-        let mut prog = handle.lock().unwrap();
-        prog.current_file = name.to_string_lossy().to_string();
-        prog.bytes_done = 0;
-        prog.bytes_total = total;
+    // Create local directory (ignore "already exists")
+    match std::fs::create_dir(&local) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(SftpError::Tcp(e)),
+    }
+    let children = sftp.readdir(remote).map_err(|e| SftpError::Path(e.to_string()))?;
+    for (child, _) in children {
+        collect_download_files(sftp, &child, &local, false, out)?;
     }
+    Ok(())
+}
+
+/// Fetch `files` over `first` plus up to `options.workers - 1` extra
+/// sessions sharing one queue. An extra session that cannot be opened (e.g.
+/// the server's `MaxSessions` / `MaxStartups` limit) is left out and the
+/// others take its share, so the download never depends on more than `first`.
+fn run_download_workers(
+    first: &Sftp,
+    profile: &Profile,
+    password: Option<&str>,
+    mut files: Vec<DownloadItem>,
+    options: TransferOptions,
+    handle: &TransferHandle,
+) -> Result<(), SftpError> {
+    let extra = options.workers.min(files.len()).saturating_sub(1);
+    let (resume, chunk) = (options.resume, options.chunk);
+    // Workers pop from the end — reverse so files start in listing order.
+    files.reverse();
+    let queue = Mutex::new(files);
+    std::thread::scope(|scope| {
+        let queue = &queue;
+        let mut threads = vec![scope.spawn(move || download_worker(first, queue, resume, chunk, handle))];
+        threads.extend((0..extra).map(|_| {
+            scope.spawn(move || match open_fresh_sftp(profile, password) {
+                Ok((_session, sftp)) => download_worker(&sftp, queue, resume, chunk, handle),
+                Err(_) => Ok(()),
+            })
+        }));
+        // Join every worker, then report the first error.
+        let results: Vec<Result<(), SftpError>> = threads
+            .into_iter()
+            .map(|t| {
+                t.join()
+                    .unwrap_or_else(|_| Err(SftpError::Path("download worker panicked".into())))
+            })
+            .collect();
+        results.into_iter().collect::<Result<(), SftpError>>()
+    })
+}
+
+/// One download session: take files from `queue` until it is empty or
+/// another worker has failed.
+fn download_worker(
+    sftp: &Sftp,
+    queue: &Mutex<Vec<DownloadItem>>,
+    resume: bool,
+    chunk: usize,
+    handle: &TransferHandle,
+) -> Result<(), SftpError> {
+    loop {
+        if matches!(handle.lock().unwrap().state, TransferState::Failed(_)) {
+            return Ok(());
+        }
+        let item = match queue.lock().unwrap().pop() {
+            Some(item) => item,
+            None => return Ok(()),
+        };
+        // Only a file this download creates may be removed again on failure;
+        // an existing one is the user's own copy. With `resume` the data goes
+        // to a `.vela-part` file that stays so the next attempt continues it,
+        // and the target itself is only replaced once complete.
+        let keep_partial = resume || item.local.symlink_metadata().is_ok();
+        let result = download_item(sftp, &item, resume, chunk, handle);
+        // Size of what arrived, so a failed file logs it.
+        let written = if resume && result.is_err() { partial_path(&item.local) } else { item.local.clone() };
+        let bytes = std::fs::metadata(&written).map(|m| m.len()).unwrap_or(0);
+        log_file_result(handle, Direction::Download, &item.remote, &item.local, bytes, &result);
+        if result.is_err() && !keep_partial && std::fs::remove_file(&item.local).is_ok() {
+            handle.lock().unwrap().partial_removed = true;
+        }
+        if let Err(e) = result {
+            handle.lock().unwrap().state = TransferState::Failed(e.to_string());
+            return Err(e);
+        }
+    }
+}
+
+/// Download a single file, adding its bytes to the batch-wide counters.
+/// With `resume` the bytes go to the file's `.vela-part` sidecar, which is
+/// renamed onto the target when complete; a sidecar left by an interrupted
+/// attempt is appended to and its kept bytes count as done.
+fn download_item(
+    sftp: &Sftp,
+    item: &DownloadItem,
+    resume: bool,
+    chunk: usize,
+    handle: &TransferHandle,
+) -> Result<(), SftpError> {
+    handle.lock().unwrap().current_file = item
+        .remote
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
 
     let mut remote_file = sftp
-        .open(remote)
+        .open(&item.remote)
         .map_err(|e| SftpError::Path(e.to_string()))?;
+    let target = if resume { partial_path(&item.local) } else { item.local.clone() };
+    let offset = if resume { resume_offset(&target, item) } else { 0 };
+    let mut local_file = if offset > 0 {
+        remote_file
+            .seek(SeekFrom::Start(offset))
+            .map_err(|e| SftpError::Path(e.to_string()))?;
+        let file = std::fs::OpenOptions::new().append(true).open(&target)?;
+        let mut h = handle.lock().unwrap();
+        h.bytes_done += offset;
+        h.files_resumed += 1;
+        file
+    } else {
+        std::fs::File::create(&target)?
+    };
 
-    let mut local_file = std::fs::File::create(&local_path)?;
+    pipelined_read(&mut remote_file, &mut local_file, chunk, |n| {
+        handle.lock().unwrap().bytes_done += n;
+    })?;
+    drop(local_file);
+    if resume {
+        std::fs::rename(&target, &item.local)?;
+    }
 
-    let mut buf = vec![0u8; 64 * 1024]; // 64 KiB chunks
-    loop {
-        let n = remote_file
-            .read(&mut buf)
-            .map_err(|e| SftpError::Path(e.to_string()))?;
-        if n == 0 {
-            break;
+    handle.lock().unwrap().files_done += 1;
+    Ok(())
+}
+
+/// Suffix of the sidecar a resumable download writes into.
+const PARTIAL_SUFFIX: &str = ".vela-part";
+
+/// Sidecar of `local` for resumable downloads: `name.vela-part` next to it.
+/// Only this file is ever continued, never the target itself, so a local
+/// copy the user edited is not mistaken for an interrupted download.
+fn partial_path(local: &Path) -> PathBuf {
+    let mut name = local.file_name().unwrap_or_default().to_os_string();
+    name.push(PARTIAL_SUFFIX);
+    local.with_file_name(name)
+}
+
+/// Number of leading bytes of `item` that the sidecar `part` already holds,
+/// or 0 to download from scratch. The sidecar must be non-empty, shorter
+/// than the remote file and written after the remote file last changed.
+fn resume_offset(part: &Path, item: &DownloadItem) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(part) else {
+        return 0;
+    };
+    let len = meta.len();
+    if !meta.is_file() || len == 0 || len >= item.size {
+        return 0;
+    }
+    let local_mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+    match (local_mtime, item.mtime) {
+        (Some(local), Some(remote)) if local >= remote => len,
+        _ => 0,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Remote copy — runs inside a dedicated thread with its own SSH session
+// ---------------------------------------------------------------------------
+
+/// Open a second connection and copy each `(src, dst)` pair on the server,
+/// reporting progress through `handle`.
+/// On success the state is set to `Done`; on failure to `Failed`.
+pub fn copy_batch(
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    pairs: Vec<(PathBuf, PathBuf)>,
+    handle: TransferHandle,
+) {
+    let result = (|| -> Result<(), SftpError> {
+        let conn = SftpConnection::connect(&profile, password.as_ref().map(|z| z.as_str()))?;
+        let total: usize = pairs
+            .iter()
+            .map(|(src, _)| count_sftp_files(&conn.sftp, src))
+            .sum::<usize>()
+            .max(1);
+        handle.lock().unwrap().files_total = total;
+
+        for (src, dst) in &pairs {
+            conn.copy_remote(src, dst, &handle)?;
         }
-        local_file.write_all(&buf[..n])?;
+        Ok(())
+    })();
 
-        let mut prog = handle.lock().unwrap();
-        prog.bytes_done = if total > 0 {
-            (prog.bytes_done + n as u64).min(total)
-        } else {
-            prog.bytes_done + n as u64
-        };
+    let mut prog = handle.lock().unwrap();
+    match result {
+        Ok(()) => prog.state = TransferState::Done,
+        Err(e) => prog.state = TransferState::Failed(e.to_string()),
     }
+}
 
-    {
+// ---------------------------------------------------------------------------
+// Remote delete — runs inside a dedicated thread
+// ---------------------------------------------------------------------------
+
+/// Delete `entries` (name, is_dir) of the remote directory `dir` on a second
+/// connection, so big trees don't block the UI. `files_done` counts removed
+/// entries (files and directories). A failure stops only the entry it
+/// happened in; the others are still tried and the first error is reported.
+pub fn delete_batch(
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    dir: PathBuf,
+    entries: Vec<(OsString, bool)>,
+    handle: TransferHandle,
+) {
+    let result = (|| -> Result<Option<String>, SftpError> {
+        let conn = SftpConnection::connect(&profile, password.as_ref().map(|z| z.as_str()))?;
+        let total: usize = entries
+            .iter()
+            .map(|(name, _)| count_removable(&conn.sftp, &dir.join(name)))
+            .sum::<usize>()
+            .max(1);
+        handle.lock().unwrap().files_total = total;
+
+        let mut first_error = None;
+        for (name, is_dir) in &entries {
+            let path = dir.join(name);
+            let removed = if *is_dir {
+                conn.rmdir_recursive(&path, Some(&handle))
+            } else {
+                conn.sftp
+                    .unlink(&path)
+                    .map(|()| count_removed(Some(&handle), &path))
+                    .map_err(|e| SftpError::Path(e.to_string()))
+            };
+            if let Err(e) = removed {
+                first_error.get_or_insert(format!("'{}': {}", name.to_string_lossy(), e));
+            }
+        }
+        Ok(first_error)
+    })();
+
+    let mut prog = handle.lock().unwrap();
+    prog.state = match result {
+        Ok(None) => TransferState::Done,
+        Ok(Some(msg)) => TransferState::Failed(msg),
+        Err(e) => TransferState::Failed(e.to_string()),
+    };
+}
+
+/// Number of entries `rmdir_recursive` removes for `path` (itself included).
+fn count_removable(sftp: &Sftp, path: &Path) -> usize {
+    match sftp.lstat(path) {
+        Ok(stat) if stat.file_type().is_dir() => {
+            1 + sftp
+                .readdir(path)
+                .map(|children| children.iter().map(|(p, _)| count_removable(sftp, p)).sum())
+                .unwrap_or(0)
+        }
+        Ok(_) => 1,
+        Err(_) => 0,
+    }
+}
+
+/// Record one removed entry on the progress handle, if any.
+fn count_removed(progress: Option<&TransferHandle>, path: &Path) {
+    if let Some(handle) = progress {
         let mut prog = handle.lock().unwrap();
         prog.files_done += 1;
+        prog.current_file = path.display().to_string();
     }
+}
 
-    Ok(())
+// ---------------------------------------------------------------------------
+// Recursive search — runs inside a dedicated thread
+// ---------------------------------------------------------------------------
+
+/// The search stops once this many matches have been collected.
+pub const SEARCH_MAX_RESULTS: usize = 1000;
+
+/// Shared search state — written by the search thread, read by the dialog.
+#[derive(Debug, Default)]
+pub struct SearchProgress {
+    /// Number of entries looked at so far.
+    pub scanned: usize,
+    /// Absolute remote paths of matching entries, shallowest first.
+    pub matches: Vec<PathBuf>,
+    pub finished: bool,
+    pub error: Option<String>,
+}
+
+pub type SearchHandle = Arc<Mutex<SearchProgress>>;
+
+/// Open a second connection and walk `root` breadth-first, collecting every
+/// entry whose name matches `pattern` (see `name_matches`). Symlinked
+/// directories are not followed and unreadable subdirectories are skipped.
+/// Setting `cancel` stops the walk before the next directory is read.
+pub fn search_remote(
+    profile: Profile,
+    password: Option<Zeroizing<String>>,
+    root: PathBuf,
+    pattern: String,
+    handle: SearchHandle,
+    cancel: Arc<AtomicBool>,
+) {
+    let result = (|| -> Result<(), SftpError> {
+        let (_session, sftp) = open_fresh_sftp(&profile, password.as_ref().map(|z| z.as_str()))?;
+        let pattern = pattern.to_lowercase();
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(dir) = queue.pop_front() {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let entries = match sftp.readdir(&dir) {
+                Ok(e) => e,
+                Err(e) if dir == root => return Err(SftpError::Path(e.to_string())),
+                Err(_) => continue,
+            };
+            let mut prog = handle.lock().unwrap();
+            for (path, stat) in entries {
+                prog.scanned += 1;
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                if name_matches(&pattern, &name) {
+                    prog.matches.push(path.clone());
+                    if prog.matches.len() >= SEARCH_MAX_RESULTS {
+                        return Ok(());
+                    }
+                }
+                // readdir reports links themselves, so linked dirs are never queued.
+                if stat.file_type().is_dir() {
+                    queue.push_back(path);
+                }
+            }
+        }
+        Ok(())
+    })();
+
+    let mut prog = handle.lock().unwrap();
+    prog.finished = true;
+    if let Err(e) = result {
+        prog.error = Some(e.to_string());
+    }
+}
+
+/// Match a lower-cased `name` against a lower-cased `pattern`. With `*` or
+/// `?` the pattern is a glob over the whole name, otherwise a substring.
+fn name_matches(pattern: &str, name: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return name.contains(pattern);
+    }
+    glob_match(pattern, name)
 }
 
-/// Recursively download a remote directory tree into `local_parent`.
-fn download_dir_recursive(
+/// Whole-name match of `name` against `pattern` with `*` (any run) and `?`
+/// (one character); every other character matches itself.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    // Position of the last '*' and the name index it currently absorbs up to.
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            // Let the last '*' swallow one more character and retry.
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+fn copy_remote_recursive(
     sftp: &Sftp,
-    remote_dir: &Path,
-    local_parent: &Path,
+    src: &Path,
+    dst: &Path,
+    top_level: bool,
     handle: &TransferHandle,
 ) -> Result<(), SftpError> {
-    let dir_name = remote_dir
-        .file_name()
-        .ok_or_else(|| SftpError::Path("no dirname".into()))?;
-    let local_dir = local_parent.join(dir_name);
-
-    // Create local directory (ignore "already exists")
-    match std::fs::create_dir(&local_dir) {
+    let Some(stat) = walk_stat(sftp, src, top_level)? else {
+        return Ok(());
+    };
+    if !stat.file_type().is_dir() {
+        return copy_remote_file(sftp, src, dst, stat.perm.unwrap_or(0o644), handle);
+    }
+    match sftp.mkdir(dst, stat.perm.map(|p| (p & 0o777) as i32).unwrap_or(0o755)) {
         Ok(()) => {}
-        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
-        Err(e) => return Err(SftpError::Tcp(e)),
+        // SSH_FX_FAILURE = already exists (or a real failure: check it is a directory)
+        Err(e) if e.code() == ssh2::ErrorCode::SFTP(4)
+            && sftp.lstat(dst).map(|s| s.is_dir()).unwrap_or(false) => {}
+        Err(e) => return Err(SftpError::Path(e.to_string())),
+    }
+    let entries = sftp.readdir(src).map_err(|e| SftpError::Path(e.to_string()))?;
+    for (child, _) in entries {
+        if let Some(name) = child.file_name() {
+            copy_remote_recursive(sftp, &child, &dst.join(name), false, handle)?;
+        }
     }
+    Ok(())
+}
 
-    let entries = sftp
-        .readdir(remote_dir)
+/// Stream a single remote file to `dst` in 64 KiB chunks.
+fn copy_remote_file(
+    sftp: &Sftp,
+    src: &Path,
+    dst: &Path,
+    perm: u32,
+    handle: &TransferHandle,
+) -> Result<(), SftpError> {
+    let total = sftp.stat(src).ok().and_then(|s| s.size).unwrap_or(0);
+    {
+        let mut prog = handle.lock().unwrap();
+        prog.current_file = src
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        prog.bytes_done = 0;
+        prog.bytes_total = total;
+    }
+
+    let mut reader = sftp.open(src).map_err(|e| SftpError::Path(e.to_string()))?;
+    let mut writer = sftp
+        .open_mode(
+            dst,
+            OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE,
+            (perm & 0o777) as i32,
+            OpenType::File,
+        )
         .map_err(|e| SftpError::Path(e.to_string()))?;
 
-    for (remote_child, stat) in entries {
-        if stat.file_type().is_dir() {
-            download_dir_recursive(sftp, &remote_child, &local_dir, handle)?;
-        } else {
-            download_file(sftp, &remote_child, &local_dir, handle)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf).map_err(|e| SftpError::Path(e.to_string()))?;
+        if n == 0 {
+            break;
         }
+        writer.write_all(&buf[..n]).map_err(|e| SftpError::Path(e.to_string()))?;
+        handle.lock().unwrap().bytes_done += n as u64;
     }
+
+    handle.lock().unwrap().files_done += 1;
     Ok(())
 }
 
@@ -629,6 +1631,7 @@ pub(crate) fn download_file_to_dir(
     sftp: &Sftp,
     remote: &Path,
     local_dir: &Path,
+    chunk: usize,
 ) -> Result<PathBuf, SftpError> {
     let name = remote
         .file_name()
@@ -639,17 +1642,7 @@ pub(crate) fn download_file_to_dir(
         .open(remote)
         .map_err(|e| SftpError::Path(e.to_string()))?;
     let mut local_file = std::fs::File::create(&local_path)?;
-
-    let mut buf = vec![0u8; 64 * 1024];
-    loop {
-        let n = remote_file
-            .read(&mut buf)
-            .map_err(|e| SftpError::Path(e.to_string()))?;
-        if n == 0 {
-            break;
-        }
-        local_file.write_all(&buf[..n])?;
-    }
+    pipelined_read(&mut remote_file, &mut local_file, chunk, |_| {})?;
     Ok(local_path)
 }
 
@@ -659,6 +1652,7 @@ pub(crate) fn upload_file_to_path(
     sftp: &Sftp,
     local: &Path,
     remote: &Path,
+    chunk: usize,
 ) -> Result<(), SftpError> {
     let mut local_file = std::fs::File::open(local)?;
     let mut remote_file = sftp
@@ -669,41 +1663,292 @@ pub(crate) fn upload_file_to_path(
             OpenType::File,
         )
         .map_err(|e| SftpError::Path(e.to_string()))?;
-
-    let mut buf = vec![0u8; 64 * 1024];
-    loop {
-        let n = local_file.read(&mut buf)?;
-        if n == 0 {
-            break;
-        }
-        remote_file
-            .write_all(&buf[..n])
-            .map_err(|e| SftpError::Path(e.to_string()))?;
-    }
-    Ok(())
+    pipelined_write(&mut local_file, &mut remote_file, chunk, |_| {})
 }
 
 /// Open a **fresh** SSH+SFTP session and upload a single local file to
 /// `remote_path`.  Used by the F4 edit flow where the existing session may
-/// have timed out while the editor was open.
+/// have timed out while the editor was open. The upload goes to the
+/// transfer log like any other when `options.log` is set.
 pub fn upload_file_fresh(
     profile: &Profile,
     password: Option<&str>,
     local: &Path,
     remote: &Path,
+    options: TransferOptions,
 ) -> Result<(), SftpError> {
-    let addr = format!("{}:{}", profile.host, profile.port);
-    let tcp = TcpStream::connect(&addr)?;
-    tcp.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let result = open_fresh_sftp(profile, password)
+        .and_then(|(_session, sftp)| {
+            upload_file_to_path(&sftp, local, remote, options.chunk)
+        });
+    if options.log {
+        let bytes = match result {
+            Ok(()) => std::fs::metadata(local).map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        };
+        let error = result.as_ref().err().map(|e| e.to_string());
+        transfer_log::record(&profile.name, Direction::Upload, remote, local, bytes, error.as_deref());
+    }
+    result
+}
+
+/// Open a **fresh** session and return the modification time (Unix seconds)
+/// of `remote`, or None when the server does not report one. Used by the F4
+/// edit flow when the panel session timed out while the editor was open.
+pub fn remote_mtime_fresh(
+    profile: &Profile,
+    password: Option<&str>,
+    remote: &Path,
+) -> Result<Option<u64>, SftpError> {
+    let (_session, sftp) = open_fresh_sftp(profile, password)?;
+    let stat = sftp.stat(remote).map_err(|e| SftpError::Path(e.to_string()))?;
+    Ok(stat.mtime)
+}
+
+/// Connect, verify and authenticate a new session for a one-off operation.
+/// The session is returned alongside the SFTP channel to keep it alive.
+fn open_fresh_sftp(profile: &Profile, password: Option<&str>) -> Result<(Session, Sftp), SftpError> {
+    let session = open_session(profile, password, Duration::from_secs(30))?;
+    let sftp = session.sftp()?;
+    Ok((session, sftp))
+}
+
+/// Host without surrounding whitespace and without the brackets users may put
+/// around IPv6 literals (`[::1]` → `::1`).
+pub fn bare_host(host: &str) -> &str {
+    let host = host.trim();
+    host.strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+/// `host:port` for display and for `ssh -J`; IPv6 literals get brackets.
+pub fn host_port(host: &str, port: u16) -> String {
+    let host = bare_host(host);
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+/// Key file used when a profile names none.
+const DEFAULT_KEY_PATH: &str = "~/.ssh/id_rsa";
+
+/// The key file `profile` authenticates with, as written and expanded: its
+/// `key_path`, or `DEFAULT_KEY_PATH` when that is unset or blank. Shared by
+/// authentication and `describe_connection` so both name the same file.
+fn profile_key(profile: &Profile) -> (&str, PathBuf) {
+    let raw = profile
+        .key_path
+        .as_deref()
+        .filter(|k| !k.trim().is_empty())
+        .unwrap_or(DEFAULT_KEY_PATH);
+    (raw, expand_local_path(raw))
+}
+
+/// What `open_session` attempts for `profile`, as lines for the status log:
+/// target, socket address, auth method with the expanded key path, the jump
+/// host and an equivalent `ssh` command line. Never includes a password.
+pub fn describe_connection(profile: &Profile) -> Vec<String> {
+    let host = bare_host(&profile.host);
+    let (key_raw, key_path) = profile_key(profile);
+    let jump = profile.jump_host.as_deref().filter(|h| !h.trim().is_empty());
+
+    let mut lines = vec![format!(
+        "Ziel: {}@{}",
+        profile.user,
+        host_port(&profile.host, profile.port)
+    )];
+    lines.push(match jump {
+        Some(_) => format!("Adresse: ({}, {}) über direct-tcpip-Kanal des Jump-Hosts", host, profile.port),
+        None => format!("Adresse: ({}, {}) per TCP", host, profile.port),
+    });
+    lines.push(match profile.auth {
+        AuthMethod::Key => format!(
+            "Auth: Schlüssel {} → {} ({})",
+            key_raw,
+            key_path.display(),
+            key_file_state(&key_path)
+        ),
+        AuthMethod::Password => "Auth: Passwort (wird nicht angezeigt)".to_string(),
+    });
+    if let Some(jump_host) = jump {
+        let user = profile.jump_user.as_deref().unwrap_or(&profile.user);
+        let port = profile.jump_port.unwrap_or(22);
+        lines.push(format!(
+            "Jump-Host: {}@{} — SSH-Agent, sonst Schlüssel {} ({})",
+            user,
+            host_port(jump_host, port),
+            key_path.display(),
+            key_file_state(&key_path)
+        ));
+    }
+
+    let mut ssh = vec!["ssh".to_string(), "-p".to_string(), profile.port.to_string()];
+    if profile.auth == AuthMethod::Key {
+        ssh.push("-i".to_string());
+        ssh.push(key_path.to_string_lossy().to_string());
+    }
+    if let Some(jump_host) = jump {
+        let user = profile.jump_user.as_deref().unwrap_or(&profile.user);
+        ssh.push("-J".to_string());
+        ssh.push(format!("{}@{}", user, host_port(jump_host, profile.jump_port.unwrap_or(22))));
+    }
+    ssh.push(format!("{}@{}", profile.user, host));
+    lines.push(format!("Vergleichbar: {}", shell_words::join(&ssh)));
+    lines
+}
 
+/// Whether the key file exists and has permissions `authenticate` accepts.
+fn key_file_state(path: &Path) -> String {
+    match std::fs::metadata(path) {
+        Err(_) => "fehlt".to_string(),
+        Ok(meta) => {
+            let mode = meta.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                format!("Rechte {:04o} — zu offen, wird abgelehnt", mode)
+            } else {
+                format!("vorhanden, Rechte {:04o}", mode)
+            }
+        }
+    }
+}
+
+/// Connect to the profile's host — directly or through `jump_host` — then
+/// handshake, verify the host key and authenticate. Every session (panel,
+/// transfers, search, edit) is opened here so all of them honour the bastion.
+fn open_session(profile: &Profile, password: Option<&str>, timeout: Duration) -> Result<Session, SftpError> {
     let mut session = Session::new()?;
-    session.set_tcp_stream(tcp);
+    match profile.jump_host.as_deref().filter(|h| !h.trim().is_empty()) {
+        Some(jump_host) => {
+            let stream = open_jump_tunnel(profile, bare_host(jump_host))?;
+            stream.set_read_timeout(Some(timeout))?;
+            session.set_tcp_stream(stream);
+        }
+        None => {
+            // A (host, port) tuple resolves names and IPv4/IPv6 literals alike;
+            // "host:port" strings are ambiguous for IPv6.
+            let tcp = TcpStream::connect((bare_host(&profile.host), profile.port))?;
+            tcp.set_read_timeout(Some(timeout))?;
+            session.set_tcp_stream(tcp);
+        }
+    }
     session.handshake()?;
-    verify_host_key(&session, &profile.host, profile.port)?;
+    verify_host_key(&session, bare_host(&profile.host), profile.port)?;
     authenticate(&mut session, profile, password)?;
+    Ok(session)
+}
 
-    let sftp = session.sftp()?;
-    upload_file_to_path(&sftp, local, remote)
+// ---------------------------------------------------------------------------
+// Jump host — the target session runs over a direct-tcpip channel
+// ---------------------------------------------------------------------------
+
+/// Log in to the bastion, open a direct-tcpip channel to the target and
+/// return a local socket connected to it. libssh2 needs a real file
+/// descriptor for the target session, so a background thread shuttles bytes
+/// between the channel and the other end of a socket pair. The thread (and
+/// the bastion session) ends when the target session closes its end.
+fn open_jump_tunnel(profile: &Profile, jump_host: &str) -> Result<UnixStream, SftpError> {
+    let port = profile.jump_port.unwrap_or(22);
+    let user = profile.jump_user.as_deref().unwrap_or(&profile.user);
+    let hop_err = |e: SftpError| match e {
+        // Keep host key errors intact so the host key dialog can handle them.
+        SftpError::UnknownHostKey { .. } | SftpError::HostKeyMismatch { .. } => e,
+        other => SftpError::JumpHost { host: jump_host.to_string(), reason: other.to_string() },
+    };
+
+    let (bastion, channel) = (|| -> Result<(Session, ssh2::Channel), SftpError> {
+        let tcp = TcpStream::connect((jump_host, port))?;
+        tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
+        let mut bastion = Session::new()?;
+        bastion.set_tcp_stream(tcp);
+        bastion.handshake()?;
+        verify_host_key(&bastion, jump_host, port)?;
+        authenticate_jump(&mut bastion, profile, user)?;
+        let channel = bastion.channel_direct_tcpip(bare_host(&profile.host), profile.port, None)?;
+        Ok((bastion, channel))
+    })()
+    .map_err(hop_err)?;
+
+    let (ours, theirs) = UnixStream::pair()?;
+    std::thread::spawn(move || pump_tunnel(bastion, channel, ours));
+    Ok(theirs)
+}
+
+/// Authenticate on the bastion with the SSH agent, falling back to the
+/// profile's key file. Passwords are only ever sent to the target host.
+fn authenticate_jump(session: &mut Session, profile: &Profile, user: &str) -> Result<(), SftpError> {
+    if session.userauth_agent(user).is_ok() && session.authenticated() {
+        return Ok(());
+    }
+    let (_, key_path) = profile_key(profile);
+    if key_path.exists() {
+        let _ = session.userauth_pubkey_file(user, None, &key_path, None);
+    }
+    if !session.authenticated() {
+        return Err(SftpError::AuthFailed);
+    }
+    Ok(())
+}
+
+/// Copy bytes both ways between the bastion channel and the local socket
+/// until either side closes. Runs non-blocking on one thread because a
+/// libssh2 session must not be driven from two threads at once.
+fn pump_tunnel(bastion: Session, mut channel: ssh2::Channel, mut local: UnixStream) {
+    bastion.set_blocking(false);
+    if local.set_nonblocking(true).is_err() {
+        return;
+    }
+    let mut buf = vec![0u8; 32 * 1024];
+    loop {
+        let mut idle = true;
+
+        match channel.read(&mut buf) {
+            Ok(0) if channel.eof() => break,
+            Ok(0) => {}
+            Ok(n) => {
+                if write_fully(&mut local, &buf[..n]).is_err() {
+                    break;
+                }
+                idle = false;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(_) => break,
+        }
+
+        match local.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if write_fully(&mut channel, &buf[..n]).is_err() {
+                    break;
+                }
+                idle = false;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(_) => break,
+        }
+
+        if idle {
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+    let _ = channel.close();
+}
+
+/// `write_all` for a non-blocking writer: retry until everything is written.
+fn write_fully(w: &mut impl Write, mut data: &[u8]) -> std::io::Result<()> {
+    while !data.is_empty() {
+        match w.write(data) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(n) => data = &data[n..],
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -717,11 +1962,7 @@ fn authenticate(
 ) -> Result<(), SftpError> {
     match &profile.auth {
         AuthMethod::Key => {
-            let key_path_raw = profile
-                .key_path
-                .as_deref()
-                .unwrap_or("~/.ssh/id_rsa");
-            let key_path = expand_tilde(key_path_raw);
+            let (_, key_path) = profile_key(profile);
             if !key_path.exists() {
                 return Err(SftpError::KeyNotFound(
                     key_path.display().to_string(),
@@ -753,6 +1994,17 @@ fn authenticate(
     Ok(())
 }
 
+/// Extract the "Available" column (KiB) from POSIX `df -Pk` output.
+fn parse_df_available(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()
+}
+
 fn resolve_home(sftp: &Sftp) -> Result<PathBuf, SftpError> {
     // "." resolves to the user's home on most SSH servers
     let canonical = sftp
@@ -761,11 +2013,9 @@ fn resolve_home(sftp: &Sftp) -> Result<PathBuf, SftpError> {
     Ok(canonical)
 }
 
-fn file_entry_from_stat(path: PathBuf, stat: &FileStat) -> FileEntry {
-    let name = path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_default();
+fn file_entry_from_stat(path: &Path, stat: &FileStat) -> FileEntry {
+    let raw_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    let name = raw_name.to_string_lossy().to_string();
 
     let is_dir = stat
         .file_type()
@@ -781,27 +2031,16 @@ fn file_entry_from_stat(path: PathBuf, stat: &FileStat) -> FileEntry {
 
     FileEntry {
         name,
+        raw_name,
         size,
         modified,
         is_dir,
         permissions,
+        is_symlink: false,
+        link_target: None,
     }
 }
 
-/// Convert a Unix mode bitmask into a `rwxr-xr-x` style string.
-fn format_permissions(mode: u32) -> String {
-    let flags = [
-        (0o400, 'r'), (0o200, 'w'), (0o100, 'x'),
-        (0o040, 'r'), (0o020, 'w'), (0o010, 'x'),
-        (0o004, 'r'), (0o002, 'w'), (0o001, 'x'),
-    ];
-    let mut s = String::with_capacity(9);
-    for (bit, ch) in &flags {
-        s.push(if mode & bit != 0 { *ch } else { '-' });
-    }
-    s
-}
-
 /// Check the server's host key against ~/.ssh/known_hosts.
 fn verify_host_key(session: &Session, host: &str, port: u16) -> Result<(), SftpError> {
     let (key, key_type) = match session.host_key() {
@@ -817,7 +2056,7 @@ fn verify_host_key(session: &Session, host: &str, port: u16) -> Result<(), SftpE
     let key_type_str = host_key_type_str(key_type, key);
     let key_bytes = key.to_vec();
 
-    let known_hosts_path = expand_tilde("~/.ssh/known_hosts");
+    let known_hosts_path = expand_local_path("~/.ssh/known_hosts");
     let mut known_hosts = session.known_hosts()?;
     if known_hosts_path.exists() {
         known_hosts
@@ -846,7 +2085,7 @@ fn verify_host_key(session: &Session, host: &str, port: u16) -> Result<(), SftpE
 
 /// Append a trusted host key to ~/.ssh/known_hosts.
 pub fn add_to_known_hosts(host: &str, port: u16, key_type: &str, key_bytes: &[u8]) -> Result<(), SftpError> {
-    let known_hosts_path = expand_tilde("~/.ssh/known_hosts");
+    let known_hosts_path = expand_local_path("~/.ssh/known_hosts");
     if let Some(parent) = known_hosts_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -890,14 +2129,71 @@ fn host_key_type_str(key_type: ssh2::HostKeyType, key_bytes: &[u8]) -> String {
     }
 }
 
-fn expand_tilde(path: &str) -> PathBuf {
-    if let Some(rest) = path.strip_prefix("~/") {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        PathBuf::from(home).join(rest)
-    } else if path == "~" {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        PathBuf::from(home)
-    } else {
-        PathBuf::from(path)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(local: &Path, size: u64) -> DownloadItem {
+        DownloadItem { remote: PathBuf::from("/srv/f.txt"), local: local.to_path_buf(), size, mtime: Some(0) }
+    }
+
+    #[test]
+    fn blank_key_path_falls_back_to_the_default_key() {
+        let mut profile = Profile {
+            name: "web".to_string(),
+            host: "example.org".to_string(),
+            port: 22,
+            user: "deploy".to_string(),
+            auth: AuthMethod::Key,
+            key_path: Some("  ".to_string()),
+            remote_path: None,
+            local_start_path: None,
+            has_saved_password: false,
+            last_used: None,
+            verify_uploads: false,
+            jump_host: None,
+            jump_user: None,
+            jump_port: None,
+            focus_remote: false,
+        };
+        let (raw, path) = profile_key(&profile);
+        assert_eq!(raw, DEFAULT_KEY_PATH);
+        assert_eq!(path, expand_local_path(DEFAULT_KEY_PATH));
+        let line = format!("Auth: Schlüssel {} → {}", raw, path.display());
+        assert!(describe_connection(&profile).iter().any(|l| l.starts_with(&line)));
+        profile.key_path = Some("/keys/deploy".to_string());
+        assert_eq!(profile_key(&profile).1, PathBuf::from("/keys/deploy"));
+    }
+
+    #[test]
+    fn ipv6_hosts_are_bracketed_once() {
+        assert_eq!(host_port("::1", 22), "[::1]:22");
+        assert_eq!(host_port("[::1]", 2222), "[::1]:2222");
+        assert_eq!(host_port(" example.org ", 22), "example.org:22");
+        assert_eq!(bare_host("[::1]"), "::1");
+        assert_eq!(bare_host("::1"), "::1");
+        assert_eq!(bare_host("[::1"), "[::1");
+    }
+
+    #[test]
+    fn resume_ignores_a_shorter_target_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("f.txt");
+        std::fs::write(&local, b"edited").unwrap();
+        let item = item(&local, 100);
+        assert_eq!(resume_offset(&partial_path(&local), &item), 0);
+    }
+
+    #[test]
+    fn resume_continues_the_part_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("f.txt");
+        let part = partial_path(&local);
+        assert_eq!(part, dir.path().join("f.txt.vela-part"));
+        std::fs::write(&part, b"abc").unwrap();
+        assert_eq!(resume_offset(&part, &item(&local, 100)), 3);
+        // A complete (or longer) sidecar is fetched again.
+        assert_eq!(resume_offset(&part, &item(&local, 3)), 0);
     }
 }
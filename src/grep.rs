@@ -0,0 +1,138 @@
+//! Recursive content search below the local panel directory ('g').
+//!
+//! `run_grep` walks the tree in a background thread and publishes results
+//! through a shared `GrepProgress`, the same way transfers report through
+//! `TransferProgress`.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use regex::Regex;
+
+/// Files larger than this are skipped.
+pub const GREP_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// The search stops once this many matching lines have been collected.
+pub const GREP_MAX_RESULTS: usize = 5000;
+/// A NUL byte in this many leading bytes marks a file as binary.
+const BINARY_PROBE_LEN: usize = 8192;
+/// Matched lines are cut to this many characters for display.
+const MAX_LINE_CHARS: usize = 200;
+
+/// One matching line.
+#[derive(Debug, Clone)]
+pub struct GrepHit {
+    pub path: PathBuf,
+    /// 1-based line number.
+    pub line: usize,
+    pub text: String,
+}
+
+/// Shared grep state — written by the search thread, read by the dialog.
+#[derive(Debug, Default)]
+pub struct GrepProgress {
+    /// Number of text files searched so far.
+    pub files_scanned: usize,
+    pub hits: Vec<GrepHit>,
+    pub finished: bool,
+}
+
+pub type GrepHandle = Arc<Mutex<GrepProgress>>;
+
+/// Search every regular file below `root` for `pattern`. Symlinks are not
+/// followed; binary, unreadable and oversized files are skipped silently.
+pub fn run_grep(root: PathBuf, pattern: Regex, handle: GrepHandle, cancel: Arc<AtomicBool>) {
+    grep_dir(&root, &pattern, &handle, &cancel);
+    if let Ok(mut prog) = handle.lock() {
+        prog.finished = true;
+    }
+}
+
+/// Returns false once the search should stop (cancelled or result cap hit).
+fn grep_dir(dir: &Path, pattern: &Regex, handle: &GrepHandle, cancel: &AtomicBool) -> bool {
+    let Ok(rd) = fs::read_dir(dir) else {
+        return true;
+    };
+    let mut entries: Vec<_> = rd.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        let keep_going = if file_type.is_dir() {
+            grep_dir(&path, pattern, handle, cancel)
+        } else if file_type.is_file() {
+            grep_file(&path, pattern, handle)
+        } else {
+            true
+        };
+        if !keep_going {
+            return false;
+        }
+    }
+    true
+}
+
+fn grep_file(path: &Path, pattern: &Regex, handle: &GrepHandle) -> bool {
+    let Some(content) = read_text_file(path) else {
+        return true;
+    };
+    let hits: Vec<GrepHit> = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| pattern.is_match(line))
+        .map(|(i, line)| GrepHit {
+            path: path.to_path_buf(),
+            line: i + 1,
+            text: line.trim().chars().take(MAX_LINE_CHARS).collect(),
+        })
+        .collect();
+
+    let Ok(mut prog) = handle.lock() else {
+        return false;
+    };
+    prog.files_scanned += 1;
+    let room = GREP_MAX_RESULTS - prog.hits.len();
+    prog.hits.extend(hits.into_iter().take(room));
+    prog.hits.len() < GREP_MAX_RESULTS
+}
+
+/// File contents as (lossy) UTF-8, or None for binary, oversized or unreadable files.
+fn read_text_file(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    if file.metadata().ok()?.len() > GREP_MAX_FILE_SIZE {
+        return None;
+    }
+    // Probe the start first so binary files are skipped without reading them whole.
+    let mut buf = Vec::new();
+    (&mut file).take(BINARY_PROBE_LEN as u64).read_to_end(&mut buf).ok()?;
+    if buf.contains(&0) {
+        return None;
+    }
+    file.read_to_end(&mut buf).ok()?;
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_files_are_skipped_after_the_probe() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("notes.txt");
+        let mut content = "x".repeat(BINARY_PROBE_LEN);
+        content.push_str("tail\n");
+        fs::write(&text, &content).unwrap();
+        assert_eq!(read_text_file(&text).as_deref(), Some(content.as_str()));
+        let binary = dir.path().join("blob.bin");
+        fs::write(&binary, b"ELF\0\x01\x02 text after nul").unwrap();
+        assert_eq!(read_text_file(&binary), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Single-line text input shared by the dialogs (rename, mkdir, go-to,
+//! search, palette, …).
+//!
+//! Dialogs embed a `TextInput` and hand every key they do not handle
+//! themselves to `edit_text_input`.
+
+use crossterm::event::KeyCode;
+
+/// Text field with a cursor.
+#[derive(Debug, Default, Clone)]
+pub struct TextInput {
+    pub buf: String,
+    /// Byte offset of the cursor inside `buf` (always on a char boundary).
+    pub cursor_pos: usize,
+}
+
+impl TextInput {
+    /// Start with `text`, cursor at the end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let buf = text.into();
+        let cursor_pos = buf.len();
+        Self { buf, cursor_pos }
+    }
+
+    /// Text left of the cursor.
+    pub fn before_cursor(&self) -> &str {
+        &self.buf[..self.cursor_pos]
+    }
+
+    /// Insert `text` at the cursor and move the cursor behind it (Tab completion).
+    pub fn insert_str(&mut self, text: &str) {
+        self.buf.insert_str(self.cursor_pos, text);
+        self.cursor_pos += text.len();
+    }
+
+    /// Insert a character at the cursor position and advance the cursor.
+    pub fn insert(&mut self, c: char) {
+        self.buf.insert(self.cursor_pos, c);
+        self.cursor_pos += c.len_utf8();
+    }
+
+    /// Delete the character to the left of the cursor (Backspace).
+    /// Returns false at the start of the input.
+    pub fn backspace(&mut self) -> bool {
+        let Some(c) = self.before_cursor().chars().next_back() else {
+            return false;
+        };
+        self.cursor_pos -= c.len_utf8();
+        self.buf.remove(self.cursor_pos);
+        true
+    }
+
+    /// Delete the character to the right of the cursor (Delete key).
+    /// Returns false at the end of the input.
+    pub fn delete_forward(&mut self) -> bool {
+        if self.cursor_pos >= self.buf.len() {
+            return false;
+        }
+        self.buf.remove(self.cursor_pos);
+        true
+    }
+
+    /// Move cursor one character to the left.
+    pub fn move_left(&mut self) {
+        if let Some(c) = self.before_cursor().chars().next_back() {
+            self.cursor_pos -= c.len_utf8();
+        }
+    }
+
+    /// Move cursor one character to the right.
+    pub fn move_right(&mut self) {
+        if let Some(c) = self.buf[self.cursor_pos..].chars().next() {
+            self.cursor_pos += c.len_utf8();
+        }
+    }
+
+    /// Jump to start of input.
+    pub fn move_home(&mut self) {
+        self.cursor_pos = 0;
+    }
+
+    /// Jump to end of input.
+    pub fn move_end(&mut self) {
+        self.cursor_pos = self.buf.len();
+    }
+}
+
+/// Apply a line-editing key (←/→, Home/End, Backspace/Delete, characters)
+/// to `input`; other keys are ignored. Returns true when the text changed,
+/// so the caller can drop a stale error or reset a filtered selection.
+pub fn edit_text_input(input: &mut TextInput, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Left => input.move_left(),
+        KeyCode::Right => input.move_right(),
+        KeyCode::Home => input.move_home(),
+        KeyCode::End => input.move_end(),
+        KeyCode::Backspace => return input.backspace(),
+        KeyCode::Delete => return input.delete_forward(),
+        KeyCode::Char(c) => {
+            input.insert(c);
+            return true;
+        }
+        _ => {}
+    }
+    false
+}
@@ -1,219 +1,572 @@
 mod app;
+mod batch;
 mod config;
 mod connection;
+mod grep;
+mod input;
+mod paths;
+mod perms;
+mod startup;
 mod transfer;
+mod trash;
 mod ui;
 
 use std::io;
 
 use crossterm::{
-    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode,
+        KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
+use zeroize::Zeroizing;
 
-use app::{ActivePanel, App, AppError, EditRequest, ProfileDialogMode};
-use config::profiles::AuthMethod;
-use ui::theme::{custom_theme_names, save_theme_choice, ThemeChoice};
+use app::{App, AppError, EditRequest, PaletteAction, PaletteDialog, ProfileDialog, ProfileDialogMode};
+use config::profiles::{AuthMethod, Profile};
+use config::settings::{ProfileSort, Settings};
+use input::edit_text_input;
+use ui::theme::{custom_theme_names, ThemeChoice};
 
 fn main() -> Result<(), AppError> {
-    let mut terminal = setup_terminal()?;
-    let result = run(&mut terminal);
-    restore_terminal(&mut terminal)?;
+    // `--get` / `--put` run a single transfer without the TUI.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if batch::is_batch_invocation(&args) {
+        std::process::exit(batch::run(&args));
+    }
+
+    let settings = Settings::load();
+    // Mouse capture is opt-in (`--mouse` / `mouse = true`) so native terminal
+    // text selection works by default; `--no-mouse` wins over the setting.
+    let mouse = !args.iter().any(|a| a == "--no-mouse")
+        && (args.iter().any(|a| a == "--mouse") || settings.mouse);
+
+    let no_color = args.iter().any(|a| a == "--no-color");
+
+    // `vela <profile>` / `vela user@host:/path` connect right after launch.
+    let target = match startup::parse_target(&args) {
+        Ok(target) => target,
+        Err(e) => {
+            eprintln!("vela: {}\n{}", e, startup::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // `--password-file <path>` / `$VELA_PASSWORD` skip the password dialog.
+    let password = match startup::password(&args) {
+        Ok(password) => password,
+        Err(e) => {
+            eprintln!("vela: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let mut terminal = setup_terminal(mouse)?;
+    let result = run(&mut terminal, settings, mouse, no_color, target, password);
+    restore_terminal(&mut terminal, mouse)?;
     result
 }
 
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, AppError> {
+fn setup_terminal(mouse: bool) -> Result<Terminal<CrosstermBackend<io::Stdout>>, AppError> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     Ok(Terminal::new(backend)?)
 }
 
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), AppError> {
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mouse: bool) -> Result<(), AppError> {
     disable_raw_mode()?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     execute!(terminal.backend_mut(), DisableBracketedPaste, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
     Ok(())
 }
 
-fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), AppError> {
-    let mut app = App::new()?;
+fn run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    settings: Settings,
+    mouse: bool,
+    no_color: bool,
+    target: Option<Profile>,
+    password: Option<Zeroizing<String>>,
+) -> Result<(), AppError> {
+    let mut app = App::new(settings)?;
+    app.no_color |= no_color;
+    app.preset_password = target.as_ref().zip(password).map(|(p, pw)| (p.name.clone(), pw));
+    if let Some(profile) = target {
+        app.begin_connect(profile);
+    }
 
     while app.running {
         // Poll transfer state before rendering so the UI reflects completion immediately
         app.poll_upload();
         app.poll_download();
+        app.poll_copy();
+        app.poll_delete();
+        app.poll_connection_test();
+        app.poll_connect();
+        app.poll_transfer_queue();
         app.poll_local_fs();
+        app.poll_remote_listing();
+        app.poll_remote_free_space();
+        app.poll_delete_count();
+        app.poll_shell_command();
         app.poll_remote_refresh();
+        app.poll_latency();
+        app.update_comparison();
         terminal.draw(|frame| ui::render(frame, &app))?;
         handle_events(&mut app)?;
 
         // F4: if an editor launch was requested, hand off to the editor and
         // restore the TUI afterwards.
         if let Some(req) = app.pending_edit.take() {
-            launch_editor(terminal, &req)?;
+            let notice = launch_editor(terminal, &mut app, &req, mouse)?;
             terminal.clear()?;
             app.finish_edit(req)?;
+            if let Some(notice) = notice {
+                let text = match app.status_message.take() {
+                    Some(msg) => format!("{} — {}", notice, msg),
+                    None => notice,
+                };
+                app.set_status(text);
+            }
+        }
+
+        // s: interactive remote shell via the system ssh binary.
+        if let Some(args) = app.pending_remote_shell.take() {
+            let error = launch_remote_shell(terminal, &args, mouse)?;
+            terminal.clear()?;
+            app.finish_remote_shell(error);
         }
     }
 
     Ok(())
 }
 
+/// Editor command chosen by `find_editor`.
+struct EditorChoice {
+    /// Binary followed by its arguments, e.g. `["code", "--wait"]`.
+    command: Vec<String>,
+    /// Configured or `$EDITOR` / `$VISUAL` values that were set but not usable.
+    skipped: Vec<String>,
+}
+
 /// Find an editor binary that is actually installed on this system.
-/// Search order: $EDITOR, $VISUAL, vim, nano, vi.
-/// Each candidate is verified with `which` before being accepted.
-/// Returns None only when none of the candidates exist.
-fn find_editor() -> Option<String> {
-    let candidates: Vec<String> = [
-        std::env::var("EDITOR").ok(),
-        std::env::var("VISUAL").ok(),
-        Some("vim".to_string()),
-        Some("nano".to_string()),
-        Some("vi".to_string()),
-    ]
-    .into_iter()
-    .flatten()
-    .filter(|s| !s.trim().is_empty())
-    .collect();
-
-    for candidate in candidates {
-        // Extract first token (binary name); don't pass flags to `which`.
-        let binary = candidate.split_whitespace().next().unwrap_or(&candidate);
-        let found = std::process::Command::new("which")
-            .arg(binary)
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false);
+/// Search order: `editor` from settings.toml, $EDITOR, $VISUAL, vim, nano, vi.
+/// Each candidate is split like a shell command (so `code --wait` or a quoted
+/// path with spaces works) and only its binary is verified with `which`.
+/// `command` is empty only when none of the candidates exist.
+fn find_editor(editor: Option<&str>) -> EditorChoice {
+    let configured = editor.map(|value| (Some("editor".to_string()), value.to_string()));
+    let from_env = ["EDITOR", "VISUAL"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok().map(|value| (Some(format!("${}", var)), value)));
+    let fallbacks = ["vim", "nano", "vi"].into_iter().map(|bin| (None, bin.to_string()));
+
+    let mut skipped = Vec::new();
+    for (source, candidate) in configured.into_iter().chain(from_env).chain(fallbacks) {
+        if candidate.trim().is_empty() {
+            continue;
+        }
+        let command = shell_words::split(&candidate).unwrap_or_default();
+        let found = command.first().is_some_and(|binary| {
+            std::process::Command::new("which")
+                .arg(binary)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        });
         if found {
-            return Some(candidate);
+            return EditorChoice { command, skipped };
+        }
+        if let Some(source) = source {
+            skipped.push(format!("{}='{}'", source, candidate));
         }
     }
-    None
+    EditorChoice { command: Vec::new(), skipped }
 }
 
-/// Suspend the TUI, launch the editor for `req`, then restore the TUI.
-/// Hands the terminal back to the shell cleanly and restores raw mode
-/// afterwards.  Ignores the editor exit code — mtime comparison determines
+/// GUI editors that return at once unless told to wait, with the flag that
+/// makes them block until the file is closed.
+const GUI_EDITORS: &[(&str, &str)] = &[
+    ("code", "--wait"),
+    ("code-insiders", "--wait"),
+    ("codium", "--wait"),
+    ("zed", "--wait"),
+    ("atom", "--wait"),
+    ("subl", "--wait"),
+    ("mate", "-w"),
+    ("gvim", "-f"),
+    ("mvim", "-f"),
+];
+
+/// Prepare a GUI editor command: add the wait flag for a known GUI editor
+/// when it is missing. Returns true for GUI editors — known ones, or any
+/// command already carrying `--wait`.
+fn prepare_gui_editor(command: &mut Vec<String>) -> bool {
+    let name = command
+        .first()
+        .and_then(|bin| std::path::Path::new(bin).file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match GUI_EDITORS.iter().find(|(bin, _)| *bin == name) {
+        Some((_, flag)) => {
+            if !command.iter().skip(1).any(|a| a == flag || a == "--wait") {
+                command.insert(1, flag.to_string());
+            }
+            true
+        }
+        None => command.iter().skip(1).any(|a| a == "--wait"),
+    }
+}
+
+/// Launch the editor for `req` and wait until it exits.
+/// A terminal editor gets the terminal: the TUI is suspended and restored
+/// afterwards. A GUI editor runs in its own window while the TUI stays up
+/// with a waiting notice; its output is discarded so it cannot draw over
+/// the screen. Ignores the editor exit code — mtime comparison determines
 /// whether a file was saved.
+/// Returns a notice for the status bar when the configured editor or
+/// $EDITOR / $VISUAL could not be used, or when no editor could be started.
 fn launch_editor(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
     req: &EditRequest,
-) -> Result<(), AppError> {
+    mouse: bool,
+) -> Result<Option<String>, AppError> {
     let path = match req {
         EditRequest::Local  { path }            => path,
         EditRequest::Remote { temp_path, .. }   => temp_path,
     };
-    match find_editor() {
-        Some(editor) => {
-            // Leave alternate screen and disable raw mode so the editor runs cleanly
-            disable_raw_mode()?;
-            execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    let mut choice = find_editor(app.settings.editor.as_deref());
+    if choice.command.is_empty() {
+        // No editor found — finish_edit will see no mtime change.
+        return Ok(Some("Kein Editor gefunden (editor, $EDITOR, $VISUAL, vim, nano, vi)".to_string()));
+    }
+    let gui = prepare_gui_editor(&mut choice.command);
+    let bin = choice.command[0].clone();
+    let mut cmd = std::process::Command::new(&bin);
+    cmd.args(&choice.command[1..]).arg(path);
+
+    let status = if gui {
+        app.set_status(format!("Warte auf {} — Datei im Editor schließen", bin));
+        terminal.draw(|frame| ui::render(frame, app))?;
+        cmd.stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+    } else {
+        // Leave alternate screen and disable raw mode so the editor runs cleanly
+        disable_raw_mode()?;
+        if mouse {
+            execute!(terminal.backend_mut(), DisableMouseCapture)?;
+        }
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
 
-            // shell-words correctly handles quoted arguments (e.g. "code --wait")
-            let parts = shell_words::split(&editor).unwrap_or_else(|_| vec![editor.clone()]);
-            if let Some(bin) = parts.first() {
-                let mut cmd = std::process::Command::new(bin);
-                for arg in &parts[1..] {
-                    cmd.arg(arg);
-                }
-                let _ = cmd.arg(path).status();
-            }
+        let status = cmd.status();
 
-            // Re-enter alternate screen and raw mode
-            enable_raw_mode()?;
-            execute!(terminal.backend_mut(), EnterAlternateScreen)?;
-            terminal.clear()?;
-        }
-        None => {
-            // No editor found — nothing to do; finish_edit will see no mtime change.
+        // Re-enter alternate screen and raw mode
+        enable_raw_mode()?;
+        execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+        if mouse {
+            execute!(terminal.backend_mut(), EnableMouseCapture)?;
         }
+        terminal.clear()?;
+        status
+    };
+    app.status_message = None;
+
+    if let Err(e) = status {
+        return Ok(Some(format!("Editor konnte nicht gestartet werden: {} ({})", bin, e)));
     }
-    Ok(())
+    if choice.skipped.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "{} nicht ausführbar, {} verwendet",
+        choice.skipped.join(", "),
+        bin
+    )))
+}
+
+/// Suspend the TUI, run `ssh` with `args` in the foreground, then restore
+/// the TUI. Returns a message when `ssh` could not be started; the exit code
+/// of the remote shell itself is ignored.
+fn launch_remote_shell(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    args: &[String],
+    mouse: bool,
+) -> Result<Option<String>, AppError> {
+    disable_raw_mode()?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    let error = std::process::Command::new("ssh")
+        .args(args)
+        .status()
+        .err()
+        .map(|e| e.to_string());
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    if mouse {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    Ok(error)
 }
 
 fn handle_events(app: &mut App) -> Result<(), AppError> {
     if !event::poll(std::time::Duration::from_millis(50))? {
         return Ok(());
     }
-
     match event::read()? {
-        Event::Key(key) => {
-            if key.kind != KeyEventKind::Press {
-                return Ok(());
-            }
-
-            // F1 toggles the help overlay from any context.
-            // Esc closes it when it is visible.
-            if key.code == KeyCode::F(1) {
-                app.help_visible = !app.help_visible;
-                return Ok(());
-            }
-            if app.help_visible {
-                if key.code == KeyCode::Esc {
-                    app.help_visible = false;
-                }
-                return Ok(());
-            }
-
-            // Ctrl+U / Ctrl+S — swap panels visually (works from any mode)
-            if key.modifiers.contains(KeyModifiers::CONTROL)
-                && matches!(key.code, KeyCode::Char('u') | KeyCode::Char('s'))
-            {
-                app.swap_panels();
-                return Ok(());
-            }
-
-            // Ctrl+T — cycle theme: Auto → Dark → Light → custom1 → custom2 → ... → Auto
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('t') {
-                let customs = custom_theme_names();
-                app.theme_choice = next_theme(&app.theme_choice, &customs);
-                save_theme_choice(&app.theme_choice);
-                app.status_message = Some(format!("Theme: {}", app.theme_choice.label()));
-                return Ok(());
-            }
-
-            // Priority (highest first): host_key > permission > password > delete > rename > mkdir > shell > profile > main
-            if app.host_key_dialog.is_some() {
-                handle_host_key_key(app, key.code);
-            } else if app.permission_dialog.is_some() {
-                handle_permission_key(app, key.code);
-            } else if app.password_dialog.is_some() {
-                handle_password_key(app, key.code);
-            } else if app.delete_dialog.is_some() {
-                handle_delete_key(app, key.code);
-            } else if app.rename_dialog.is_some() {
-                handle_rename_key(app, key.code);
-            } else if app.mkdir_dialog.is_some() {
-                handle_mkdir_key(app, key.code);
-            } else if app.shell_dialog.is_some() {
-                handle_shell_key(app, key.code);
-            } else if app.profile_dialog.is_some() {
-                handle_dialog_key(app, key.code);
-            } else {
-                handle_main_key(app, key.code)?;
-            }
+        Event::Key(key) if key.kind == KeyEventKind::Press => handle_key_event(app, key)?,
+        Event::Paste(text) => handle_paste_event(app, &text),
+        Event::Mouse(mouse) => handle_mouse_event(app, mouse)?,
+        Event::Resize(width, height) => handle_resize_event(app, width, height),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Route a key press: help overlay, global shortcuts, the topmost open
+/// dialog, and only then the panels.
+fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<(), AppError> {
+    if handle_help_key(app, key.code) || handle_shortcut_key(app, key) {
+        return Ok(());
+    }
+    if dispatch_dialog_key(app, key)? {
+        return Ok(());
+    }
+    handle_panel_key(app, key)
+}
+
+/// F1 toggles the help overlay from any context; while it is visible only
+/// Esc (closing it) does anything. Returns true when the key was consumed.
+fn handle_help_key(app: &mut App, code: KeyCode) -> bool {
+    if code == KeyCode::F(1) {
+        app.help_visible = !app.help_visible;
+        return true;
+    }
+    if !app.help_visible {
+        return false;
+    }
+    if code == KeyCode::Esc {
+        app.help_visible = false;
+    }
+    true
+}
+
+/// What a shortcut runs.
+type KeyAction = fn(&mut App);
+
+/// Ctrl shortcuts that work from any mode, even over a dialog.
+const GLOBAL_CTRL_KEYS: &[(char, KeyAction)] = &[
+    // Ctrl+U / Ctrl+S — swap panels visually
+    ('u', App::swap_panels),
+    ('s', App::swap_panels),
+    // Ctrl+W — single panel, full width
+    ('w', App::toggle_single_panel),
+    // Ctrl+T — cycle theme: Auto → Dark → Light → Monochrome → High-Contrast → custom1 → ... → Auto
+    ('t', cycle_theme),
+    // Ctrl+Z — size column: rounded units ↔ exact bytes
+    ('z', App::toggle_exact_sizes),
+    // Ctrl+O — permissions column for the local panel
+    ('o', App::toggle_local_permissions),
+    // Ctrl+D — date column: timestamp ↔ relative age
+    ('d', App::toggle_relative_dates),
+];
+
+/// Shortcuts that only apply from the panels, never over a dialog.
+const PANEL_SHORTCUTS: &[(KeyModifiers, KeyCode, KeyAction)] = &[
+    // Ctrl+R — reload the active panel
+    (KeyModifiers::CONTROL, KeyCode::Char('r'), App::refresh_active_panel),
+    // Ctrl+G — go to a path in the active panel
+    (KeyModifiers::CONTROL, KeyCode::Char('g'), App::open_goto_dialog),
+    // Shift+F3 — reconnect with the last profile
+    (KeyModifiers::SHIFT, KeyCode::F(3), App::reconnect_last),
+    // Shift+F4 — create a new file and edit it
+    (KeyModifiers::SHIFT, KeyCode::F(4), |app| app.open_touch_dialog(true)),
+    // Ctrl+P — command palette
+    (KeyModifiers::CONTROL, KeyCode::Char('p'), |app| {
+        app.palette_dialog = Some(PaletteDialog::default())
+    }),
+];
+
+/// Run the global or panel shortcut bound to `key`. Returns true when one ran.
+fn handle_shortcut_key(app: &mut App, key: KeyEvent) -> bool {
+    if let (KeyCode::Char(c), true) = (key.code, key.modifiers.contains(KeyModifiers::CONTROL)) {
+        if let Some((_, run)) = GLOBAL_CTRL_KEYS.iter().find(|(k, _)| *k == c) {
+            run(app);
+            return true;
         }
-        // Bracketed paste: terminals send file paths when files are dragged onto the window.
-        Event::Paste(text) => {
-            let no_dialog = app.host_key_dialog.is_none()
-                && app.permission_dialog.is_none()
-                && app.password_dialog.is_none()
-                && app.delete_dialog.is_none()
-                && app.rename_dialog.is_none()
-                && app.mkdir_dialog.is_none()
-                && app.shell_dialog.is_none()
-                && app.profile_dialog.is_none();
-            if no_dialog {
-                app.handle_paste_drop(&text);
-            }
+    }
+    if any_dialog_open(app) {
+        return false;
+    }
+    let hit = PANEL_SHORTCUTS
+        .iter()
+        .find(|(mods, code, _)| key.modifiers.contains(*mods) && key.code == *code);
+    match hit {
+        Some((_, _, run)) => {
+            run(app);
+            true
+        }
+        None => false,
+    }
+}
+
+/// A dialog: whether it is open, and the handler that gets every key then.
+type DialogKeys = (fn(&App) -> bool, fn(&mut App, KeyCode));
+
+/// Modal dialogs in priority order, highest first. The command palette and
+/// the profile dialog come last; see `dispatch_dialog_key`.
+const DIALOG_KEYS: &[DialogKeys] = &[
+    (|a| a.quit_confirm_visible, handle_quit_confirm_key),
+    (|a| a.pending_connect.is_some(), handle_connecting_key),
+    (|a| a.host_key_dialog.is_some(), handle_host_key_key),
+    (|a| a.edit_conflict_dialog.is_some(), handle_edit_conflict_key),
+    (|a| a.edit_upload_dialog.is_some(), handle_edit_upload_key),
+    (|a| a.edit_size_dialog.is_some(), handle_edit_size_key),
+    (|a| a.permission_dialog.is_some(), handle_permission_key),
+    (|a| a.password_dialog.is_some(), handle_password_key),
+    (|a| a.delete_dialog.is_some(), handle_delete_key),
+    (|a| a.rename_dialog.is_some(), handle_rename_key),
+    (|a| a.batch_rename_dialog.is_some(), handle_batch_rename_key),
+    (|a| a.mkdir_dialog.is_some(), handle_mkdir_key),
+    (|a| a.touch_dialog.is_some(), handle_touch_key),
+    (|a| a.goto_dialog.is_some(), handle_goto_key),
+    (|a| a.upload_as_dialog.is_some(), handle_upload_as_key),
+    (|a| a.mark_pattern_dialog.is_some(), handle_mark_pattern_key),
+    (|a| a.mark_age_dialog.is_some(), handle_mark_age_key),
+    (|a| a.chown_dialog.is_some(), handle_chown_key),
+    (|a| a.search_dialog.is_some(), handle_search_key),
+    (|a| a.grep_dialog.is_some(), handle_grep_key),
+    (|a| a.shell_dialog.is_some(), handle_shell_key),
+    (|a| a.temp_cleanup_dialog.is_some(), handle_temp_cleanup_key),
+    (|a| a.sync_dialog.is_some(), handle_sync_key),
+    (|a| a.info_dialog.is_some(), handle_info_key),
+    (|a| a.log_dialog.is_some(), handle_log_key),
+];
+
+/// Hand `key` to the topmost open dialog. Returns false when none is open.
+fn dispatch_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppError> {
+    if let Some((_, handle)) = DIALOG_KEYS.iter().find(|(open, _)| open(app)) {
+        handle(app, key.code);
+    } else if app.palette_dialog.is_some() {
+        // Palette actions run through `handle_main_key`, which can fail.
+        handle_palette_key(app, key.code)?;
+    } else if app.profile_dialog.is_some() {
+        handle_dialog_key(app, key.code, key.modifiers);
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// True while any modal dialog is open; panel input is ignored then.
+fn any_dialog_open(app: &App) -> bool {
+    DIALOG_KEYS.iter().any(|(open, _)| open(app))
+        || app.palette_dialog.is_some()
+        || app.profile_dialog.is_some()
+}
+
+fn handle_quit_confirm_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.quit(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.quit_confirm_visible = false;
         }
         _ => {}
     }
+}
 
+/// Only Esc: the connect runs on a worker thread meanwhile.
+fn handle_connecting_key(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Esc {
+        app.cancel_connect();
+    }
+}
+
+/// Read-only popup: any closing key dismisses it.
+fn handle_info_key(app: &mut App, code: KeyCode) {
+    if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('i') | KeyCode::Char('q')) {
+        app.info_dialog = None;
+    }
+}
+
+/// Keys for the panels once no dialog took them.
+fn handle_panel_key(app: &mut App, key: KeyEvent) -> Result<(), AppError> {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        // Alt+Left / Alt+Right = back / forward through visited directories
+        KeyCode::Left | KeyCode::Right if alt => app.history_move(key.code == KeyCode::Right),
+        // Alt+letters = type-ahead jump (plain letters are commands)
+        KeyCode::Char(c) if alt => app.type_ahead(c),
+        code => {
+            app.active_panel_mut().reset_type_ahead();
+            handle_main_key(app, code)?;
+        }
+    }
+    Ok(())
+}
+
+/// Bracketed paste: terminals send file paths when files are dragged onto the window.
+fn handle_paste_event(app: &mut App, text: &str) {
+    if !any_dialog_open(app) {
+        app.handle_paste_drop(text);
+    }
+}
+
+/// The loop redraws right after this returns (ratatui resizes its buffers
+/// in `draw`); only size-dependent state needs fixing up here.
+fn handle_resize_event(app: &mut App, width: u16, height: u16) {
+    let screen = ratatui::layout::Rect::new(0, 0, width, height);
+    let visible = ui::dialogs::shell_output_lines(screen).max(1);
+    if let Some(d) = app.shell_dialog.as_mut() {
+        let total = d.output.as_ref().map(|l| l.len()).unwrap_or(0);
+        d.clamp_scroll(total, visible);
+    }
+}
+
+/// Advance to the next theme and persist the choice.
+fn cycle_theme(app: &mut App) {
+    let customs = custom_theme_names();
+    app.theme_choice = next_theme(&app.theme_choice, &customs);
+    app.set_status(format!("Theme: {}", app.theme_choice.label()));
+    app.settings.theme = Some(app.theme_choice.ser_name().to_string());
+    app.save_settings();
+}
+
+/// Left click selects (double-click opens), the wheel moves the cursor of
+/// the panel under the pointer. Mouse events only arrive when capture is
+/// enabled (`--mouse` / `mouse = true`) and are ignored over help and dialogs.
+fn handle_mouse_event(app: &mut App, mouse: MouseEvent) -> Result<(), AppError> {
+    if app.help_visible || any_dialog_open(app) {
+        return Ok(());
+    }
+    let (width, height) = crossterm::terminal::size()?;
+    let screen = ratatui::layout::Rect::new(0, 0, width, height);
+    let Some((panel, pos)) = ui::hit_test(app, screen, mouse.column, mouse.row) else {
+        return Ok(());
+    };
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => app.mouse_click(panel, pos),
+        MouseEventKind::ScrollDown => app.mouse_scroll(panel, true),
+        MouseEventKind::ScrollUp => app.mouse_scroll(panel, false),
+        _ => {}
+    }
     Ok(())
 }
 
@@ -222,101 +575,152 @@ fn handle_events(app: &mut App) -> Result<(), AppError> {
 // ---------------------------------------------------------------------------
 
 fn handle_main_key(app: &mut App, code: KeyCode) -> Result<(), AppError> {
+    let handled = handle_quick_connect_key(app, code)
+        || handle_navigation_key(app, code)
+        || handle_mark_key(app, code)
+        || handle_file_key(app, code);
+    if !handled {
+        handle_tool_key(app, code);
+    }
+    Ok(())
+}
+
+/// Quick-connect list of the disconnected remote panel.
+fn handle_quick_connect_key(app: &mut App, code: KeyCode) -> bool {
+    if !app.is_remote_active() || !app.quick_connect_shown() {
+        return false;
+    }
+    match code {
+        KeyCode::Up => app.quick_connect_move(false),
+        KeyCode::Down => app.quick_connect_move(true),
+        KeyCode::Enter => app.quick_connect_selected(),
+        _ => return false,
+    }
+    true
+}
+
+/// Cursor movement, panel switching and entering / leaving directories.
+fn handle_navigation_key(app: &mut App, code: KeyCode) -> bool {
     match code {
-        KeyCode::F(10) | KeyCode::Char('q') => app.quit(),
         KeyCode::Tab => app.toggle_panel(),
         KeyCode::Up => app.active_panel_mut().move_up(),
         KeyCode::Down => app.active_panel_mut().move_down(),
-
-        // Space = toggle mark on current entry; move down after marking
-        KeyCode::Char(' ') => {
-            app.active_panel_mut().toggle_mark();
-            app.active_panel_mut().move_down();
-        }
-
-        // * = mark all / unmark all in active panel
-        KeyCode::Char('*') => {
-            app.active_panel_mut().mark_all();
-        }
-
-        KeyCode::Enter => match app.active {
-            ActivePanel::Left => {
-                if let Err(e) = app.left.enter_selected() {
-                    app.status_message = Some(e.to_string());
-                }
-            }
-            ActivePanel::Right => {
-                if app.is_connected() {
-                    app.remote_enter_selected();
-                }
-            }
-        },
-
-        KeyCode::Backspace => match app.active {
-            ActivePanel::Left => {
-                if let Err(e) = app.left.go_up() {
-                    app.status_message = Some(e.to_string());
-                }
-            }
-            ActivePanel::Right => {
-                if app.is_connected() {
-                    app.remote_go_up();
+        KeyCode::PageUp => app.active_panel_mut().page_up(),
+        KeyCode::PageDown => app.active_panel_mut().page_down(),
+        KeyCode::Home => app.active_panel_mut().select_first(),
+        KeyCode::End => app.active_panel_mut().select_last(),
+        KeyCode::Enter => {
+            if !app.is_remote_active() {
+                if let Err(e) = app.local_mut().enter_selected() {
+                    app.set_status(e.to_string());
                 }
-            }
-        },
-
-        // F3 = disconnect (only when connected)
-        KeyCode::F(3) => {
-            if app.is_connected() {
-                app.disconnect();
+            } else if app.is_connected() {
+                app.remote_enter_selected();
             }
         }
-
-        // F5 = upload (left panel → remote)
-        KeyCode::F(5) => {
-            if app.is_connected() && !app.is_transferring() {
-                app.start_upload();
+        KeyCode::Backspace => {
+            if !app.is_remote_active() {
+                if let Err(e) = app.local_mut().go_up() {
+                    app.set_status(e.to_string());
+                }
+            } else if app.is_connected() {
+                app.remote_go_up();
             }
         }
+        _ => return false,
+    }
+    true
+}
 
-        // F6 = download (remote → left panel)
-        KeyCode::F(6) => {
-            if app.is_connected() && !app.is_transferring() {
-                app.start_download();
+/// Marking entries and what the panel lists.
+fn handle_mark_key(app: &mut App, code: KeyCode) -> bool {
+    match code {
+        // Space = toggle mark on current entry; move down after marking unless
+        // `mark_advances` is off
+        KeyCode::Char(' ') => {
+            app.active_panel_mut().toggle_mark();
+            if app.mark_advances {
+                app.active_panel_mut().move_down();
             }
         }
+        // * = mark all / unmark all in active panel
+        KeyCode::Char('*') => app.active_panel_mut().mark_all(),
+        // # = invert marks in active panel
+        KeyCode::Char('#') => app.active_panel_mut().invert_marks(),
+        KeyCode::Char('+') => app.open_mark_pattern_dialog(true),
+        KeyCode::Char('-') => app.open_mark_pattern_dialog(false),
+        KeyCode::Char('a') => app.open_mark_age_dialog(),
+        KeyCode::Char('.') => app.toggle_hidden(),
+        KeyCode::Char('=') => app.toggle_compare(),
+        _ => return false,
+    }
+    true
+}
 
+/// Operations on the highlighted or marked entries.
+fn handle_file_key(app: &mut App, code: KeyCode) -> bool {
+    match code {
+        // F5 = upload (local panel → remote)
+        KeyCode::F(5) if app.is_connected() => app.start_upload(),
+        // F6 = download (remote → local panel)
+        KeyCode::F(6) if app.is_connected() => app.start_download(),
+        // u = upload the selected file under a different remote name
+        KeyCode::Char('u') => app.open_upload_as_dialog(),
         // F2 = rename selected entry
         KeyCode::F(2) => app.open_rename_dialog(),
-
         // F4 = edit selected file in $EDITOR
         KeyCode::F(4) => app.prepare_edit(),
-
         // F7 = create new directory
         KeyCode::F(7) => app.open_mkdir_dialog(),
-
+        // n = create empty file
+        KeyCode::Char('n') => app.open_touch_dialog(false),
+        // o = change owner/group of remote entries
+        KeyCode::Char('o') if app.is_connected() => app.open_chown_dialog(),
         // F8 = delete selected entry
         KeyCode::F(8) => app.open_delete_dialog(),
+        // x / y / v = cut / copy / paste remote entries (server-side move or copy)
+        KeyCode::Char('x') if app.is_connected() => app.cut_remote_selection(),
+        KeyCode::Char('y') if app.is_connected() => app.copy_remote_selection(),
+        KeyCode::Char('v') if app.is_connected() => app.paste_clipboard(),
+        KeyCode::Char('m') if app.is_connected() => app.open_sync_dialog(),
+        _ => return false,
+    }
+    true
+}
 
+/// Connection, search, shells, popups and quitting.
+fn handle_tool_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::F(10) | KeyCode::Char('q') => app.request_quit(),
+        // F3 = disconnect (only when connected)
+        KeyCode::F(3) if app.is_connected() => app.disconnect(),
+        // F9 / p = profile manager
+        KeyCode::F(9) | KeyCode::Char('p') => app.open_profile_dialog(),
         // ! = shell command dialog
         KeyCode::Char('!') => app.open_shell_dialog(),
+        KeyCode::Char('s') => app.open_remote_shell(),
+        KeyCode::Char('c') => app.open_temp_cleanup_dialog(),
+        // / = recursive search below the current remote directory
+        KeyCode::Char('/') if app.is_connected() => app.open_search_dialog(),
+        // g = grep file contents below the local directory
+        KeyCode::Char('g') => app.open_grep_dialog(),
+        // i = details popup (Ctrl+I is indistinguishable from Tab in terminals)
+        KeyCode::Char('i') => app.open_info_dialog(),
+        KeyCode::Char('l') => app.open_log_dialog(),
         KeyCode::Char('t') => app.open_tail_dialog(),
-
-        // F9 / p = profile manager
-        KeyCode::F(9) | KeyCode::Char('p') => app.open_profile_dialog(),
-
         _ => {}
     }
-    Ok(())
 }
 
-/// Cycle theme: Auto → Dark → Light → custom1 → custom2 → ... → Auto
+/// Cycle theme: Auto → Dark → Light → Monochrome → High-Contrast → custom1 → ... → Auto
 fn next_theme(current: &ThemeChoice, customs: &[String]) -> ThemeChoice {
     let list = build_cycle_list(customs);
     let pos = list.iter().position(|t| match (t, current) {
         (ThemeChoice::Auto, ThemeChoice::Auto) => true,
         (ThemeChoice::Dark, ThemeChoice::Dark) => true,
         (ThemeChoice::Light, ThemeChoice::Light) => true,
+        (ThemeChoice::Monochrome, ThemeChoice::Monochrome) => true,
+        (ThemeChoice::HighContrast, ThemeChoice::HighContrast) => true,
         (ThemeChoice::Custom(a), ThemeChoice::Custom(b)) => a == b,
         _ => false,
     });
@@ -327,7 +731,13 @@ fn next_theme(current: &ThemeChoice, customs: &[String]) -> ThemeChoice {
 }
 
 fn build_cycle_list(customs: &[String]) -> Vec<ThemeChoice> {
-    let mut v = vec![ThemeChoice::Auto, ThemeChoice::Dark, ThemeChoice::Light];
+    let mut v = vec![
+        ThemeChoice::Auto,
+        ThemeChoice::Dark,
+        ThemeChoice::Light,
+        ThemeChoice::Monochrome,
+        ThemeChoice::HighContrast,
+    ];
     for name in customs {
         v.push(ThemeChoice::Custom(name.clone()));
     }
@@ -338,83 +748,154 @@ fn build_cycle_list(customs: &[String]) -> Vec<ThemeChoice> {
 // Profile dialog key handling
 // ---------------------------------------------------------------------------
 
-fn handle_dialog_key(app: &mut App, code: KeyCode) {
+fn handle_dialog_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
     let mode = match app.profile_dialog.as_ref() {
         Some(d) => d.mode.clone(),
         None => return,
     };
 
     match mode {
-        ProfileDialogMode::List => handle_list_key(app, code),
+        ProfileDialogMode::List => handle_list_key(app, code, modifiers),
         ProfileDialogMode::New { field } => handle_new_form_key(app, code, field),
         ProfileDialogMode::Edit { field, index } => handle_edit_form_key(app, code, field, index),
         ProfileDialogMode::ConfirmDelete { index } => handle_confirm_delete_key(app, code, index),
     }
 }
 
-fn handle_list_key(app: &mut App, code: KeyCode) {
+fn handle_list_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    let (filtering, filtered) = match app.profile_dialog.as_ref() {
+        Some(d) => (d.filtering, !d.filter.is_empty()),
+        None => return,
+    };
+    // While the filter is being typed, letters extend it instead of acting as commands.
+    if filtering {
+        handle_list_filter_key(app, code);
+        return;
+    }
     match code {
+        // First Esc drops an active filter, the second closes the dialog.
+        KeyCode::Esc if filtered => with_profile_dialog(app, |d| d.clear_filter()),
         KeyCode::Esc => app.close_profile_dialog(),
-        KeyCode::Up => {
-            if let Some(d) = app.profile_dialog.as_mut() {
-                d.list_move_up();
-            }
-        }
-        KeyCode::Down => {
-            if let Some(d) = app.profile_dialog.as_mut() {
-                d.list_move_down();
-            }
-        }
-        KeyCode::Enter => {
-            // Take the selected profile and initiate connection
-            if let Some(d) = app.profile_dialog.as_ref() {
-                if d.store.profiles.is_empty() {
-                    return;
-                }
-                let profile = d.store.profiles[d.list_selected].clone();
-                app.close_profile_dialog();
-                app.begin_connect(profile);
-            }
+        KeyCode::Char('/') => with_profile_dialog(app, |d| d.filtering = true),
+        KeyCode::Backspace if filtered => with_profile_dialog(app, |d| d.filter_pop()),
+        // Shift+↑/↓ — move the profile within profiles.toml
+        KeyCode::Up | KeyCode::Down if modifiers.contains(KeyModifiers::SHIFT) => {
+            move_selected_profile(app, code == KeyCode::Up)
+        }
+        KeyCode::Up => with_profile_dialog(app, |d| d.list_move_up()),
+        KeyCode::Down => with_profile_dialog(app, |d| d.list_move_down()),
+        KeyCode::Enter => connect_selected_profile(app),
+        KeyCode::Char('c') | KeyCode::Char('C') => duplicate_profile(app),
+        KeyCode::Char('f') | KeyCode::Char('F') => forget_selected_password(app),
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            if let Some(profile) = selected_profile(app) {
+                app.test_connection(profile, None);
+            }
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => cycle_profile_sort(app),
+        KeyCode::Char('n' | 'N' | 'e' | 'E' | 'd' | 'D') | KeyCode::F(2) | KeyCode::Delete => {
+            with_profile_dialog(app, |d| open_profile_form(d, code))
         }
+        _ => {}
+    }
+}
+
+/// Keys while the profile filter is being typed.
+fn handle_list_filter_key(app: &mut App, code: KeyCode) {
+    with_profile_dialog(app, |d| match code {
+        KeyCode::Char(c) => d.filter_push(c),
+        KeyCode::Backspace => d.filter_pop(),
+        KeyCode::Enter => d.filtering = false,
+        KeyCode::Esc => d.clear_filter(),
+        KeyCode::Up => d.list_move_up(),
+        KeyCode::Down => d.list_move_down(),
+        _ => {}
+    });
+}
+
+/// Run `f` on the profile dialog, if it is open.
+fn with_profile_dialog(app: &mut App, f: impl FnOnce(&mut ProfileDialog)) {
+    if let Some(d) = app.profile_dialog.as_mut() {
+        f(d);
+    }
+}
+
+/// The highlighted profile of the profile list.
+fn selected_profile(app: &App) -> Option<Profile> {
+    let d = app.profile_dialog.as_ref()?;
+    d.selected_index().and_then(|i| d.store.profiles.get(i)).cloned()
+}
+
+/// Enter: close the dialog and connect with the highlighted profile.
+fn connect_selected_profile(app: &mut App) {
+    if let Some(profile) = selected_profile(app) {
+        app.close_profile_dialog();
+        app.begin_connect(profile);
+    }
+}
+
+/// n / e / d: switch the list to the new, edit or delete-confirm form.
+fn open_profile_form(d: &mut ProfileDialog, code: KeyCode) {
+    match code {
         KeyCode::Char('n') | KeyCode::Char('N') => {
-            if let Some(d) = app.profile_dialog.as_mut() {
-                d.mode = ProfileDialogMode::New { field: 0 };
-                d.form = crate::app::NewProfileForm::new();
-            }
+            d.mode = ProfileDialogMode::New { field: 0 };
+            d.form = crate::app::NewProfileForm::new();
         }
         KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::F(2) => {
-            if let Some(d) = app.profile_dialog.as_mut() {
-                if !d.store.profiles.is_empty() {
-                    let idx = d.list_selected;
-                    let p = &d.store.profiles[idx];
-                    d.form = crate::app::NewProfileForm {
-                        name:             p.name.clone(),
-                        host:             p.host.clone(),
-                        port:             p.port.to_string(),
-                        user:             p.user.clone(),
-                        auth:             p.auth.clone(),
-                        key_path:         p.key_path.clone().unwrap_or_else(|| "~/.ssh/id_rsa".to_string()),
-                        remote_path:      p.remote_path.clone().unwrap_or_default(),
-                        local_start_path: p.local_start_path.clone().unwrap_or_default(),
-                        save_password:    p.has_saved_password,
-                        password:         String::new(),
-                    };
-                    d.mode = ProfileDialogMode::Edit { field: 0, index: idx };
-                }
+            if let Some(idx) = d.selected_index() {
+                d.form = crate::app::NewProfileForm::from_profile(&d.store.profiles[idx]);
+                d.mode = ProfileDialogMode::Edit { field: 0, index: idx };
             }
         }
         KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
-            if let Some(d) = app.profile_dialog.as_mut() {
-                if !d.store.profiles.is_empty() {
-                    let idx = d.list_selected;
-                    d.mode = ProfileDialogMode::ConfirmDelete { index: idx };
-                }
+            if let Some(idx) = d.selected_index() {
+                d.mode = ProfileDialogMode::ConfirmDelete { index: idx };
             }
         }
         _ => {}
     }
 }
 
+/// Shift+↑/↓: move the highlighted profile one place up or down in
+/// profiles.toml. Only in file order and without a filter.
+fn move_selected_profile(app: &mut App, up: bool) {
+    let Some(d) = app.profile_dialog.as_mut() else {
+        return;
+    };
+    if d.sort != ProfileSort::Insertion {
+        app.set_status("Verschieben nur bei Sortierung 'Reihenfolge' (S)".to_string());
+    } else if !d.filter.is_empty() {
+        app.set_status("Verschieben nur ohne Filter (Esc)".to_string());
+    } else if d.move_selected(up) {
+        if let Err(e) = d.save() {
+            app.set_status(format!("Speichern fehlgeschlagen: {}", e));
+        }
+    }
+}
+
+/// S: cycle the sort order of the profile list.
+fn cycle_profile_sort(app: &mut App) {
+    let Some(d) = app.profile_dialog.as_mut() else {
+        return;
+    };
+    d.cycle_sort();
+    let sort = d.sort;
+    app.set_status(format!("Profile sortiert nach: {}", sort.label()));
+    app.settings.profile_sort = sort;
+    app.save_settings();
+}
+
+/// f: drop the saved keychain password of the highlighted profile.
+fn forget_selected_password(app: &mut App) {
+    match selected_profile(app).map(|p| (p.name, p.has_saved_password)) {
+        Some((name, true)) => app.forget_password(&name),
+        Some((name, false)) => {
+            app.set_status(format!("Kein gespeichertes Passwort für '{}'", name));
+        }
+        None => {}
+    }
+}
+
 fn handle_new_form_key(app: &mut App, code: KeyCode, field: usize) {
     match code {
         KeyCode::Esc => {
@@ -447,9 +928,15 @@ fn handle_new_form_key(app: &mut App, code: KeyCode, field: usize) {
                 d.form.save_password = !d.form.save_password;
             }
         }
+        KeyCode::Char(' ') if field == 10 => {
+            if let Some(d) = app.profile_dialog.as_mut() {
+                d.form.focus_remote = !d.form.focus_remote;
+            }
+        }
         KeyCode::Enter => {
             save_new_profile(app);
         }
+        KeyCode::F(5) => test_form_profile(app),
         KeyCode::Backspace => {
             if let Some(d) = app.profile_dialog.as_mut() {
                 if let Some(s) = d.form.active_field_mut(field) {
@@ -457,7 +944,7 @@ fn handle_new_form_key(app: &mut App, code: KeyCode, field: usize) {
                 }
             }
         }
-        KeyCode::Char(c) if field != 4 && field != 8 => {
+        KeyCode::Char(c) if field != 4 && field != 8 && field != 10 => {
             if let Some(d) = app.profile_dialog.as_mut() {
                 if field == 2 && !c.is_ascii_digit() {
                     return;
@@ -471,6 +958,40 @@ fn handle_new_form_key(app: &mut App, code: KeyCode, field: usize) {
     }
 }
 
+/// Copy the highlighted profile ('c'), save it and open the copy for editing.
+fn duplicate_profile(app: &mut App) {
+    let Some(d) = app.profile_dialog.as_mut() else {
+        return;
+    };
+    let Some(index) = d.duplicate_selected() else {
+        return;
+    };
+    let name = d.store.profiles[index].name.clone();
+    let saved = d.save();
+    d.form = crate::app::NewProfileForm::from_profile(&d.store.profiles[index]);
+    d.mode = ProfileDialogMode::Edit { field: 0, index };
+    app.set_status(match saved {
+        Ok(()) => format!("Profil dupliziert: '{}'", name),
+        Err(e) => format!("Speichern fehlgeschlagen: {}", e),
+    });
+}
+
+/// Test the profile as currently entered in the form (F5), before saving.
+fn test_form_profile(app: &mut App) {
+    let Some(d) = app.profile_dialog.as_ref() else {
+        return;
+    };
+    match d.form.to_profile() {
+        Some(profile) => {
+            let password = Some(d.form.password.clone()).filter(|pw| !pw.is_empty());
+            app.test_connection(profile, password);
+        }
+        None => {
+            app.set_status("Name, Host und User dürfen nicht leer sein".to_string());
+        }
+    }
+}
+
 /// Save a new profile with keychain handling.
 /// Keychain operations run BEFORE the TOML save so that
 /// `has_saved_password` always reflects the real keychain state.
@@ -499,11 +1020,11 @@ fn save_new_profile(app: &mut App) {
                     profile.has_saved_password = false;
                 }
                 d.store.add(profile);
+                d.resort();
                 match d.save() {
-                    Ok(()) => app.status_message = Some(msg),
+                    Ok(()) => app.set_status(msg),
                     Err(e) => {
-                        app.status_message =
-                            Some(format!("Speichern fehlgeschlagen: {}", e));
+                        app.set_status(format!("Speichern fehlgeschlagen: {}", e));
                     }
                 }
                 if let Some(d) = app.profile_dialog.as_mut() {
@@ -511,8 +1032,7 @@ fn save_new_profile(app: &mut App) {
                 }
             }
             None => {
-                app.status_message =
-                    Some("Name, Host und User dürfen nicht leer sein".to_string());
+                app.set_status("Name, Host und User dürfen nicht leer sein".to_string());
             }
         }
     }
@@ -550,9 +1070,15 @@ fn handle_edit_form_key(app: &mut App, code: KeyCode, field: usize, index: usize
                 d.form.save_password = !d.form.save_password;
             }
         }
+        KeyCode::Char(' ') if field == 10 => {
+            if let Some(d) = app.profile_dialog.as_mut() {
+                d.form.focus_remote = !d.form.focus_remote;
+            }
+        }
         KeyCode::Enter => {
             save_edited_profile(app, index);
         }
+        KeyCode::F(5) => test_form_profile(app),
         KeyCode::Backspace => {
             if let Some(d) = app.profile_dialog.as_mut() {
                 if let Some(s) = d.form.active_field_mut(field) {
@@ -560,7 +1086,7 @@ fn handle_edit_form_key(app: &mut App, code: KeyCode, field: usize, index: usize
                 }
             }
         }
-        KeyCode::Char(c) if field != 4 && field != 8 => {
+        KeyCode::Char(c) if field != 4 && field != 8 && field != 10 => {
             if let Some(d) = app.profile_dialog.as_mut() {
                 if field == 2 && !c.is_ascii_digit() {
                     return;
@@ -588,8 +1114,17 @@ fn save_edited_profile(app: &mut App, index: usize) {
             .get(index)
             .map(|p| p.has_saved_password)
             .unwrap_or(false);
+        // Fields without a form row are carried over from the stored profile.
+        let original = d.store.profiles.get(index).cloned();
         match d.form.to_profile() {
             Some(mut profile) => {
+                if let Some(original) = original {
+                    profile.last_used = original.last_used;
+                    profile.verify_uploads = original.verify_uploads;
+                    profile.jump_host = original.jump_host;
+                    profile.jump_user = original.jump_user;
+                    profile.jump_port = original.jump_port;
+                }
                 let name = profile.name.clone();
                 let mut msg = format!("Profil '{}' aktualisiert", name);
                 if wants_save {
@@ -614,11 +1149,11 @@ fn save_edited_profile(app: &mut App, index: usize) {
                     profile.has_saved_password = original_had_saved;
                 }
                 d.store.update(index, profile);
+                d.resort();
                 match d.save() {
-                    Ok(()) => app.status_message = Some(msg),
+                    Ok(()) => app.set_status(msg),
                     Err(e) => {
-                        app.status_message =
-                            Some(format!("Speichern fehlgeschlagen: {}", e));
+                        app.set_status(format!("Speichern fehlgeschlagen: {}", e));
                     }
                 }
                 if let Some(d) = app.profile_dialog.as_mut() {
@@ -626,8 +1161,7 @@ fn save_edited_profile(app: &mut App, index: usize) {
                 }
             }
             None => {
-                app.status_message =
-                    Some("Name, Host und User dürfen nicht leer sein".to_string());
+                app.set_status("Name, Host und User dürfen nicht leer sein".to_string());
             }
         }
     }
@@ -647,14 +1181,11 @@ fn handle_confirm_delete_key(app: &mut App, code: KeyCode, index: usize) {
                     let _ = crate::config::profiles::delete_password(name);
                 }
                 d.store.remove(index);
-                let len = d.store.profiles.len();
-                if d.list_selected >= len && len > 0 {
-                    d.list_selected = len - 1;
-                }
+                d.resort();
                 match d.save() {
-                    Ok(()) => app.status_message = Some("Profil gelöscht".to_string()),
+                    Ok(()) => app.set_status("Profil gelöscht".to_string()),
                     Err(e) => {
-                        app.status_message = Some(format!("Löschen fehlgeschlagen: {}", e));
+                        app.set_status(format!("Löschen fehlgeschlagen: {}", e));
                     }
                 }
                 if let Some(d) = app.profile_dialog.as_mut() {
@@ -678,8 +1209,18 @@ fn handle_confirm_delete_key(app: &mut App, code: KeyCode, index: usize) {
 fn handle_password_key(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Esc => {
-            app.password_dialog = None;
-            app.status_message = Some("Verbindung abgebrochen".to_string());
+            let test_only = app.password_dialog.take().is_some_and(|d| d.test_only);
+            app.set_status(if test_only {
+                "Verbindungstest abgebrochen".to_string()
+            } else {
+                "Verbindung abgebrochen".to_string()
+            });
+        }
+        KeyCode::Enter if app.password_dialog.as_ref().is_some_and(|d| d.test_only) => {
+            // Test runs in the background — the result lands in the status bar.
+            if let Some(dlg) = app.password_dialog.take() {
+                app.start_connection_test(dlg.profile, Some(dlg.input));
+            }
         }
         KeyCode::Enter => {
             // Take the dialog out, attempt connect, put back on failure
@@ -690,6 +1231,11 @@ fn handle_password_key(app: &mut App, code: KeyCode) {
                 app.do_connect(profile, Some(&password));
             }
         }
+        KeyCode::Tab => {
+            if let Some(dlg) = app.password_dialog.as_mut().filter(|d| !d.test_only) {
+                dlg.remember = !dlg.remember;
+            }
+        }
         KeyCode::Backspace => {
             if let Some(dlg) = app.password_dialog.as_mut() {
                 dlg.input.pop();
@@ -712,99 +1258,395 @@ fn handle_password_key(app: &mut App, code: KeyCode) {
 
 fn handle_rename_key(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Esc => {
-            app.rename_dialog = None;
-        }
-        KeyCode::Enter => {
-            app.confirm_rename();
-        }
-        KeyCode::Left => {
+        KeyCode::Esc => app.rename_dialog = None,
+        KeyCode::Enter => app.confirm_rename(),
+        _ => {
             if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.move_left();
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.error = None;
+                }
             }
         }
-        KeyCode::Right => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.move_right();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Batch rename dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_batch_rename_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.batch_rename_dialog = None,
+        KeyCode::Enter => app.confirm_batch_rename(),
+        _ => {
+            if let Some(dlg) = app.batch_rename_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.error = None;
+                }
             }
         }
-        KeyCode::Home => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.move_home();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mkdir dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_mkdir_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.mkdir_dialog = None,
+        KeyCode::Enter => app.confirm_mkdir(),
+        _ => {
+            if let Some(dlg) = app.mkdir_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.error = None;
+                }
             }
         }
-        KeyCode::End => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.move_end();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Go-to-path dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_goto_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.goto_dialog = None,
+        KeyCode::Enter => app.confirm_goto(),
+        KeyCode::Tab => app.complete_goto_path(),
+        _ => {
+            if let Some(dlg) = app.goto_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.edited();
+                }
             }
         }
-        KeyCode::Backspace => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.backspace();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Touch dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_touch_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.touch_dialog = None,
+        KeyCode::Enter => app.confirm_touch(),
+        _ => {
+            if let Some(dlg) = app.touch_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.error = None;
+                }
             }
         }
-        KeyCode::Delete => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.delete_forward();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Upload-as dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_upload_as_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.upload_as_dialog = None,
+        KeyCode::Enter => app.confirm_upload_as(),
+        _ => {
+            if let Some(dlg) = app.upload_as_dialog.as_mut() {
+                edit_text_input(&mut dlg.input, code);
             }
         }
-        KeyCode::Char(c) => {
-            if let Some(dlg) = app.rename_dialog.as_mut() {
-                dlg.insert(c);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mark-by-age dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_mark_age_key(app: &mut App, code: KeyCode) {
+    let Some(dlg) = app.mark_age_dialog.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => app.mark_age_dialog = None,
+        KeyCode::Enter => app.confirm_mark_age(),
+        KeyCode::Tab | KeyCode::BackTab => dlg.older = !dlg.older,
+        _ => {
+            if edit_text_input(&mut dlg.input, code) {
+                dlg.error = None;
             }
         }
-        _ => {}
     }
 }
 
 // ---------------------------------------------------------------------------
-// Mkdir dialog key handling
+// Mark-by-pattern dialog key handling
 // ---------------------------------------------------------------------------
 
-fn handle_mkdir_key(app: &mut App, code: KeyCode) {
+fn handle_mark_pattern_key(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Esc => {
-            app.mkdir_dialog = None;
+        KeyCode::Esc => app.mark_pattern_dialog = None,
+        KeyCode::Enter => app.confirm_mark_pattern(),
+        _ => {
+            if let Some(dlg) = app.mark_pattern_dialog.as_mut() {
+                edit_text_input(&mut dlg.input, code);
+            }
         }
-        KeyCode::Enter => {
-            app.confirm_mkdir();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Search dialog key handling
+// ---------------------------------------------------------------------------
+
+/// Input phase: edit the pattern, Enter starts the search.
+/// Result phase: ↑/↓ pick a match, Enter jumps to it, Esc cancels/closes.
+fn handle_search_key(app: &mut App, code: KeyCode) {
+    let searching = app.search_dialog.as_ref().is_some_and(|d| d.handle.is_some());
+    if searching {
+        match code {
+            KeyCode::Esc => app.close_search(),
+            KeyCode::Enter => app.search_jump(),
+            KeyCode::Up => {
+                if let Some(dlg) = app.search_dialog.as_mut() {
+                    dlg.move_up();
+                }
+            }
+            KeyCode::Down => {
+                if let Some(dlg) = app.search_dialog.as_mut() {
+                    dlg.move_down();
+                }
+            }
+            _ => {}
         }
-        KeyCode::Left => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.move_left();
+        return;
+    }
+    match code {
+        KeyCode::Esc => app.close_search(),
+        KeyCode::Enter => app.start_search(),
+        _ => {
+            if let Some(dlg) = app.search_dialog.as_mut() {
+                edit_text_input(&mut dlg.input, code);
             }
         }
-        KeyCode::Right => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.move_right();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Grep dialog key handling
+// ---------------------------------------------------------------------------
+
+/// Input phase: edit the pattern, Tab toggles regex, Enter starts the search.
+/// Result phase: ↑/↓ pick a match, Enter jumps to it, Esc cancels/closes.
+fn handle_grep_key(app: &mut App, code: KeyCode) {
+    let searching = app.grep_dialog.as_ref().is_some_and(|d| d.handle.is_some());
+    if searching {
+        match code {
+            KeyCode::Esc => app.close_grep(),
+            KeyCode::Enter => app.grep_jump(),
+            KeyCode::Up => {
+                if let Some(dlg) = app.grep_dialog.as_mut() {
+                    dlg.move_up();
+                }
             }
+            KeyCode::Down => {
+                if let Some(dlg) = app.grep_dialog.as_mut() {
+                    dlg.move_down();
+                }
+            }
+            _ => {}
         }
-        KeyCode::Home => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.move_home();
+        return;
+    }
+    match code {
+        KeyCode::Esc => app.close_grep(),
+        KeyCode::Enter => app.start_grep(),
+        KeyCode::Tab => {
+            if let Some(dlg) = app.grep_dialog.as_mut() {
+                dlg.regex = !dlg.regex;
+                dlg.error = None;
             }
         }
-        KeyCode::End => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.move_end();
+        _ => {
+            if let Some(dlg) = app.grep_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.error = None;
+                }
             }
         }
-        KeyCode::Backspace => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.backspace();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Status log key handling
+// ---------------------------------------------------------------------------
+
+fn handle_log_key(app: &mut App, code: KeyCode) {
+    let len = app.status_log.len();
+    let Some(dlg) = app.log_dialog.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc | KeyCode::Char('l') | KeyCode::Char('q') => app.log_dialog = None,
+        KeyCode::Up => dlg.move_up(1),
+        KeyCode::Down => dlg.move_down(1, len),
+        KeyCode::PageUp => dlg.move_up(10),
+        KeyCode::PageDown => dlg.move_down(10, len),
+        KeyCode::Home => dlg.selected = 0,
+        KeyCode::End => dlg.selected = len.saturating_sub(1),
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Command palette key handling
+// ---------------------------------------------------------------------------
+
+fn handle_palette_key(app: &mut App, code: KeyCode) -> Result<(), AppError> {
+    match code {
+        KeyCode::Esc => {
+            app.palette_dialog = None;
+        }
+        KeyCode::Enter => {
+            let action = app.palette_dialog.take().and_then(|d| d.selected_action());
+            if let Some(action) = action {
+                run_palette_action(app, action)?;
             }
         }
-        KeyCode::Delete => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.delete_forward();
+        KeyCode::Up => {
+            if let Some(dlg) = app.palette_dialog.as_mut() {
+                dlg.move_up();
             }
         }
-        KeyCode::Char(c) => {
-            if let Some(dlg) = app.mkdir_dialog.as_mut() {
-                dlg.insert(c);
+        KeyCode::Down => {
+            if let Some(dlg) = app.palette_dialog.as_mut() {
+                dlg.move_down();
+            }
+        }
+        _ => {
+            // A changed filter starts again at the first match.
+            if let Some(dlg) = app.palette_dialog.as_mut() {
+                if edit_text_input(&mut dlg.input, code) {
+                    dlg.selected = 0;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a palette action through the same path as its shortcut, so guards
+/// (connected, no transfer running, …) apply unchanged.
+fn run_palette_action(app: &mut App, action: PaletteAction) -> Result<(), AppError> {
+    let code = match action {
+        PaletteAction::SwapPanels => {
+            app.swap_panels();
+            return Ok(());
+        }
+        PaletteAction::SinglePanel => {
+            app.toggle_single_panel();
+            return Ok(());
+        }
+        PaletteAction::ToggleHidden => {
+            app.toggle_hidden();
+            return Ok(());
+        }
+        PaletteAction::ExactSizes => {
+            app.toggle_exact_sizes();
+            return Ok(());
+        }
+        PaletteAction::LocalPermissions => {
+            app.toggle_local_permissions();
+            return Ok(());
+        }
+        PaletteAction::RelativeDates => {
+            app.toggle_relative_dates();
+            return Ok(());
+        }
+        PaletteAction::Refresh => {
+            app.refresh_active_panel();
+            return Ok(());
+        }
+        PaletteAction::GoTo => {
+            app.open_goto_dialog();
+            return Ok(());
+        }
+        PaletteAction::HistoryBack => {
+            app.history_move(false);
+            return Ok(());
+        }
+        PaletteAction::HistoryForward => {
+            app.history_move(true);
+            return Ok(());
+        }
+        PaletteAction::CycleTheme => {
+            cycle_theme(app);
+            return Ok(());
+        }
+        PaletteAction::Help => {
+            app.help_visible = true;
+            return Ok(());
+        }
+        PaletteAction::EditNew => {
+            app.open_touch_dialog(true);
+            return Ok(());
+        }
+        PaletteAction::Reconnect => {
+            app.reconnect_last();
+            return Ok(());
+        }
+        PaletteAction::ConnectionDetails => {
+            app.log_connection_details();
+            return Ok(());
+        }
+        PaletteAction::Connect => KeyCode::F(9),
+        PaletteAction::Disconnect => KeyCode::F(3),
+        PaletteAction::Upload => KeyCode::F(5),
+        PaletteAction::UploadAs => KeyCode::Char('u'),
+        PaletteAction::Download => KeyCode::F(6),
+        PaletteAction::Edit => KeyCode::F(4),
+        PaletteAction::Rename => KeyCode::F(2),
+        PaletteAction::Mkdir => KeyCode::F(7),
+        PaletteAction::Touch => KeyCode::Char('n'),
+        PaletteAction::Delete => KeyCode::F(8),
+        PaletteAction::Chown => KeyCode::Char('o'),
+        PaletteAction::Info => KeyCode::Char('i'),
+        PaletteAction::StatusLog => KeyCode::Char('l'),
+        PaletteAction::Search => KeyCode::Char('/'),
+        PaletteAction::Grep => KeyCode::Char('g'),
+        PaletteAction::Cut => KeyCode::Char('x'),
+        PaletteAction::Copy => KeyCode::Char('y'),
+        PaletteAction::Paste => KeyCode::Char('v'),
+        PaletteAction::Tail => KeyCode::Char('t'),
+        PaletteAction::Compare => KeyCode::Char('='),
+        PaletteAction::Sync => KeyCode::Char('m'),
+        PaletteAction::Shell => KeyCode::Char('!'),
+        PaletteAction::RemoteShell => KeyCode::Char('s'),
+        PaletteAction::TempCleanup => KeyCode::Char('c'),
+        PaletteAction::MarkAll => KeyCode::Char('*'),
+        PaletteAction::MarkPattern => KeyCode::Char('+'),
+        PaletteAction::UnmarkPattern => KeyCode::Char('-'),
+        PaletteAction::MarkAge => KeyCode::Char('a'),
+        PaletteAction::InvertMarks => KeyCode::Char('#'),
+        PaletteAction::SwitchPanel => KeyCode::Tab,
+        PaletteAction::Quit => KeyCode::Char('q'),
+    };
+    handle_main_key(app, code)
+}
+
+// ---------------------------------------------------------------------------
+// Chown dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_chown_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.chown_dialog = None,
+        KeyCode::Enter => app.confirm_chown(),
+        _ => {
+            if let Some(dlg) = app.chown_dialog.as_mut() {
+                edit_text_input(&mut dlg.input, code);
             }
         }
-        _ => {}
     }
 }
 
@@ -818,22 +1660,109 @@ fn handle_delete_key(app: &mut App, code: KeyCode) {
             app.confirm_delete();
         }
         KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
-            app.delete_dialog = None;
+            if let Some(dlg) = app.delete_dialog.take() {
+                dlg.cancel();
+            }
+        }
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Edit conflict dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_edit_conflict_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.resolve_edit_conflict(true);
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.resolve_edit_conflict(false);
         }
         _ => {}
     }
 }
 
+fn handle_edit_upload_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.resolve_edit_upload(true);
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.resolve_edit_upload(false);
+        }
+        _ => {}
+    }
+}
+
+fn handle_edit_size_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.resolve_edit_size(true);
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.resolve_edit_size(false);
+        }
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Temp cleanup dialog key handling
+// ---------------------------------------------------------------------------
+
+fn handle_temp_cleanup_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.confirm_temp_cleanup();
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.temp_cleanup_dialog = None;
+        }
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sync preview key handling
+// ---------------------------------------------------------------------------
+
+fn handle_sync_key(app: &mut App, code: KeyCode) {
+    let dlg = match app.sync_dialog.as_mut() {
+        Some(d) => d,
+        None => return,
+    };
+    match code {
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_sync(),
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => app.sync_dialog = None,
+        KeyCode::Char('d') | KeyCode::Char('D') => dlg.delete_extra = !dlg.delete_extra,
+        KeyCode::Up => dlg.selected = dlg.selected.saturating_sub(1),
+        KeyCode::Down => dlg.selected = (dlg.selected + 1).min(dlg.len().saturating_sub(1)),
+        _ => {}
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shell command dialog key handling
 // ---------------------------------------------------------------------------
 
-/// Approximate number of output lines visible in the shell output popup.
-const SHELL_VISIBLE_LINES: usize = 20;
-/// Lines scrolled per PgUp / PgDn.
-const SHELL_PAGE_SIZE: usize = 10;
+/// Output lines visible in the shell output popup at the current terminal
+/// size — fallback before the popup has been rendered once.
+fn shell_visible_lines() -> usize {
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    ui::dialogs::shell_output_lines(ratatui::layout::Rect::new(0, 0, width, height)).max(1)
+}
 
 fn handle_shell_key(app: &mut App, code: KeyCode) {
+    let visible = app
+        .shell_dialog
+        .as_ref()
+        .map(|d| d.view_height.get())
+        .filter(|&h| h > 0)
+        .unwrap_or_else(shell_visible_lines);
+    // PgUp / PgDn move half a screen.
+    let page = (visible / 2).max(1);
     let in_output = app
         .shell_dialog
         .as_ref()
@@ -841,50 +1770,64 @@ fn handle_shell_key(app: &mut App, code: KeyCode) {
         .unwrap_or(false);
 
     if in_output {
+        // Rows as last rendered (wrapped lines count several times); the raw
+        // line count until the first render.
         let total = app
             .shell_dialog
             .as_ref()
-            .and_then(|d| d.output.as_ref())
-            .map(|l| l.len())
+            .map(|d| match d.content_rows.get() {
+                0 => d.output.as_ref().map(|l| l.len()).unwrap_or(0),
+                rows => rows,
+            })
             .unwrap_or(0);
         match code {
             KeyCode::Esc | KeyCode::Char('q') => { app.shell_dialog = None; }
+            KeyCode::Char('w') => {
+                // Row count changes with wrapping — start again from the top.
+                if let Some(d) = app.shell_dialog.as_mut() {
+                    d.wrap = !d.wrap;
+                    d.scroll = 0;
+                }
+            }
             KeyCode::Up => {
                 if let Some(d) = app.shell_dialog.as_mut() { d.scroll_up(); }
             }
             KeyCode::Down => {
                 if let Some(d) = app.shell_dialog.as_mut() {
-                    d.scroll_down(total, SHELL_VISIBLE_LINES);
+                    d.scroll_down(total, visible);
                 }
             }
             KeyCode::PageUp => {
-                if let Some(d) = app.shell_dialog.as_mut() { d.page_up(SHELL_PAGE_SIZE); }
+                if let Some(d) = app.shell_dialog.as_mut() { d.page_up(page); }
             }
             KeyCode::PageDown => {
                 if let Some(d) = app.shell_dialog.as_mut() {
-                    d.page_down(total, SHELL_VISIBLE_LINES, SHELL_PAGE_SIZE);
+                    d.page_down(total, visible, page);
                 }
             }
             _ => {}
         }
+    } else if app.shell_dialog.as_ref().is_some_and(|d| d.running.is_some()) {
+        // A remote command is running: only Esc (give up on it) is accepted.
+        if code == KeyCode::Esc {
+            if let Some(d) = app.shell_dialog.take() {
+                d.cancel();
+            }
+            app.set_status("Remote-Befehl abgebrochen".to_string());
+        }
     } else {
+        if let Some(d) = app.shell_dialog.as_mut() {
+            d.candidates.clear();
+        }
         match code {
             KeyCode::Esc => { app.shell_dialog = None; }
             KeyCode::Enter => { app.run_shell_command(); }
-            KeyCode::Left  => { if let Some(d) = app.shell_dialog.as_mut() { d.move_left(); } }
-            KeyCode::Right => { if let Some(d) = app.shell_dialog.as_mut() { d.move_right(); } }
-            KeyCode::Home  => { if let Some(d) = app.shell_dialog.as_mut() { d.move_home(); } }
-            KeyCode::End   => { if let Some(d) = app.shell_dialog.as_mut() { d.move_end(); } }
-            KeyCode::Backspace => {
-                if let Some(d) = app.shell_dialog.as_mut() { d.backspace(); }
-            }
-            KeyCode::Delete => {
-                if let Some(d) = app.shell_dialog.as_mut() { d.delete_forward(); }
-            }
-            KeyCode::Char(c) => {
-                if let Some(d) = app.shell_dialog.as_mut() { d.insert(c); }
+            KeyCode::Tab => { app.complete_shell_path(); }
+            _ => {
+                if let Some(d) = app.shell_dialog.as_mut() {
+                    edit_text_input(&mut d.input, code);
+                }
             }
-            _ => {}
         }
     }
 }
@@ -895,8 +1838,8 @@ fn handle_shell_key(app: &mut App, code: KeyCode) {
 
 /// Total form fields:
 /// 0=Name 1=Host 2=Port 3=User 4=Auth 5=KeyPath
-/// 6=RemotePath 7=LocalPath 8=SavePassword 9=Password
-const FORM_FIELDS: usize = 10;
+/// 6=RemotePath 7=LocalPath 8=SavePassword 9=Password 10=FocusRemote
+const FORM_FIELDS: usize = 11;
 
 /// Determine whether a field is visible given the current form state.
 fn field_visible(idx: usize, auth: &AuthMethod, save_pw: bool) -> bool {
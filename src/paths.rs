@@ -0,0 +1,118 @@
+//! Expansion of user-entered local paths: `~`, `~user`, `$VAR` and `${VAR}`.
+//!
+//! Anything that cannot be expanded (unknown user, unset variable, unclosed
+//! brace) is kept literally, so a typo shows up as a missing path instead of
+//! silently pointing somewhere else.
+
+use std::ffi::{CStr, CString};
+use std::path::PathBuf;
+
+/// Expand a local path as a shell would for `~`, `~user` and environment variables.
+/// Tilde expansion comes first, so a variable whose value starts with `~` is
+/// not expanded a second time.
+pub fn expand_local_path(input: &str) -> PathBuf {
+    PathBuf::from(expand_vars(&expand_home(input)))
+}
+
+/// Replace a leading `~` or `~user` with the home directory.
+fn expand_home(input: &str) -> String {
+    let Some(rest) = input.strip_prefix('~') else {
+        return input.to_string();
+    };
+    let (user, tail) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    let home = if user.is_empty() {
+        std::env::var("HOME").ok()
+    } else {
+        user_home(user)
+    };
+    match home {
+        Some(home) => format!("{}{}", home, tail),
+        None => input.to_string(),
+    }
+}
+
+/// Start and upper bound for the `getpwnam_r` string buffer.
+const PASSWD_BUF_START: usize = 1024;
+const PASSWD_BUF_MAX: usize = 1024 * 1024;
+
+/// Home directory of `user` from the passwd database. Uses the reentrant
+/// `getpwnam_r`, since paths are also expanded on worker threads.
+fn user_home(user: &str) -> Option<String> {
+    let name = CString::new(user).ok()?;
+    let mut buf: Vec<libc::c_char> = vec![0; PASSWD_BUF_START];
+    loop {
+        // SAFETY: `passwd` is a plain C struct of integers and pointers, for
+        // which all-zero bytes are a valid value; getpwnam_r overwrites it.
+        let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result: *mut libc::passwd = std::ptr::null_mut();
+        // SAFETY: `name` is NUL-terminated, `buf` is a writable buffer of the
+        // given length, and `pwd` / `result` are valid for writes. All of them
+        // outlive the call.
+        let rc = unsafe {
+            libc::getpwnam_r(name.as_ptr(), &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result)
+        };
+        if rc == libc::ERANGE && buf.len() < PASSWD_BUF_MAX {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+        if rc != 0 || result.is_null() || pwd.pw_dir.is_null() {
+            return None;
+        }
+        // SAFETY: on success `pw_dir` points to a NUL-terminated string
+        // inside `buf`, which is still alive and not modified meanwhile.
+        let dir = unsafe { CStr::from_ptr(pwd.pw_dir) };
+        return dir.to_str().ok().map(str::to_string);
+    }
+}
+
+/// Substitute `$VAR` and `${VAR}`; unset variables stay as written.
+fn expand_vars(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, literal_len) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+        match std::env::var(name).ok().filter(|_| !name.is_empty()) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push('$');
+                out.push_str(&after[..literal_len]);
+            }
+        }
+        rest = &after[literal_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_user_home_is_expanded() {
+        let path = expand_local_path("~root/x");
+        assert!(path.is_absolute());
+        assert!(path.ends_with("x"));
+    }
+
+    #[test]
+    fn unknown_user_and_unset_variable_stay_literal() {
+        assert_eq!(expand_local_path("~no_such_user_vela/x"), PathBuf::from("~no_such_user_vela/x"));
+        assert_eq!(expand_local_path("${VELA_UNSET_TEST_VAR}/x"), PathBuf::from("${VELA_UNSET_TEST_VAR}/x"));
+    }
+}
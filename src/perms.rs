@@ -0,0 +1,16 @@
+//! Unix permission bits as shown in the panels and the info dialog, shared by
+//! local listings (`std::fs` metadata) and remote ones (SFTP attributes).
+
+/// Convert a Unix mode bitmask into a `rwxr-xr-x` style string.
+pub fn format_permissions(mode: u32) -> String {
+    let flags = [
+        (0o400, 'r'), (0o200, 'w'), (0o100, 'x'),
+        (0o040, 'r'), (0o020, 'w'), (0o010, 'x'),
+        (0o004, 'r'), (0o002, 'w'), (0o001, 'x'),
+    ];
+    let mut s = String::with_capacity(9);
+    for (bit, ch) in &flags {
+        s.push(if mode & bit != 0 { *ch } else { '-' });
+    }
+    s
+}
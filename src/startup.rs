@@ -0,0 +1,202 @@
+//! Connect-on-launch target from the command line:
+//!
+//!   vela <profile-name>
+//!   vela [user@]host[:/remote/path]      (IPv6: [user@][::1][:/remote/path])
+//!
+//! A name that matches a saved profile wins; anything containing `@` or `:`
+//! is taken as an ad-hoc target and turned into an unsaved key-auth profile.
+//!
+//! For unattended use, password profiles take their password from
+//! `--password-file <path>` or `$VELA_PASSWORD` instead of asking.
+
+use std::path::Path;
+
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::config::profiles::{AuthMethod, ConfigError, Profile, ProfileStore};
+
+pub const USAGE: &str = "usage: vela [--mouse | --no-mouse] [--no-color] [--password-file <path>] [<profile> | [user@]host[:path]]\n       \
+                         vela --get <profile> <remote-path> <local-dir>\n       \
+                         vela --put <profile> <local-path> <remote-dir>";
+
+/// Flags the TUI understands; everything else starting with `-` is rejected.
+const TUI_FLAGS: &[&str] = &["--mouse", "--no-mouse", "--no-color"];
+
+/// Flag taking the path of a file that holds the connection password.
+const PASSWORD_FILE_FLAG: &str = "--password-file";
+
+/// Environment variable consulted for password-auth profiles.
+pub const PASSWORD_ENV: &str = "VELA_PASSWORD";
+
+#[derive(Debug, Error)]
+pub enum StartupError {
+    #[error("Unknown option: {0}")]
+    UnknownOption(String),
+    #[error("Only one connection target may be given")]
+    TooManyTargets,
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    #[error("Unknown profile: {0}")]
+    UnknownProfile(String),
+    #[error("Invalid target: {0}")]
+    InvalidTarget(String),
+    #[error("Option {0} needs a value")]
+    MissingValue(String),
+    #[error("Cannot read password file {0}: {1}")]
+    PasswordFile(String, std::io::Error),
+}
+
+/// Resolve the optional connection target in `args` (without argv[0]).
+/// Returns `Ok(None)` when only flags were given.
+pub fn parse_target(args: &[String]) -> Result<Option<Profile>, StartupError> {
+    let mut target: Option<&str> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if TUI_FLAGS.contains(&arg.as_str()) {
+            continue;
+        }
+        if arg == PASSWORD_FILE_FLAG {
+            args.next().ok_or_else(|| StartupError::MissingValue(arg.clone()))?;
+            continue;
+        }
+        if arg.starts_with('-') {
+            return Err(StartupError::UnknownOption(arg.clone()));
+        }
+        if target.replace(arg).is_some() {
+            return Err(StartupError::TooManyTargets);
+        }
+    }
+    let target = match target {
+        Some(t) => t,
+        None => return Ok(None),
+    };
+
+    let store = ProfileStore::load()?;
+    resolve_target(target, store.profiles).map(Some)
+}
+
+/// A saved profile named `target`, else an ad-hoc profile when `target`
+/// looks like `[user@]host[:path]`.
+fn resolve_target(target: &str, profiles: Vec<Profile>) -> Result<Profile, StartupError> {
+    if let Some(profile) = profiles.into_iter().find(|p| p.name == target) {
+        return Ok(profile);
+    }
+    if !target.contains(['@', ':', '[']) {
+        return Err(StartupError::UnknownProfile(target.to_string()));
+    }
+    adhoc_profile(target)
+}
+
+/// The non-interactive password, if any: the first line of the file named by
+/// `--password-file`, otherwise `$VELA_PASSWORD`. An empty value counts as
+/// none. The password itself never appears in errors or the status bar.
+pub fn password(args: &[String]) -> Result<Option<Zeroizing<String>>, StartupError> {
+    let file = args
+        .iter()
+        .position(|a| a == PASSWORD_FILE_FLAG)
+        .map(|i| {
+            args.get(i + 1)
+                .ok_or_else(|| StartupError::MissingValue(PASSWORD_FILE_FLAG.to_string()))
+        })
+        .transpose()?;
+    let password = match file {
+        Some(path) => read_password_file(Path::new(path))?,
+        None => match std::env::var(PASSWORD_ENV) {
+            Ok(pw) => Zeroizing::new(pw),
+            Err(_) => return Ok(None),
+        },
+    };
+    Ok(Some(password).filter(|pw| !pw.is_empty()))
+}
+
+/// First line of `path` without its line ending.
+fn read_password_file(path: &Path) -> Result<Zeroizing<String>, StartupError> {
+    let content = std::fs::read_to_string(path)
+        .map(Zeroizing::new)
+        .map_err(|e| StartupError::PasswordFile(path.display().to_string(), e))?;
+    let line = content.lines().next().unwrap_or_default();
+    Ok(Zeroizing::new(line.to_string()))
+}
+
+/// Build an unsaved profile from `[user@]host[:path]`. The user defaults to
+/// `$USER`; authentication uses the default key file. IPv6 literals must be
+/// bracketed so their colons are not taken as the path separator.
+fn adhoc_profile(target: &str) -> Result<Profile, StartupError> {
+    let invalid = || StartupError::InvalidTarget(target.to_string());
+    let (user, rest) = match target.split_once('@') {
+        Some((user, rest)) => (user.to_string(), rest),
+        None => (std::env::var("USER").unwrap_or_default(), target),
+    };
+    let (host, path) = match rest.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, after) = bracketed.split_once(']').ok_or_else(invalid)?;
+            match after {
+                "" => (host, None),
+                _ => (host, Some(after.strip_prefix(':').ok_or_else(invalid)?)),
+            }
+        }
+        None => match rest.split_once(':') {
+            Some((host, path)) => (host, Some(path)),
+            None => (rest, None),
+        },
+    };
+    if user.is_empty() || host.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Profile {
+        name: format!("{}@{}", user, host),
+        host: host.to_string(),
+        port: 22,
+        user,
+        auth: AuthMethod::Key,
+        key_path: None,
+        remote_path: path.filter(|p| !p.is_empty()).map(str::to_string),
+        local_start_path: None,
+        has_saved_password: false,
+        last_used: None,
+        verify_uploads: false,
+        jump_host: None,
+        jump_user: None,
+        jump_port: None,
+        focus_remote: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bracketed_ipv6_target_with_path() {
+        let profile = resolve_target("user@[::1]:/srv/www", Vec::new()).unwrap();
+        assert_eq!(profile.user, "user");
+        assert_eq!(profile.host, "::1");
+        assert_eq!(profile.remote_path.as_deref(), Some("/srv/www"));
+        let profile = adhoc_profile("user@[fe80::1]").unwrap();
+        assert_eq!(profile.host, "fe80::1");
+        assert_eq!(profile.remote_path, None);
+    }
+
+    #[test]
+    fn malformed_ipv6_targets_are_rejected() {
+        for target in ["user@[::1", "user@[::1]/srv", "user@[]:/srv"] {
+            assert!(matches!(resolve_target(target, Vec::new()), Err(StartupError::InvalidTarget(_))), "{}", target);
+        }
+    }
+
+    #[test]
+    fn saved_profile_name_wins_over_adhoc_parsing() {
+        let saved = adhoc_profile("deploy@[::1]:/srv").unwrap();
+        let name = saved.name.clone();
+        let profile = resolve_target(&name, vec![saved]).unwrap();
+        assert_eq!(profile.remote_path.as_deref(), Some("/srv"));
+        assert!(matches!(resolve_target("nosuch", Vec::new()), Err(StartupError::UnknownProfile(_))));
+    }
+
+    #[test]
+    fn flags_alone_give_no_target() {
+        let args = vec!["--no-mouse".to_string(), "--no-color".to_string()];
+        assert!(matches!(parse_target(&args), Ok(None)));
+    }
+}
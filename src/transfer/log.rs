@@ -0,0 +1,82 @@
+//! Opt-in audit log of transferred files (settings `transfer_log`).
+//!
+//! Every finished upload or download file appends one tab-separated line to
+//! `~/.config/vela/transfers.log`:
+//!
+//!   timestamp  direction  profile  remote path  local path  bytes  result
+//!
+//! Writing is best-effort: a log that cannot be opened never fails a transfer.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::settings::config_dir;
+use crate::ui::panels::format_timestamp;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    Upload,
+    Download,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Upload => "upload",
+            Self::Download => "download",
+        }
+    }
+}
+
+/// Path of the transfer log file.
+pub fn log_path() -> PathBuf {
+    config_dir().join("transfers.log")
+}
+
+/// Append one record. `error` is None for a completed file.
+pub fn record(
+    profile: &str,
+    direction: Direction,
+    remote: &Path,
+    local: &Path,
+    bytes: u64,
+    error: Option<&str>,
+) {
+    let result = match error {
+        None => "ok".to_string(),
+        // Keep one record per line.
+        Some(e) => format!("failed: {}", e.replace(['\n', '\t'], " ")),
+    };
+    append(profile, direction, remote, local, bytes, &result);
+}
+
+/// Append a record for a file the batch did not transfer, e.g. a FIFO.
+pub fn record_skipped(profile: &str, direction: Direction, remote: &Path, local: &Path, reason: &str) {
+    append(profile, direction, remote, local, 0, &format!("skipped: {}", reason));
+}
+
+fn append(profile: &str, direction: Direction, remote: &Path, local: &Path, bytes: u64, result: &str) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let line = format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+        format_timestamp(now),
+        direction.as_str(),
+        profile,
+        remote.display(),
+        local.display(),
+        bytes,
+        result
+    );
+    let path = log_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = file.write_all(line.as_bytes());
+    }
+}
@@ -1 +1,3 @@
+pub mod log;
+pub mod notify;
 pub mod queue;
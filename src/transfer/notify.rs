@@ -0,0 +1,51 @@
+//! Announce finished uploads and downloads (settings `transfer_notify`), so a
+//! long transfer can run while you work in another window.
+//!
+//! `bell` rings the terminal bell. `desktop` posts a notification through
+//! `notify-send` (Linux) or `osascript` (macOS); when that tool is missing
+//! nothing happens.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::config::settings::TransferNotify;
+
+/// Announce a finished transfer described by `text` the way `mode` asks.
+pub fn transfer_finished(mode: TransferNotify, text: &str) {
+    match mode {
+        TransferNotify::Off => {}
+        TransferNotify::Bell => {
+            let mut out = std::io::stdout();
+            let _ = out.write_all(b"\x07");
+            let _ = out.flush();
+        }
+        TransferNotify::Desktop => {
+            let mut cmd = desktop_command(text);
+            // Wait on a thread of its own so the UI never blocks and the
+            // child is reaped.
+            std::thread::spawn(move || {
+                let _ = cmd
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn desktop_command(text: &str) -> Command {
+    let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut cmd = Command::new("osascript");
+    cmd.arg("-e")
+        .arg(format!("display notification \"{}\" with title \"Vela\"", quote(text)));
+    cmd
+}
+
+#[cfg(not(target_os = "macos"))]
+fn desktop_command(text: &str) -> Command {
+    let mut cmd = Command::new("notify-send");
+    cmd.arg("--app-name=Vela").arg("Vela").arg(text);
+    cmd
+}
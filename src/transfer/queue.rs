@@ -1,5 +1,8 @@
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use crate::app::FileEntry;
+
 /// Current state of a running transfer (upload or download).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TransferState {
@@ -20,14 +23,26 @@ pub struct TransferProgress {
     pub state: TransferState,
     /// Name of the file currently being transferred.
     pub current_file: String,
-    /// Bytes transferred for the current file.
+    /// Bytes transferred for the current file (downloads: for the whole batch).
     pub bytes_done: u64,
-    /// Total bytes of the current file (0 if unknown / directory).
+    /// Total bytes of the current file (downloads: of the whole batch; 0 if unknown).
     pub bytes_total: u64,
     /// Number of files fully transferred so far.
     pub files_done: usize,
     /// Total number of files to transfer.
     pub files_total: usize,
+    /// Downloads continued from a partial local file instead of restarted.
+    pub files_resumed: usize,
+    /// Uploads whose remote SHA-256 matched the local file.
+    pub files_verified: usize,
+    /// Profile name to record finished files under in the transfer log
+    /// (settings `transfer_log`); None = no logging.
+    pub log_profile: Option<String>,
+    /// A failed file's partly written destination, created by this batch,
+    /// was removed again.
+    pub partial_removed: bool,
+    /// Special files (FIFOs, sockets, devices) an upload left out.
+    pub files_skipped: usize,
 }
 
 // Backwards-compat alias used by the upload code.
@@ -42,6 +57,21 @@ impl TransferProgress {
             bytes_total: 0,
             files_done: 0,
             files_total,
+            files_resumed: 0,
+            files_verified: 0,
+            log_profile: None,
+            partial_removed: false,
+            files_skipped: 0,
+        }
+    }
+
+    /// Failure text for the status bar: `error`, plus a note when the
+    /// incomplete destination file was cleaned up.
+    pub fn failure_message(&self, error: &str) -> String {
+        if self.partial_removed {
+            format!("{} — unvollständige Datei entfernt", error)
+        } else {
+            error.to_string()
         }
     }
 
@@ -65,6 +95,92 @@ impl TransferProgress {
     }
 }
 
+/// An upload or download waiting for the running transfer to finish
+/// (F5 / F6 while busy). The directories are fixed when the job is queued,
+/// so navigating away in the meantime does not change its target.
+pub enum TransferJob {
+    Upload {
+        entries: Vec<FileEntry>,
+        local_dir: PathBuf,
+        remote_dir: PathBuf,
+        /// Remote name for a single file (upload-as).
+        dest_name: Option<String>,
+    },
+    Download {
+        entries: Vec<FileEntry>,
+        remote_dir: PathBuf,
+        local_dir: PathBuf,
+    },
+}
+
+impl TransferJob {
+    /// Short description for the status bar: "'name'", "'a' als 'b'" or
+    /// "N Dateien".
+    pub fn label(&self) -> String {
+        let (entries, dest_name) = match self {
+            TransferJob::Upload { entries, dest_name, .. } => (entries, dest_name.as_deref()),
+            TransferJob::Download { entries, .. } => (entries, None),
+        };
+        // Dropped files carry their full path as name; show the basename.
+        let base = |e: &FileEntry| {
+            Path::new(&e.name)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| e.name.clone())
+        };
+        match (dest_name, entries.as_slice()) {
+            (Some(dest), [entry]) => format!("'{}' als '{}'", base(entry), dest),
+            (_, [entry]) => format!("'{}'", base(entry)),
+            _ => format!("{} Dateien", entries.len()),
+        }
+    }
+}
+
+/// A queued job with the number of files it will transfer, counted when it
+/// was queued.
+pub struct QueuedJob {
+    pub job: TransferJob,
+    pub files: usize,
+    /// The job downloads directories whose contents are only counted once it
+    /// starts, so `files` is a lower bound.
+    pub open_ended: bool,
+}
+
+/// Position of the current queue run, drawn next to the running transfer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueProgress {
+    /// Jobs finished since the queue was last idle.
+    pub jobs_done: usize,
+    /// Jobs waiting behind the running one.
+    pub jobs_waiting: usize,
+    /// Files transferred by the finished jobs.
+    pub files_done: usize,
+    /// Files of the waiting jobs.
+    pub files_waiting: usize,
+    /// A waiting job's file count is only a lower bound.
+    pub open_ended: bool,
+}
+
+impl QueueProgress {
+    /// True when the running transfer is the only job of its run.
+    pub fn is_single(&self) -> bool {
+        self.jobs_done == 0 && self.jobs_waiting == 0
+    }
+
+    /// Overall label part "Auftrag n/m — gesamt x/y Dateien", given the
+    /// running job's own file counts.
+    pub fn label(&self, files_done: usize, files_total: usize) -> String {
+        format!(
+            "Auftrag {}/{} — gesamt {}/{}{} Dateien",
+            self.jobs_done + 1,
+            self.jobs_done + 1 + self.jobs_waiting,
+            self.files_done + files_done,
+            self.files_done + files_total + self.files_waiting,
+            if self.open_ended { "+" } else { "" }
+        )
+    }
+}
+
 /// A thread-safe handle to transfer progress.
 pub type TransferHandle = Arc<Mutex<TransferProgress>>;
 
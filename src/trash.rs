@@ -0,0 +1,196 @@
+//! Local trash for F8 with `safe_delete`: entries are moved into the
+//! freedesktop.org home trash (`$XDG_DATA_HOME/Trash`, usually
+//! `~/.local/share/Trash`) with a `.trashinfo` record, so desktop file
+//! managers can restore them. On macOS they go to `~/.Trash`.
+//!
+//! Entries on another filesystem than the home trash go to the trash at the
+//! top of their own mount instead (`$topdir/.Trash/$uid` or
+//! `$topdir/.Trash-$uid`; `/Volumes/X/.Trashes/$uid` on macOS), since a
+//! rename cannot cross filesystems and copying would defeat a cheap delete.
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+
+/// Move `path` (file, directory or symlink) into the trash.
+pub fn move_to_trash(path: &Path) -> io::Result<()> {
+    let path = std::path::absolute(path)?;
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "kein Dateiname"))?
+        .to_string_lossy()
+        .into_owned();
+    platform_trash(&path, &name)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn platform_trash(path: &Path, name: &str) -> io::Result<()> {
+    use std::io::Write;
+
+    let (trash, recorded) = match mount_top(path, &home_trash()?)? {
+        None => (home_trash()?, path.to_path_buf()),
+        // The spec records paths relative to the top directory here, so the
+        // entry restores correctly wherever the volume is mounted next.
+        Some(top) => (
+            topdir_trash(&top)?,
+            path.strip_prefix(&top).unwrap_or(path).to_path_buf(),
+        ),
+    };
+    let files = trash.join("files");
+    let info = trash.join("info");
+    fs::create_dir_all(&files)?;
+    fs::create_dir_all(&info)?;
+
+    // Reserve a free name by creating its .trashinfo exclusively, as the
+    // spec asks, then move the entry under the same name.
+    let mut n = 1;
+    let (trash_name, info_path, mut info_file) = loop {
+        let candidate = match n {
+            1 => name.to_string(),
+            n => format!("{}.{}", name, n),
+        };
+        n += 1;
+        if fs::symlink_metadata(files.join(&candidate)).is_ok() {
+            continue;
+        }
+        let info_path = info.join(format!("{}.trashinfo", candidate));
+        match fs::OpenOptions::new().write(true).create_new(true).open(&info_path) {
+            Ok(f) => break (candidate, info_path, f),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let date = crate::ui::panels::format_timestamp(now).replacen(' ', "T", 1);
+    let record = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode(&recorded),
+        date
+    );
+    if let Err(e) = info_file
+        .write_all(record.as_bytes())
+        .and_then(|_| fs::rename(path, files.join(&trash_name)))
+    {
+        let _ = fs::remove_file(&info_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_trash(path: &Path, name: &str) -> io::Result<()> {
+    let home = std::env::var("HOME").map_err(|_| io::Error::other("HOME nicht gesetzt"))?;
+    let home_trash = PathBuf::from(home).join(".Trash");
+    let trash = match mount_top(path, &home_trash)? {
+        None => home_trash,
+        Some(top) => private_dir(&top.join(".Trashes").join(uid().to_string()))?,
+    };
+    fs::create_dir_all(&trash)?;
+    let dest = (1..)
+        .map(|n| match n {
+            1 => trash.join(name),
+            n => trash.join(format!("{} {}", name, n)),
+        })
+        .find(|p| fs::symlink_metadata(p).is_err())
+        .unwrap_or_else(|| trash.join(name));
+    fs::rename(path, dest)
+}
+
+/// None when `path` lives on the same filesystem as `home_trash`, else the
+/// top directory of `path`'s own mount: its highest ancestor still on the
+/// same device.
+fn mount_top(path: &Path, home_trash: &Path) -> io::Result<Option<PathBuf>> {
+    let dev = fs::symlink_metadata(path)?.dev();
+    // The home trash may not exist yet; its closest existing ancestor is on
+    // the filesystem it will be created on.
+    let home_dev = home_trash
+        .ancestors()
+        .find_map(|dir| fs::metadata(dir).ok())
+        .map(|meta| meta.dev());
+    if home_dev == Some(dev) {
+        return Ok(None);
+    }
+    let mut top = path;
+    while let Some(parent) = top.parent() {
+        if fs::metadata(parent).map(|meta| meta.dev()).ok() != Some(dev) {
+            break;
+        }
+        top = parent;
+    }
+    Ok(Some(top.to_path_buf()))
+}
+
+/// Trash directory at the top of a mount: `$topdir/.Trash/$uid` when the
+/// administrator prepared a sticky, non-symlink `.Trash`, else
+/// `$topdir/.Trash-$uid`.
+#[cfg(not(target_os = "macos"))]
+fn topdir_trash(top: &Path) -> io::Result<PathBuf> {
+    let uid = uid().to_string();
+    let shared = top.join(".Trash");
+    if let Ok(meta) = fs::symlink_metadata(&shared) {
+        if meta.is_dir() && meta.mode() & 0o1000 != 0 {
+            if let Ok(dir) = private_dir(&shared.join(&uid)) {
+                return Ok(dir);
+            }
+        }
+    }
+    private_dir(&top.join(format!(".Trash-{}", uid)))
+}
+
+/// Create `dir` with mode 0700 unless it exists, and make sure it is a real
+/// directory owned by us; another user's directory or a symlink planted on
+/// a shared volume is refused.
+fn private_dir(dir: &Path) -> io::Result<PathBuf> {
+    match fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    let meta = fs::symlink_metadata(dir)?;
+    if !meta.is_dir() || meta.uid() != uid() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} gehört nicht dem aktuellen Benutzer", dir.display()),
+        ));
+    }
+    Ok(dir.to_path_buf())
+}
+
+fn uid() -> u32 {
+    // SAFETY: getuid takes no arguments, cannot fail and touches no memory.
+    unsafe { libc::getuid() }
+}
+
+/// `$XDG_DATA_HOME/Trash`, defaulting to `~/.local/share/Trash`.
+#[cfg(not(target_os = "macos"))]
+fn home_trash() -> io::Result<PathBuf> {
+    let data_home = match std::env::var("XDG_DATA_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = std::env::var("HOME").map_err(|_| io::Error::other("HOME nicht gesetzt"))?;
+            PathBuf::from(home).join(".local/share")
+        }
+    };
+    Ok(data_home.join("Trash"))
+}
+
+/// Percent-encode a path for the `Path=` key (RFC 2396 escaping, `/` kept).
+#[cfg(not(target_os = "macos"))]
+fn percent_encode(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut out = String::new();
+    for &b in path.as_os_str().as_bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
@@ -1,16 +1,21 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::app::{
-    DeleteDialog, HostKeyDialog, MkdirDialog, NewProfileForm, PasswordDialog, PermissionFixDialog,
-    ProfileDialog, ProfileDialogMode, RenameDialog, ShellDialog,
+    expand_rename_pattern, EDIT_SIZE_LIMIT, BatchRenameDialog, ChownDialog, GrepDialog, SearchDialog, DeleteDialog, EditConflictDialog, EditSizeDialog, EditUploadDialog, GotoDialog, HostKeyDialog, MkdirDialog, LogDialog, LogEntry, NewProfileForm, PaletteDialog, PasswordDialog, PendingConnect, PermissionFixDialog,
+    CompareStatus, InfoDialog, MarkAgeDialog, MarkPatternDialog, ProfileDialog, ProfileDialogMode, RenameDialog, ShellDialog, SyncDialog, TempCleanupDialog, TouchDialog, UploadAsDialog,
 };
 use crate::config::profiles::AuthMethod;
+use crate::connection::sftp::{host_port, SEARCH_MAX_RESULTS};
+use crate::perms::format_permissions;
+use crate::grep::GREP_MAX_RESULTS;
+use crate::input::TextInput;
+use crate::ui::panels::{format_relative, format_size, format_timestamp};
 use crate::ui::theme::Theme;
 
 /// Render the profile manager dialog centered on the screen.
@@ -38,33 +43,54 @@ pub fn render_profile_dialog(frame: &mut Frame, dialog: &ProfileDialog, theme: &
 
 fn render_list(frame: &mut Frame, dialog: &ProfileDialog, area: Rect, theme: &Theme) {
     let block = Block::default()
-        .title(" Verbindungsprofile (F9) ")
+        .title(format!(" Verbindungsprofile (F9) — Sortierung: {} ", dialog.sort.label()))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.dialog_active_border));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let show_filter = dialog.filtering || !dialog.filter.is_empty();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(if show_filter { 1 } else { 0 }), // filter line
             Constraint::Min(0),    // profile list
             Constraint::Length(1), // hint bar
         ])
         .split(inner);
 
+    if show_filter {
+        let mut spans = vec![
+            Span::styled(" Filter: ", Style::default().fg(theme.text_secondary)),
+            Span::styled(dialog.filter.as_str(), Style::default().fg(theme.text_primary)),
+        ];
+        if dialog.filtering {
+            spans.push(Span::styled("█", Style::default().fg(theme.cursor_bg)));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), chunks[0]);
+    }
+
     // Profile list
     let items: Vec<ListItem> = if dialog.store.profiles.is_empty() {
         vec![ListItem::new(Line::from(Span::styled(
             "  Keine Profile vorhanden. N = Neu anlegen",
             Style::default().fg(theme.text_muted),
         )))]
+    } else if dialog.display_order().is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "  Kein Profil passt zum Filter",
+            Style::default().fg(theme.text_muted),
+        )))]
     } else {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
         dialog
-            .store
-            .profiles
+            .display_order()
             .iter()
-            .enumerate()
+            .filter_map(|&i| dialog.store.profiles.get(i).map(|p| (i, p)))
             .map(|(i, p)| {
                 let active_marker = if dialog.active_profile == Some(i) {
                     "● "
@@ -85,6 +111,10 @@ fn render_list(frame: &mut Frame, dialog: &ProfileDialog, area: Rect, theme: &Th
                         format!("  [{}]", p.auth.as_str()),
                         Style::default().fg(theme.text_muted),
                     ),
+                    Span::styled(
+                        p.last_used.map(|t| format!("  {}", format_relative(t, now))).unwrap_or_default(),
+                        Style::default().fg(theme.text_muted),
+                    ),
                 ]);
                 ListItem::new(line)
             })
@@ -92,25 +122,40 @@ fn render_list(frame: &mut Frame, dialog: &ProfileDialog, area: Rect, theme: &Th
     };
 
     let mut list_state = ListState::default();
-    if !dialog.store.profiles.is_empty() {
+    if !dialog.display_order().is_empty() {
         list_state.select(Some(dialog.list_selected));
     }
 
     let list = List::new(items)
-        .highlight_style(Style::default().bg(theme.highlight_primary_bg).fg(theme.highlight_primary_fg))
+        .highlight_style(theme.fill(theme.highlight_primary_bg, theme.highlight_primary_fg))
         .highlight_symbol("► ");
 
-    frame.render_stateful_widget(list, chunks[0], &mut list_state);
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
     // Hint bar
+    if dialog.filtering {
+        let hints = Line::from(vec![
+            hint_key("↑↓", theme), hint_label(" Auswahl  ", theme),
+            hint_key("Enter", theme), hint_label(" Filter übernehmen  ", theme),
+            hint_key("Esc", theme), hint_label(" Filter löschen", theme),
+        ]);
+        frame.render_widget(Paragraph::new(hints), chunks[2]);
+        return;
+    }
+    let esc_label = if dialog.filter.is_empty() { " Schließen" } else { " Filter löschen" };
     let hints = Line::from(vec![
         hint_key("Enter", theme), hint_label(" Auswählen  ", theme),
+        hint_key("/", theme), hint_label(" Filter  ", theme),
         hint_key("N", theme), hint_label(" Neu  ", theme),
         hint_key("E / F2", theme), hint_label(" Bearbeiten  ", theme),
         hint_key("D", theme), hint_label(" Löschen  ", theme),
-        hint_key("Esc", theme), hint_label(" Schließen", theme),
+        hint_key("C", theme), hint_label(" Duplizieren  ", theme),
+        hint_key("T", theme), hint_label(" Testen  ", theme),
+        hint_key("F", theme), hint_label(" PW vergessen  ", theme),
+        hint_key("S", theme), hint_label(" Sortieren  ", theme),
+        hint_key("Esc", theme), hint_label(esc_label, theme),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[1]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
 }
 
 // ---------------------------------------------------------------------------
@@ -122,6 +167,7 @@ const ALL_FIELDS: &[(usize, &str)] = &[
     (0, "Name"), (1, "Host"), (2, "Port"), (3, "User"),
     (4, "Auth"), (5, "Key-Pfad"), (6, "Remote-Startpfad"),
     (7, "Lokaler Startpfad"), (8, "Passwort speichern"), (9, "Passwort"),
+    (10, "Fokus nach Verbinden"),
 ];
 
 /// Return only the fields that should be visible for the current form state.
@@ -177,6 +223,7 @@ fn render_profile_form(
         match field_idx {
             4 => render_auth_toggle(frame, form, is_active, border_style, label, rows[row_idx], theme),
             8 => render_save_pw_toggle(frame, form, is_active, border_style, label, rows[row_idx], theme),
+            10 => render_focus_toggle(frame, form, is_active, border_style, label, rows[row_idx], theme),
             9 => render_password_field(frame, form, is_active, border_style, rows[row_idx], theme),
             _ => {
                 let value = match field_idx {
@@ -220,6 +267,7 @@ fn render_profile_form(
     let hints = Line::from(vec![
         hint_key("Tab", theme), hint_label(" Nächstes Feld  ", theme),
         hint_key("Enter", theme), hint_label(" Speichern  ", theme),
+        hint_key("F5", theme), hint_label(" Testen  ", theme),
         hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
     frame.render_widget(Paragraph::new(hints), *rows.last().unwrap());
@@ -287,6 +335,37 @@ fn render_save_pw_toggle(
     frame.render_widget(Paragraph::new(toggle_line).block(field_block), area);
 }
 
+/// Render the focus-after-connect toggle (local / remote panel).
+fn render_focus_toggle(
+    frame: &mut Frame, form: &NewProfileForm,
+    is_active: bool, border_style: Style, label: &str, area: Rect,
+    theme: &Theme,
+) {
+    let (local_style, remote_style) = if form.focus_remote {
+        (
+            Style::default().fg(theme.toggle_off),
+            Style::default().fg(theme.toggle_on).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        (
+            Style::default().fg(theme.toggle_on).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.toggle_off),
+        )
+    };
+    let hint = if is_active { "  [Space]" } else { "" };
+    let field_block = Block::default()
+        .title(format!(" {} ", label))
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let toggle_line = Line::from(vec![
+        Span::styled("● Lokal", local_style),
+        Span::raw("   "),
+        Span::styled("● Remote", remote_style),
+        Span::styled(hint, Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(toggle_line).block(field_block), area);
+}
+
 /// Render the masked password input field.
 fn render_password_field(
     frame: &mut Frame, form: &NewProfileForm,
@@ -369,10 +448,7 @@ fn render_confirm_delete(
 fn hint_key(k: &str, theme: &Theme) -> Span<'static> {
     Span::styled(
         format!(" {} ", k),
-        Style::default()
-            .bg(theme.badge_bg)
-            .fg(theme.badge_fg)
-            .add_modifier(Modifier::BOLD),
+        theme.fill(theme.badge_bg, theme.badge_fg).add_modifier(Modifier::BOLD),
     )
 }
 
@@ -380,10 +456,11 @@ fn hint_label(l: &str, theme: &Theme) -> Span<'static> {
     Span::styled(l.to_string(), Style::default().fg(theme.text_secondary))
 }
 
-/// Build a `Line` that shows the text with a block-cursor at `cursor_pos`.
+/// Build a `Line` that shows the text with a block-cursor at the cursor.
 /// Text before the cursor is primary, the cursor character (or a space if at
 /// end) is shown with inverted cursor colours, text after is primary again.
-fn cursor_line<'a>(input: &'a str, cursor_pos: usize, theme: &Theme) -> Line<'a> {
+fn cursor_line<'a>(text: &'a TextInput, theme: &Theme) -> Line<'a> {
+    let (input, cursor_pos) = (text.buf.as_str(), text.cursor_pos);
     let before = &input[..cursor_pos];
 
     // Find the end of the character sitting under the cursor (if any).
@@ -406,14 +483,11 @@ fn cursor_line<'a>(input: &'a str, cursor_pos: usize, theme: &Theme) -> Line<'a>
 
     let cursor_span = if under.is_empty() {
         // Cursor is past the last character — show an empty block
-        Span::styled(" ", Style::default().bg(theme.cursor_bg).fg(theme.cursor_fg))
+        Span::styled(" ", theme.fill(theme.cursor_bg, theme.cursor_fg))
     } else {
         Span::styled(
             under.to_string(),
-            Style::default()
-                .bg(theme.cursor_bg)
-                .fg(theme.cursor_fg)
-                .add_modifier(Modifier::BOLD),
+            theme.fill(theme.cursor_bg, theme.cursor_fg).add_modifier(Modifier::BOLD),
         )
     };
 
@@ -483,9 +557,39 @@ pub fn render_password_dialog(frame: &mut Frame, dlg: &PasswordDialog, theme: &T
         frame.render_widget(Paragraph::new(err_line), chunks[1]);
     }
 
+    if dlg.test_only {
+        let hints = Line::from(vec![
+            hint_key("Enter", theme), hint_label(" Testen  ", theme),
+            hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+        ]);
+        frame.render_widget(Paragraph::new(hints), chunks[3]);
+        return;
+    }
+
+    // Keychain toggle
+    let (ja_style, nein_style) = if dlg.remember {
+        (
+            Style::default().fg(theme.toggle_on).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.toggle_off),
+        )
+    } else {
+        (
+            Style::default().fg(theme.toggle_off),
+            Style::default().fg(theme.toggle_on).add_modifier(Modifier::BOLD),
+        )
+    };
+    let remember_line = Line::from(vec![
+        Span::styled(" Im Keychain speichern: ", Style::default().fg(theme.text_secondary)),
+        Span::styled("● Ja", ja_style),
+        Span::raw("   "),
+        Span::styled("● Nein", nein_style),
+    ]);
+    frame.render_widget(Paragraph::new(remember_line), chunks[2]);
+
     // Hints
     let hints = Line::from(vec![
         hint_key("Enter", theme), hint_label(" Verbinden  ", theme),
+        hint_key("Tab", theme), hint_label(" Speichern ja/nein  ", theme),
         hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
     frame.render_widget(Paragraph::new(hints), chunks[3]);
@@ -495,6 +599,26 @@ pub fn render_password_dialog(frame: &mut Frame, dlg: &PasswordDialog, theme: &T
 // Rename dialog
 // ---------------------------------------------------------------------------
 
+/// Border colour of a name input dialog: red while a validation error is shown.
+fn input_border(has_error: bool, theme: &Theme) -> Color {
+    if has_error {
+        theme.dialog_error_border
+    } else {
+        theme.dialog_warning_border
+    }
+}
+
+/// Draw the validation error of a name input dialog, if any.
+fn render_input_error(frame: &mut Frame, error: Option<&str>, area: Rect, theme: &Theme) {
+    if let Some(err) = error {
+        let err_line = Line::from(Span::styled(
+            format!("✗ {}", err),
+            Style::default().fg(theme.text_danger),
+        ));
+        frame.render_widget(Paragraph::new(err_line), area);
+    }
+}
+
 /// Render the rename input dialog.
 pub fn render_rename_dialog(frame: &mut Frame, dlg: &RenameDialog, theme: &Theme) {
     let area = centered_rect(50, 30, frame.area());
@@ -503,7 +627,7 @@ pub fn render_rename_dialog(frame: &mut Frame, dlg: &RenameDialog, theme: &Theme
     let block = Block::default()
         .title(" Umbenennen ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.dialog_warning_border));
+        .border_style(Style::default().fg(input_border(dlg.error.is_some(), theme)));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -512,6 +636,7 @@ pub fn render_rename_dialog(frame: &mut Frame, dlg: &RenameDialog, theme: &Theme
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // input field
+            Constraint::Length(1), // error
             Constraint::Length(1), // hints
             Constraint::Min(0),
         ])
@@ -521,29 +646,30 @@ pub fn render_rename_dialog(frame: &mut Frame, dlg: &RenameDialog, theme: &Theme
         .title(format!(" {} ", dlg.original))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.dialog_active_border));
-    let input_line = cursor_line(&dlg.input, dlg.cursor_pos, theme);
+    let input_line = cursor_line(&dlg.input, theme);
     frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+    render_input_error(frame, dlg.error.as_deref(), chunks[1], theme);
 
     let hints = Line::from(vec![
         hint_key("Enter", theme), hint_label(" OK  ", theme),
         hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[1]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
 }
 
 // ---------------------------------------------------------------------------
-// Mkdir dialog
+// Upload-as dialog
 // ---------------------------------------------------------------------------
 
-/// Render the mkdir input dialog.
-pub fn render_mkdir_dialog(frame: &mut Frame, dlg: &MkdirDialog, theme: &Theme) {
+/// Render the remote file name prompt for uploading under a different name.
+pub fn render_upload_as_dialog(frame: &mut Frame, dlg: &UploadAsDialog, theme: &Theme) {
     let area = centered_rect(50, 30, frame.area());
     frame.render_widget(Clear, area);
 
     let block = Block::default()
-        .title(" Verzeichnis erstellen ")
+        .title(format!(" Upload als — {} ", dlg.original))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.dialog_warning_border));
+        .border_style(Style::default().fg(theme.dialog_active_border));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -558,50 +684,37 @@ pub fn render_mkdir_dialog(frame: &mut Frame, dlg: &MkdirDialog, theme: &Theme)
         .split(inner);
 
     let input_block = Block::default()
-        .title(" Name ")
+        .title(" Dateiname auf dem Server ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.dialog_active_border));
-    let input_line = cursor_line(&dlg.input, dlg.cursor_pos, theme);
+    let input_line = cursor_line(&dlg.input, theme);
     frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
 
     let hints = Line::from(vec![
-        hint_key("Enter", theme), hint_label(" Erstellen  ", theme),
+        hint_key("Enter", theme), hint_label(" Hochladen  ", theme),
         hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
     frame.render_widget(Paragraph::new(hints), chunks[1]);
 }
 
 // ---------------------------------------------------------------------------
-// Delete confirmation dialog
+// Batch rename dialog
 // ---------------------------------------------------------------------------
 
-/// Render the delete confirmation dialog.
-/// Shows a single entry name or a summary for multiple entries.
-pub fn render_delete_dialog(frame: &mut Frame, dlg: &DeleteDialog, theme: &Theme) {
-    let n = dlg.entries.len();
-
-    // Height: 1 line per entry (max 6 shown) + 2 for padding/title + 1 hint
-    let list_lines = n.min(6) as u16;
-    let height_pct = (25 + list_lines * 3).min(80);
-    let area = centered_rect(55, height_pct, frame.area());
+/// Render the pattern rename dialog with a live preview of the first entries.
+pub fn render_batch_rename_dialog(frame: &mut Frame, dlg: &BatchRenameDialog, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
     frame.render_widget(Clear, area);
 
-    let location = match dlg.side {
-        crate::app::PanelSide::Left => "Lokal",
-        crate::app::PanelSide::Right => "Remote",
-    };
-    let title = if n == 1 {
-        let (_name, is_dir) = &dlg.entries[0];
-        let kind = if *is_dir { "Verzeichnis" } else { "Datei" };
-        format!(" {} {} löschen? ", location, kind)
+    let border = if dlg.error.is_some() {
+        theme.dialog_error_border
     } else {
-        format!(" {} — {} Einträge löschen? ", location, n)
+        theme.dialog_warning_border
     };
-
     let block = Block::default()
-        .title(title)
+        .title(format!(" {} Einträge umbenennen ", dlg.names.len()))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.dialog_error_border));
+        .border_style(Style::default().fg(border));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -609,151 +722,109 @@ pub fn render_delete_dialog(frame: &mut Frame, dlg: &DeleteDialog, theme: &Theme
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),    // entry list
+            Constraint::Length(3), // pattern input
+            Constraint::Length(1), // token help / error
+            Constraint::Min(0),    // preview
             Constraint::Length(1), // hints
         ])
         .split(inner);
 
-    // Build the list of entries to show (cap at 6, add "… and N more" if needed)
-    let mut items: Vec<ListItem> = dlg
-        .entries
+    let input_block = Block::default()
+        .title(" Muster ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    let info = match dlg.error {
+        Some(ref err) => Span::styled(format!("✗ {}", err), Style::default().fg(theme.text_danger)),
+        None => Span::styled(
+            "{n} Zähler  {n:03} mit Nullen  {name} Name  {ext} Endung",
+            Style::default().fg(theme.text_muted),
+        ),
+    };
+    frame.render_widget(Paragraph::new(Line::from(info)), chunks[1]);
+
+    let visible = chunks[2].height as usize;
+    let items: Vec<ListItem> = dlg
+        .names
         .iter()
-        .take(6)
-        .map(|(name, is_dir)| {
-            let icon = if *is_dir { "▶ " } else { "  " };
-            let icon_style = if *is_dir {
-                Style::default().fg(theme.directory_icon).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(theme.file_name)
-            };
+        .enumerate()
+        .take(visible)
+        .map(|(i, old)| {
+            let new = expand_rename_pattern(&dlg.input.buf, old, i + 1)
+                .unwrap_or_else(|_| "?".to_string());
             ListItem::new(Line::from(vec![
-                Span::styled(format!(" {}", icon), icon_style),
-                Span::styled(name.clone(), Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" {}", old), Style::default().fg(theme.text_secondary)),
+                Span::styled("  →  ", Style::default().fg(theme.text_muted)),
+                Span::styled(new, Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
             ]))
         })
         .collect();
-
-    if n > 6 {
-        items.push(ListItem::new(Line::from(Span::styled(
-            format!("  … und {} weitere", n - 6),
-            Style::default().fg(theme.text_muted),
-        ))));
-    }
-
-    frame.render_widget(List::new(items), chunks[0]);
+    frame.render_widget(List::new(items), chunks[2]);
 
     let hints = Line::from(vec![
-        hint_key("Y/Enter", theme), hint_label(" Löschen  ", theme),
-        hint_key("N/Esc", theme), hint_label(" Abbrechen", theme),
+        hint_key("Enter", theme), hint_label(" Umbenennen  ", theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[1]);
+    frame.render_widget(Paragraph::new(hints), chunks[3]);
 }
 
 // ---------------------------------------------------------------------------
-// Help / keyboard shortcut overlay (F1)
+// Mkdir dialog
 // ---------------------------------------------------------------------------
 
-/// All shortcuts shown in the help overlay.
-/// Each entry is (key_label, description).
-const SHORTCUTS: &[(&str, &str)] = &[
-    // Navigation
-    ("↑ / ↓",         "Cursor bewegen"),
-    ("Enter",          "Verzeichnis öffnen / Datei bearbeiten"),
-    ("Backspace",      "Übergeordnetes Verzeichnis"),
-    ("Tab",            "Panel wechseln (lokal ↔ remote)"),
-    ("Ctrl+U / Ctrl+S","Panels tauschen (lokal ↔ remote, nur visuell)"),
-    ("Ctrl+T",          "Theme umschalten (Auto/Dark/Light)"),
-    // Selection
-    ("Leertaste",      "Datei/Verzeichnis markieren"),
-    ("*",              "Alle markieren / alle abwählen"),
-    // File operations
-    ("F2",             "Umbenennen"),
-    ("F4",             "Datei bearbeiten (lokal: $EDITOR / remote: dl→edit→ul)"),
-    ("F5",             "Upload (lokal → remote)"),
-    ("F6",             "Download (remote → lokal)"),
-    ("F7",             "Verzeichnis erstellen"),
-    ("F8",             "Löschen (mit Bestätigung)"),
-    ("!",              "Shell-Befehl im lokalen Verzeichnis ausführen"),
-    // Connection
-    ("F3",             "Verbindung trennen"),
-    ("F9  /  p",       "Verbindungsprofile öffnen"),
-    ("E  /  F2",       "Profil bearbeiten (im Profil-Dialog)"),
-    // App
-    ("F1",             "Diese Hilfe anzeigen / schließen"),
-    ("F10  /  q",      "Beenden"),
-];
-
-pub fn render_help_dialog(frame: &mut Frame, theme: &Theme) {
-    let area = centered_rect(60, 85, frame.area());
+/// Render the mkdir input dialog.
+pub fn render_mkdir_dialog(frame: &mut Frame, dlg: &MkdirDialog, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
     frame.render_widget(Clear, area);
 
     let block = Block::default()
-        .title(" Tastaturkürzel — F1 / Esc zum Schließen ")
+        .title(" Verzeichnis erstellen ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.dialog_active_border));
+        .border_style(Style::default().fg(input_border(dlg.error.is_some(), theme)));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split inner: shortcut list + bottom hint
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // error
+            Constraint::Length(1), // hints
+            Constraint::Min(0),
+        ])
         .split(inner);
 
-    let key_col_w = 16usize;
-
-    let items: Vec<ListItem> = SHORTCUTS
-        .iter()
-        .map(|(key, desc)| {
-            let line = Line::from(vec![
-                Span::styled(
-                    format!(" {:<width$}", key, width = key_col_w),
-                    Style::default()
-                        .fg(theme.dialog_active_border)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    format!(" {}", desc),
-                    Style::default().fg(theme.text_primary),
-                ),
-            ]);
-            ListItem::new(line)
-        })
-        .collect();
-
-    let list = List::new(items);
-    frame.render_widget(list, chunks[0]);
+    let input_block = Block::default()
+        .title(" Name ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+    render_input_error(frame, dlg.error.as_deref(), chunks[1], theme);
 
-    let close_hint = Line::from(vec![
-        hint_key("F1", theme), hint_label(" / ", theme),
-        hint_key("Esc", theme), hint_label(" Schließen", theme),
+    let hints = Line::from(vec![
+        hint_key("Enter", theme), hint_label(" Erstellen  ", theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
-    frame.render_widget(Paragraph::new(close_hint), chunks[1]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
 }
 
 // ---------------------------------------------------------------------------
-// Shell command dialog ('!')
+// Touch dialog
 // ---------------------------------------------------------------------------
 
-pub fn render_shell_dialog(frame: &mut Frame, dlg: &ShellDialog, cwd: &std::path::Path, theme: &Theme) {
-    if dlg.output.is_none() {
-        render_shell_input(frame, dlg, cwd, theme);
-    } else {
-        render_shell_output(frame, dlg, theme);
-    }
-}
-
-fn render_shell_input(frame: &mut Frame, dlg: &ShellDialog, cwd: &std::path::Path, theme: &Theme) {
-    let area = centered_rect(70, 25, frame.area());
+pub fn render_touch_dialog(frame: &mut Frame, dlg: &TouchDialog, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
     frame.render_widget(Clear, area);
 
-    let cwd_str = cwd.to_string_lossy();
-    let title = format!(" Shell  {}  ", cwd_str);
+    let title = if dlg.edit { " Neue Datei bearbeiten " } else { " Leere Datei erstellen " };
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.dialog_warning_border));
+        .border_style(Style::default().fg(input_border(dlg.error.is_some(), theme)));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -761,53 +832,1157 @@ fn render_shell_input(frame: &mut Frame, dlg: &ShellDialog, cwd: &std::path::Pat
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // label
-            Constraint::Length(1), // input
-            Constraint::Length(1), // spacer
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // error
             Constraint::Length(1), // hints
+            Constraint::Min(0),
         ])
         .split(inner);
 
-    frame.render_widget(
-        Paragraph::new(Line::from(Span::styled(
-            " Befehl:",
-            Style::default().fg(theme.shell_label).add_modifier(Modifier::BOLD),
-        ))),
-        chunks[0],
-    );
-
-    // Build input line with cursor block.
-    let before: &str = &dlg.input[..dlg.cursor_pos];
-    let cursor_char = dlg.input[dlg.cursor_pos..]
-        .chars()
-        .next()
-        .map(|c| c.to_string())
-        .unwrap_or_else(|| " ".to_string());
-    let after: &str = if dlg.cursor_pos < dlg.input.len() {
-        let end = dlg.cursor_pos + cursor_char.len();
-        &dlg.input[end..]
-    } else {
-        ""
-    };
-    let input_line = Line::from(vec![
-        Span::styled(" ", Style::default()),
-        Span::styled(before, Style::default().fg(theme.text_primary)),
-        Span::styled(
-            cursor_char,
-            Style::default().bg(theme.shell_cursor_bg).fg(theme.shell_cursor_fg),
-        ),
-        Span::styled(after, Style::default().fg(theme.text_primary)),
-    ]);
-    frame.render_widget(Paragraph::new(input_line), chunks[1]);
+    let input_block = Block::default()
+        .title(" Dateiname ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+    render_input_error(frame, dlg.error.as_deref(), chunks[1], theme);
 
+    let confirm = if dlg.edit { " Erstellen & bearbeiten  " } else { " Erstellen  " };
     let hints = Line::from(vec![
-        hint_key("Enter", theme), hint_label(" Ausführen  ", theme),
+        hint_key("Enter", theme), hint_label(confirm, theme),
         hint_key("Esc", theme), hint_label(" Abbrechen", theme),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[3]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
 }
 
-fn render_shell_output(frame: &mut Frame, dlg: &ShellDialog, theme: &Theme) {
+/// Render the go-to-path prompt (Ctrl+G) with its completion candidates.
+pub fn render_goto_dialog(frame: &mut Frame, dlg: &GotoDialog, theme: &Theme) {
+    let area = centered_rect(60, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let title = match dlg.side {
+        crate::app::PanelSide::Local => " Gehe zu — lokal ",
+        crate::app::PanelSide::Remote => " Gehe zu — remote ",
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(input_border(dlg.error.is_some(), theme)));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // error / completion candidates
+            Constraint::Length(1), // hints
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" Pfad ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    if dlg.error.is_some() {
+        render_input_error(frame, dlg.error.as_deref(), chunks[1], theme);
+    } else if !dlg.candidates.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                format!(" {}", dlg.candidates.join("  ")),
+                Style::default().fg(theme.text_secondary),
+            ))),
+            chunks[1],
+        );
+    }
+
+    let hints = Line::from(vec![
+        hint_key("Enter", theme), hint_label(" Wechseln  ", theme),
+        hint_key("Tab", theme), hint_label(" Ergänzen  ", theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
+}
+
+/// Render the mark-by-pattern dialog ('+' marks, '-' unmarks).
+pub fn render_mark_pattern_dialog(frame: &mut Frame, dlg: &MarkPatternDialog, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let title = if dlg.mark { " Nach Muster markieren " } else { " Nach Muster abwählen " };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // hints
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" Muster (*, ?) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    let action = if dlg.mark { " Markieren  " } else { " Abwählen  " };
+    let hints = Line::from(vec![
+        hint_key("Enter", theme), hint_label(action, theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+/// Render the mark-by-age dialog ('a'); Tab switches between older and newer.
+pub fn render_mark_age_dialog(frame: &mut Frame, dlg: &MarkAgeDialog, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Nach Alter markieren ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(input_border(dlg.error.is_some(), theme)));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // error
+            Constraint::Length(1), // hints
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input_title = if dlg.older {
+        " Älter als (30d, 12h, 2w, JJJJ-MM-TT) "
+    } else {
+        " Neuer als (30d, 12h, 2w, JJJJ-MM-TT) "
+    };
+    let input_block = Block::default()
+        .title(input_title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+    render_input_error(frame, dlg.error.as_deref(), chunks[1], theme);
+
+    let toggle = if dlg.older { " Neuer als  " } else { " Älter als  " };
+    let hints = Line::from(vec![
+        hint_key("Enter", theme), hint_label(" Markieren  ", theme),
+        hint_key("Tab", theme), hint_label(toggle, theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
+}
+
+/// Render the owner dialog: one "uid:gid" input pre-filled with the current owner.
+pub fn render_chown_dialog(frame: &mut Frame, dlg: &ChownDialog, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let title = match dlg.names.as_slice() {
+        [name] => format!(" Besitzer ändern — {} ", name),
+        names => format!(" Besitzer ändern — {} Einträge ", names.len()),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // hints
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" UID:GID (numerisch) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = cursor_line(&dlg.input, theme);
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    let hints = Line::from(vec![
+        hint_key("Enter", theme), hint_label(" Übernehmen  ", theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+/// Render the recursive search: pattern input, progress line and the
+/// result list (paths relative to the search root).
+pub fn render_search_dialog(frame: &mut Frame, dlg: &SearchDialog, theme: &Theme) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Suchen in {} ", dlg.root.display()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // progress
+            Constraint::Min(0),    // results
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" Name oder Muster (*, ?) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = match dlg.handle {
+        None => cursor_line(&dlg.input, theme),
+        Some(_) => Line::from(Span::styled(dlg.input.buf.as_str(), Style::default().fg(theme.text_primary))),
+    };
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    let Some(handle) = dlg.handle.as_ref() else {
+        let hints = Line::from(vec![
+            hint_key("Enter", theme), hint_label(" Suchen  ", theme),
+            hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+        ]);
+        frame.render_widget(Paragraph::new(hints), chunks[3]);
+        return;
+    };
+    let prog = match handle.lock() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let (state, state_style) = match (&prog.error, prog.finished) {
+        (Some(e), _) => (format!("Fehler: {}", e), Style::default().fg(theme.text_danger)),
+        (None, true) => ("fertig".to_string(), Style::default().fg(theme.text_success)),
+        (None, false) => ("läuft…".to_string(), Style::default().fg(theme.text_warning)),
+    };
+    let limit = if prog.matches.len() >= SEARCH_MAX_RESULTS { " (Limit erreicht)" } else { "" };
+    let progress = Line::from(vec![
+        Span::styled(
+            format!(" {} Einträge durchsucht, {} Treffer{} — ", prog.scanned, prog.matches.len(), limit),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(state, state_style.add_modifier(Modifier::BOLD)),
+    ]);
+    frame.render_widget(Paragraph::new(progress), chunks[1]);
+
+    let items: Vec<ListItem> = prog
+        .matches
+        .iter()
+        .map(|path| {
+            let rel = path.strip_prefix(&dlg.root).unwrap_or(path);
+            ListItem::new(Line::from(Span::styled(
+                format!(" {}", rel.display()),
+                Style::default().fg(theme.text_primary),
+            )))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !prog.matches.is_empty() {
+        list_state.select(Some(dlg.selected));
+    }
+    let list = List::new(items)
+        .highlight_style(theme.fill(theme.highlight_primary_bg, theme.highlight_primary_fg))
+        .highlight_symbol("► ");
+    frame.render_stateful_widget(list, chunks[2], &mut list_state);
+
+    let esc_label = if prog.finished { " Schließen" } else { " Abbrechen" };
+    let hints = Line::from(vec![
+        hint_key("↑↓", theme), hint_label(" Auswahl  ", theme),
+        hint_key("Enter", theme), hint_label(" Hinspringen  ", theme),
+        hint_key("Esc", theme), hint_label(esc_label, theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[3]);
+}
+
+/// Render the local content search: pattern input (literal or regex),
+/// progress line and "path:line: text" results.
+pub fn render_grep_dialog(frame: &mut Frame, dlg: &GrepDialog, theme: &Theme) {
+    let area = centered_rect(85, 75, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Inhalt durchsuchen in {} ", dlg.root.display()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // input field
+            Constraint::Length(1), // progress / regex error
+            Constraint::Min(0),    // results
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    let mode = if dlg.regex { " Regex " } else { " Text " };
+    let input_block = Block::default()
+        .title(format!(" Suchbegriff —{}", mode))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    let input_line = match dlg.handle {
+        None => cursor_line(&dlg.input, theme),
+        Some(_) => Line::from(Span::styled(dlg.input.buf.as_str(), Style::default().fg(theme.text_primary))),
+    };
+    frame.render_widget(Paragraph::new(input_line).block(input_block), chunks[0]);
+
+    let Some(handle) = dlg.handle.as_ref() else {
+        if let Some(ref err) = dlg.error {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(" Ungültiger Ausdruck: {}", err.lines().last().unwrap_or_default()),
+                    Style::default().fg(theme.text_danger),
+                )),
+                chunks[1],
+            );
+        }
+        let hints = Line::from(vec![
+            hint_key("Enter", theme), hint_label(" Suchen  ", theme),
+            hint_key("Tab", theme), hint_label(" Text/Regex  ", theme),
+            hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+        ]);
+        frame.render_widget(Paragraph::new(hints), chunks[3]);
+        return;
+    };
+    let prog = match handle.lock() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let (state, state_style) = if prog.finished {
+        ("fertig", Style::default().fg(theme.text_success))
+    } else {
+        ("läuft…", Style::default().fg(theme.text_warning))
+    };
+    let limit = if prog.hits.len() >= GREP_MAX_RESULTS { " (Limit erreicht)" } else { "" };
+    let progress = Line::from(vec![
+        Span::styled(
+            format!(" {} Dateien durchsucht, {} Treffer{} — ", prog.files_scanned, prog.hits.len(), limit),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(state, state_style.add_modifier(Modifier::BOLD)),
+    ]);
+    frame.render_widget(Paragraph::new(progress), chunks[1]);
+
+    let items: Vec<ListItem> = prog
+        .hits
+        .iter()
+        .map(|hit| {
+            let rel = hit.path.strip_prefix(&dlg.root).unwrap_or(&hit.path);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {}:{}: ", rel.display(), hit.line),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(hit.text.as_str(), Style::default().fg(theme.text_primary)),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !prog.hits.is_empty() {
+        list_state.select(Some(dlg.selected));
+    }
+    let list = List::new(items)
+        .highlight_style(theme.fill(theme.highlight_primary_bg, theme.highlight_primary_fg))
+        .highlight_symbol("► ");
+    frame.render_stateful_widget(list, chunks[2], &mut list_state);
+
+    let esc_label = if prog.finished { " Schließen" } else { " Abbrechen" };
+    let hints = Line::from(vec![
+        hint_key("↑↓", theme), hint_label(" Auswahl  ", theme),
+        hint_key("Enter", theme), hint_label(" Datei anwählen  ", theme),
+        hint_key("Esc", theme), hint_label(esc_label, theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[3]);
+}
+
+// ---------------------------------------------------------------------------
+// Status log ('l')
+// ---------------------------------------------------------------------------
+
+pub fn render_log_dialog(
+    frame: &mut Frame,
+    dlg: &LogDialog,
+    log: &std::collections::VecDeque<LogEntry>,
+    theme: &Theme,
+) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Meldungsverlauf ({}) ", log.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    if log.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(" Noch keine Meldungen", Style::default().fg(theme.text_muted))),
+            chunks[0],
+        );
+    } else {
+        let items: Vec<ListItem> = log
+            .iter()
+            .map(|entry| {
+                let text_style = if entry.is_error {
+                    Style::default().fg(theme.text_danger)
+                } else {
+                    Style::default().fg(theme.text_primary)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", &format_timestamp(entry.time)[11..]),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::styled(entry.text.as_str(), text_style),
+                ]))
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(dlg.selected));
+        let list = List::new(items)
+            .highlight_style(theme.fill(theme.highlight_primary_bg, theme.highlight_primary_fg))
+            .highlight_symbol("► ");
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+    }
+
+    let hints = Line::from(vec![
+        hint_key("↑↓ / PgUp PgDn", theme), hint_label(" Blättern  ", theme),
+        hint_key("Esc", theme), hint_label(" Schließen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+// ---------------------------------------------------------------------------
+// Command palette (Ctrl+P)
+// ---------------------------------------------------------------------------
+
+pub fn render_palette_dialog(frame: &mut Frame, dlg: &PaletteDialog, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Befehle ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // filter input
+            Constraint::Min(0),    // command list
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" Filter ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+    frame.render_widget(
+        Paragraph::new(cursor_line(&dlg.input, theme)).block(input_block),
+        chunks[0],
+    );
+
+    let matches = dlg.matches();
+    let label_w = matches.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|(label, key, _)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", label, width = label_w),
+                    Style::default().fg(theme.text_primary),
+                ),
+                Span::styled(
+                    format!("  {}", key),
+                    Style::default()
+                        .fg(theme.dialog_active_border)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]))
+        })
+        .collect();
+    if items.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(" Kein passender Befehl", Style::default().fg(theme.text_secondary))),
+            chunks[1],
+        );
+    } else {
+        let mut list_state = ListState::default();
+        list_state.select(Some(dlg.selected));
+        let list = List::new(items)
+            .highlight_style(theme.fill(theme.highlight_primary_bg, theme.highlight_primary_fg))
+            .highlight_symbol("► ");
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    let hints = Line::from(vec![
+        hint_key("↑↓", theme), hint_label(" Auswahl  ", theme),
+        hint_key("Enter", theme), hint_label(" Ausführen  ", theme),
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
+}
+
+// ---------------------------------------------------------------------------
+// Temp file cleanup dialog
+// ---------------------------------------------------------------------------
+
+/// Render the temp file cleanup overlay: leftover edit copies with their sizes.
+pub fn render_temp_cleanup_dialog(frame: &mut Frame, dlg: &TempCleanupDialog, theme: &Theme) {
+    let area = centered_rect(55, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " Temp-Dateien aufräumen — {} ",
+            format_size(dlg.total_size()).trim_start()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let name_w = (chunks[0].width as usize).saturating_sub(14);
+    let items: Vec<ListItem> = dlg
+        .entries
+        .iter()
+        .map(|(name, size)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", name, width = name_w),
+                    Style::default().fg(theme.text_primary),
+                ),
+                Span::styled(format_size(*size), Style::default().fg(theme.text_secondary)),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[0]);
+
+    let hints = Line::from(vec![
+        hint_key("Y/Enter", theme), hint_label(" Alle löschen  ", theme),
+        hint_key("N/Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+// ---------------------------------------------------------------------------
+// Sync preview dialog
+// ---------------------------------------------------------------------------
+
+/// Render the sync preview ('m'): every planned upload with its reason and
+/// the remote extras, which are only deleted when 'd' switched that on.
+pub fn render_sync_dialog(frame: &mut Frame, dlg: &SyncDialog, theme: &Theme) {
+    let area = centered_rect(65, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Sync lokal → remote — Vorschau ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // plan
+            Constraint::Length(1), // summary
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    let mut items: Vec<ListItem> = dlg
+        .plan
+        .upload
+        .iter()
+        .map(|(entry, status)| {
+            let reason = match status {
+                CompareStatus::OnlyHere => "neu",
+                CompareStatus::Newer => "neuer",
+                _ => "Größe abweichend",
+            };
+            let suffix = if entry.is_dir { "/" } else { "" };
+            ListItem::new(Line::from(vec![
+                Span::styled(" ↑ ", Style::default().fg(theme.text_success)),
+                Span::styled(
+                    format!("{}{}", entry.name, suffix),
+                    Style::default().fg(theme.text_primary),
+                ),
+                Span::styled(format!("  ({})", reason), Style::default().fg(theme.text_muted)),
+            ]))
+        })
+        .collect();
+    items.extend(dlg.plan.extra.iter().map(|(name, is_dir)| {
+        let suffix = if *is_dir { "/" } else { "" };
+        let (marker, style, note) = if dlg.delete_extra {
+            (" ✗ ", Style::default().fg(theme.text_danger), "  (wird gelöscht)")
+        } else {
+            (" · ", Style::default().fg(theme.text_muted), "  (nur remote, bleibt)")
+        };
+        ListItem::new(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(format!("{}{}", name, suffix), style),
+            Span::styled(note, Style::default().fg(theme.text_muted)),
+        ]))
+    }));
+    let mut state = ListState::default();
+    state.select(Some(dlg.selected));
+    frame.render_stateful_widget(
+        List::new(items).highlight_style(theme.fill(theme.cursor_bg, theme.cursor_fg)),
+        chunks[0],
+        &mut state,
+    );
+
+    let deletes = if dlg.delete_extra { dlg.plan.extra.len() } else { 0 };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(" {} hochladen, {} löschen", dlg.plan.upload.len(), deletes),
+            Style::default().fg(theme.text_warning).add_modifier(Modifier::BOLD),
+        ))),
+        chunks[1],
+    );
+
+    let delete_label = if dlg.delete_extra { " Nicht löschen  " } else { " Überzählige löschen  " };
+    let mut hints = vec![
+        hint_key("Y/Enter", theme), hint_label(" Ausführen  ", theme),
+    ];
+    if !dlg.plan.extra.is_empty() {
+        hints.extend([hint_key("d", theme), hint_label(delete_label, theme)]);
+    }
+    hints.extend([hint_key("N/Esc", theme), hint_label(" Abbrechen", theme)]);
+    frame.render_widget(Paragraph::new(Line::from(hints)), chunks[2]);
+}
+
+// ---------------------------------------------------------------------------
+// Info dialog
+// ---------------------------------------------------------------------------
+
+/// Render the metadata popup for the highlighted entry ('i').
+pub fn render_info_dialog(frame: &mut Frame, dlg: &InfoDialog, theme: &Theme) {
+    let kind = match (dlg.is_symlink, dlg.is_dir) {
+        (true, true) => "Symlink → Verzeichnis",
+        (true, false) => "Symlink → Datei",
+        (false, true) => "Verzeichnis",
+        (false, false) => "Datei",
+    };
+    let unknown = || "—".to_string();
+    let mut rows: Vec<(&str, String)> = vec![
+        ("Pfad", dlg.path.clone()),
+        ("Typ", kind.to_string()),
+    ];
+    if dlg.is_symlink {
+        rows.push(("Ziel", dlg.link_target.clone().unwrap_or_else(unknown)));
+    }
+    rows.extend([
+        ("Größe", dlg.size.map(|s| format!("{} Bytes", s)).unwrap_or_else(unknown)),
+        ("Geändert", dlg.mtime.map(format_timestamp).unwrap_or_else(unknown)),
+        (
+            "Rechte",
+            dlg.mode
+                .map(|m| format!("{} ({:04o})", format_permissions(m), m))
+                .unwrap_or_else(unknown),
+        ),
+        ("UID", dlg.uid.map(|u| u.to_string()).unwrap_or_else(unknown)),
+        ("GID", dlg.gid.map(|g| g.to_string()).unwrap_or_else(unknown)),
+    ]);
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!(" {:<10}", label), Style::default().fg(theme.text_secondary)),
+                Span::styled(value, Style::default().fg(theme.text_primary)),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![hint_key("Esc/Enter", theme), hint_label(" Schließen", theme)]));
+
+    let block = Block::default()
+        .title(" Details ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let para = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .block(block);
+
+    let area = centered_rect(60, 40, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(para, area);
+}
+
+// ---------------------------------------------------------------------------
+// Delete confirmation dialog
+// ---------------------------------------------------------------------------
+
+/// Render the delete confirmation dialog.
+/// Shows a single entry name or a summary for multiple entries.
+pub fn render_delete_dialog(frame: &mut Frame, dlg: &DeleteDialog, theme: &Theme) {
+    let n = dlg.entries.len();
+
+    // Height: 1 line per entry (max 6 shown) + 2 for padding/title + 1 hint
+    // (+1 for the recursive count line)
+    let count_line = dlg.summary.is_some() || dlg.counting.is_some();
+    let list_lines = n.min(6) as u16 + u16::from(count_line);
+    let height_pct = (25 + list_lines * 3).min(80);
+    let area = centered_rect(55, height_pct, frame.area());
+    frame.render_widget(Clear, area);
+
+    let location = match dlg.side {
+        crate::app::PanelSide::Local => "Lokal",
+        crate::app::PanelSide::Remote => "Remote",
+    };
+    let verb = if dlg.trash { "in den Papierkorb" } else { "löschen" };
+    let title = if n == 1 {
+        let (_name, is_dir) = &dlg.entries[0];
+        let kind = if *is_dir { "Verzeichnis" } else { "Datei" };
+        format!(" {} {} {}? ", location, kind, verb)
+    } else {
+        format!(" {} — {} Einträge {}? ", location, n, verb)
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_error_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // entry list
+            Constraint::Length(u16::from(count_line)), // recursive count
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    // Build the list of entries to show (cap at 6, add "… and N more" if needed)
+    let mut items: Vec<ListItem> = dlg
+        .entries
+        .iter()
+        .take(6)
+        .map(|(name, is_dir)| {
+            let icon = if *is_dir { "▶ " } else { "  " };
+            let icon_style = if *is_dir {
+                Style::default().fg(theme.directory_icon).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.file_name)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {}", icon), icon_style),
+                Span::styled(name.clone(), Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            ]))
+        })
+        .collect();
+
+    if n > 6 {
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!("  … und {} weitere", n - 6),
+            Style::default().fg(theme.text_muted),
+        ))));
+    }
+
+    frame.render_widget(List::new(items), chunks[0]);
+
+    if let Some(count) = dlg.summary {
+        let prefix = if count.truncated { "mehr als " } else { "" };
+        let text = format!(
+            " {}{} Dateien, {} Ordner werden {}",
+            prefix,
+            count.files,
+            count.dirs,
+            if dlg.trash { "verschoben" } else { "gelöscht" }
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                text,
+                Style::default().fg(theme.text_danger).add_modifier(Modifier::BOLD),
+            ))),
+            chunks[1],
+        );
+    } else if dlg.counting.is_some() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(" zähle…", Style::default().fg(theme.text_muted)))),
+            chunks[1],
+        );
+    }
+
+    let hints = Line::from(vec![
+        hint_key("Y/Enter", theme),
+        hint_label(if dlg.trash { " In Papierkorb  " } else { " Löschen  " }, theme),
+        hint_key("N/Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[2]);
+}
+
+// ---------------------------------------------------------------------------
+// Quit confirmation (q / F10 during a transfer)
+// ---------------------------------------------------------------------------
+
+pub fn render_quit_dialog(frame: &mut Frame, theme: &Theme) {
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Vela beenden? ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_error_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let msg = Paragraph::new(Text::from(vec![
+        Line::from(Span::styled(
+            " Eine Übertragung läuft noch.",
+            Style::default().fg(theme.text_danger).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            " Beenden bricht sie ab; teilweise übertragene Dateien bleiben unvollständig.",
+            Style::default().fg(theme.text_primary),
+        )),
+    ]))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(msg, chunks[0]);
+
+    let hints = Line::from(vec![
+        hint_key("Y/Enter", theme), hint_label(" Beenden  ", theme),
+        hint_key("N/Esc", theme), hint_label(" Weiter übertragen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+// ---------------------------------------------------------------------------
+// Connecting overlay
+// ---------------------------------------------------------------------------
+
+/// Render the spinner box shown while a connect runs in the background.
+pub fn render_connect_dialog(frame: &mut Frame, pending: &PendingConnect, theme: &Theme) {
+    let area = centered_rect(50, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Verbinden ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let msg = Paragraph::new(Text::from(vec![
+        Line::from(Span::styled(
+            format!(" {} Verbinde mit {}…", pending.spinner(), pending.target()),
+            Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!(" {} s", pending.elapsed_secs()),
+            Style::default().fg(theme.text_muted),
+        )),
+    ]))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(msg, chunks[0]);
+
+    let hints = Line::from(vec![
+        hint_key("Esc", theme), hint_label(" Abbrechen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(hints), chunks[1]);
+}
+
+// ---------------------------------------------------------------------------
+// Help / keyboard shortcut overlay (F1)
+// ---------------------------------------------------------------------------
+
+/// All shortcuts shown in the help overlay.
+/// Each entry is (key_label, description).
+const SHORTCUTS: &[(&str, &str)] = &[
+    // Navigation
+    ("↑ / ↓",         "Cursor bewegen"),
+    ("PgUp / PgDn",    "Seitenweise blättern"),
+    ("Pos1 / Ende",    "Erster / letzter Eintrag"),
+    ("Enter",          "Verzeichnis öffnen / Datei bearbeiten; getrennt: Profil verbinden"),
+    ("Backspace",      "Übergeordnetes Verzeichnis"),
+    ("Tab",            "Panel wechseln (lokal ↔ remote)"),
+    ("Alt+Buchstaben", "Zum ersten passenden Eintrag springen"),
+    ("Maus",           "Klick: auswählen, Doppelklick: öffnen, Rad: blättern"),
+    ("Ctrl+U / Ctrl+S","Panels tauschen (lokal ↔ remote, nur visuell)"),
+    ("Ctrl+W",         "Einzelpanel in voller Breite ein/aus"),
+    (".",              "Versteckte Dateien ein-/ausblenden"),
+    ("Ctrl+R",         "Aktives Panel neu einlesen"),
+    ("Ctrl+G",         "Gehe zu Pfad (Tab ergänzt Verzeichnisse)"),
+    ("Alt+← / Alt+→",  "Zurück / vorwärts im Verzeichnisverlauf"),
+    ("=",              "Panels vergleichen (+ nur hier, > neuer, < älter, = gleich)"),
+    ("m",              "Sync lokal → remote (Vorschau, d: Überzählige löschen)"),
+    ("Ctrl+Z",         "Größen: gerundet / exakte Bytes"),
+    ("Ctrl+O",         "Rechte-Spalte im lokalen Panel ein/aus"),
+    ("Ctrl+D",         "Datum: absolut / relativ"),
+    ("Ctrl+T",          "Theme umschalten (Auto/Dark/Light/Mono/Kontrast/eigene)"),
+    ("Ctrl+P",         "Befehlspalette (alle Aktionen, filterbar)"),
+    // Selection
+    ("Leertaste",      "Datei/Verzeichnis markieren"),
+    ("*",              "Alle markieren / alle abwählen"),
+    ("+ / -",          "Nach Muster markieren / abwählen"),
+    ("a",              "Nach Alter markieren (30d, 2w, JJJJ-MM-TT)"),
+    ("#",              "Markierung umkehren"),
+    // File operations
+    ("F2",             "Umbenennen (markiert: per Muster, z.B. IMG_{n:03}.{ext})"),
+    ("F4",             "Datei bearbeiten (lokal: $EDITOR / remote: dl→edit→ul)"),
+    ("Shift+F4",       "Neue Datei erstellen und bearbeiten"),
+    ("F5",             "Upload (lokal → remote)"),
+    ("u",              "Einzelne Datei unter anderem Namen hochladen"),
+    ("F6",             "Download (remote → lokal)"),
+    ("F7",             "Verzeichnis erstellen (auch a/b/c)"),
+    ("n",              "Leere Datei erstellen"),
+    ("o",              "Remote: Besitzer ändern (UID:GID)"),
+    ("F8",             "Löschen (mit Bestätigung)"),
+    ("/",              "Remote: rekursiv nach Namen suchen (Teilstring oder *, ?)"),
+    ("g",              "Dateiinhalte im lokalen Verzeichnis durchsuchen (Text/Regex)"),
+    ("x / y / v",      "Remote: ausschneiden / kopieren / ins aktuelle Verzeichnis einfügen"),
+    ("!",              "Shell-Befehl im aktuellen Verzeichnis (lokal / remote)"),
+    ("Tab",            "Shell-Eingabe: lokalen Pfad ergänzen"),
+    ("w",              "Shell-Ausgabe: Zeilenumbruch an/aus"),
+    ("s",              "Interaktive Remote-Shell (ssh) im aktuellen Verzeichnis"),
+    ("c",              "Verwaiste Temp-Dateien aufräumen"),
+    ("i",              "Details zum Eintrag (Pfad, Größe, Rechte, Besitzer)"),
+    ("l",              "Meldungsverlauf (alle Statusmeldungen, Fehler rot)"),
+    // Connection
+    ("F3",             "Verbindung trennen"),
+    ("Shift+F3",       "Mit letztem Profil neu verbinden"),
+    ("F9  /  p",       "Verbindungsprofile öffnen"),
+    ("E  /  F2",       "Profil bearbeiten (im Profil-Dialog)"),
+    ("C",              "Profil duplizieren (im Profil-Dialog)"),
+    ("/",              "Profile nach Name/Host filtern (im Profil-Dialog)"),
+    ("Shift+↑ / ↓",    "Profil verschieben (im Profil-Dialog, Sortierung 'Reihenfolge')"),
+    ("T  /  F5",       "Verbindung testen (Profil-Liste / Formular)"),
+    ("F",              "Gespeichertes Passwort vergessen (im Profil-Dialog)"),
+    // App
+    ("F1",             "Diese Hilfe anzeigen / schließen"),
+    ("F10  /  q",      "Beenden (fragt nach, solange eine Übertragung läuft)"),
+];
+
+pub fn render_help_dialog(frame: &mut Frame, theme: &Theme) {
+    let area = centered_rect(60, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Tastaturkürzel — F1 / Esc zum Schließen ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_active_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Split inner: shortcut list + bottom hint
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let key_col_w = 16usize;
+
+    let items: Vec<ListItem> = SHORTCUTS
+        .iter()
+        .map(|(key, desc)| {
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", key, width = key_col_w),
+                    Style::default()
+                        .fg(theme.dialog_active_border)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {}", desc),
+                    Style::default().fg(theme.text_primary),
+                ),
+            ]);
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items);
+    frame.render_widget(list, chunks[0]);
+
+    let close_hint = Line::from(vec![
+        hint_key("F1", theme), hint_label(" / ", theme),
+        hint_key("Esc", theme), hint_label(" Schließen", theme),
+    ]);
+    frame.render_widget(Paragraph::new(close_hint), chunks[1]);
+}
+
+// ---------------------------------------------------------------------------
+// Shell command dialog ('!')
+// ---------------------------------------------------------------------------
+
+pub fn render_shell_dialog(frame: &mut Frame, dlg: &ShellDialog, cwd: &std::path::Path, theme: &Theme) {
+    if dlg.output.is_none() {
+        render_shell_input(frame, dlg, cwd, theme);
+    } else {
+        render_shell_output(frame, dlg, theme);
+    }
+}
+
+fn render_shell_input(frame: &mut Frame, dlg: &ShellDialog, cwd: &std::path::Path, theme: &Theme) {
+    let area = centered_rect(70, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let cwd_str = cwd.to_string_lossy();
+    let place = if dlg.remote { "remote" } else { "lokal" };
+    let title = format!(" Shell ({})  {}  ", place, cwd_str);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // label
+            Constraint::Length(1), // input
+            Constraint::Length(1), // spacer
+            Constraint::Length(1), // hints
+        ])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            " Befehl:",
+            Style::default().fg(theme.shell_label).add_modifier(Modifier::BOLD),
+        ))),
+        chunks[0],
+    );
+
+    // Build input line with cursor block.
+    let (input, cursor_pos) = (dlg.input.buf.as_str(), dlg.input.cursor_pos);
+    let before: &str = &input[..cursor_pos];
+    let cursor_char = input[cursor_pos..]
+        .chars()
+        .next()
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| " ".to_string());
+    let after: &str = if cursor_pos < input.len() {
+        let end = cursor_pos + cursor_char.len();
+        &input[end..]
+    } else {
+        ""
+    };
+    let input_line = Line::from(vec![
+        Span::styled(" ", Style::default()),
+        Span::styled(before, Style::default().fg(theme.text_primary)),
+        Span::styled(
+            cursor_char,
+            theme.fill(theme.shell_cursor_bg, theme.shell_cursor_fg),
+        ),
+        Span::styled(after, Style::default().fg(theme.text_primary)),
+    ]);
+    frame.render_widget(Paragraph::new(input_line), chunks[1]);
+
+    if dlg.running.is_some() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                " läuft auf dem Server…",
+                Style::default().fg(theme.text_secondary),
+            ))),
+            chunks[2],
+        );
+        let hints = Line::from(vec![hint_key("Esc", theme), hint_label(" Abbrechen", theme)]);
+        frame.render_widget(Paragraph::new(hints), chunks[3]);
+        return;
+    }
+
+    // Candidates of an ambiguous Tab completion, on the spacer line.
+    if !dlg.candidates.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                format!(" {}", dlg.candidates.join("  ")),
+                Style::default().fg(theme.text_secondary),
+            ))),
+            chunks[2],
+        );
+    }
+
+    let mut hints = vec![
+        hint_key("Enter", theme), hint_label(" Ausführen  ", theme),
+    ];
+    if !dlg.remote {
+        hints.extend([hint_key("Tab", theme), hint_label(" Pfad ergänzen  ", theme)]);
+    }
+    hints.extend([hint_key("Esc", theme), hint_label(" Abbrechen", theme)]);
+    let hints = Line::from(hints);
+    frame.render_widget(Paragraph::new(hints), chunks[3]);
+}
+
+/// Output lines that fit in the shell output popup on a `screen`-sized
+/// terminal: the popup minus its borders and the hint line.
+pub fn shell_output_lines(screen: Rect) -> usize {
+    (centered_rect(85, 75, screen).height as usize).saturating_sub(3)
+}
+
+fn render_shell_output(frame: &mut Frame, dlg: &ShellDialog, theme: &Theme) {
     let area = centered_rect(85, 75, frame.area());
     frame.render_widget(Clear, area);
 
@@ -842,14 +2017,21 @@ fn render_shell_output(frame: &mut Frame, dlg: &ShellDialog, theme: &Theme) {
         .map(|l| Line::from(Span::styled(l.as_str(), Style::default().fg(theme.text_primary))))
         .collect();
 
-    let output_para = Paragraph::new(lines)
+    let mut output_para = Paragraph::new(lines)
         .style(Style::default().bg(theme.shell_output_bg))
         .scroll((dlg.scroll as u16, 0));
+    if dlg.wrap {
+        output_para = output_para.wrap(Wrap { trim: false });
+    }
+    dlg.view_height.set(chunks[0].height as usize);
+    dlg.content_rows.set(output_para.line_count(chunks[0].width));
     frame.render_widget(output_para, chunks[0]);
 
+    let wrap_label = if dlg.wrap { " Umbruch aus  " } else { " Umbruch an  " };
     let hints = Line::from(vec![
         hint_key("↑↓", theme), hint_label(" Scrollen  ", theme),
         hint_key("PgUp/PgDn", theme), hint_label(" Seite  ", theme),
+        hint_key("w", theme), hint_label(wrap_label, theme),
         hint_key("Esc", theme), hint_label(" Schließen", theme),
     ]);
     frame.render_widget(Paragraph::new(hints), chunks[1]);
@@ -941,6 +2123,155 @@ pub fn render_permission_dialog(frame: &mut Frame, dlg: &PermissionFixDialog, th
 // Unknown host key dialog
 // ---------------------------------------------------------------------------
 
+/// Render the overwrite confirmation shown when a remotely edited file was
+/// changed on the server while the editor was open.
+pub fn render_edit_conflict_dialog(frame: &mut Frame, dlg: &EditConflictDialog, theme: &Theme) {
+    let (headline, explanation) = if dlg.unverified {
+        (
+            "⚠   Server-Datei nicht prüfbar   ⚠",
+            "Die Datei konnte auf dem Server nicht abgefragt werden (gelöscht oder nicht erreichbar). ",
+        )
+    } else {
+        (
+            "⚠   Datei wurde auf dem Server geändert   ⚠",
+            "Während der Bearbeitung hat jemand die Datei gespeichert. ",
+        )
+    };
+    let message_lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                headline,
+                Style::default().fg(theme.text_warning).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Datei: "),
+            Span::styled(dlg.path(), Style::default().fg(theme.dialog_active_border)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(explanation),
+            Span::raw("Ein Upload "),
+            Span::styled("überschreibt", Style::default().fg(theme.text_danger).add_modifier(Modifier::BOLD)),
+            Span::raw(if dlg.unverified { " mögliche Änderungen." } else { " diese Änderungen." }),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Y / Enter", Style::default().fg(theme.text_danger).add_modifier(Modifier::BOLD)),
+            Span::raw(" — Trotzdem hochladen"),
+        ]),
+        Line::from(vec![
+            Span::styled("N / Esc", Style::default().fg(theme.text_success).add_modifier(Modifier::BOLD)),
+            Span::raw("   — Abbrechen, lokale Kopie behalten"),
+        ]),
+    ];
+
+    let block = Block::default()
+        .title(" Bearbeitungskonflikt ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_error_border));
+
+    let para = Paragraph::new(Text::from(message_lines))
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .alignment(Alignment::Left);
+
+    let area = centered_rect(60, 45, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(para, area);
+}
+
+/// Render the upload confirmation after a remote edit (`confirm_edit_upload`).
+pub fn render_edit_upload_dialog(frame: &mut Frame, dlg: &EditUploadDialog, theme: &Theme) {
+    let message_lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                "Änderungen hochladen?",
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Datei: "),
+            Span::styled(dlg.path(), Style::default().fg(theme.dialog_active_border)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Y / Enter", Style::default().fg(theme.text_warning).add_modifier(Modifier::BOLD)),
+            Span::raw(" — Hochladen"),
+        ]),
+        Line::from(vec![
+            Span::styled("N / Esc", Style::default().fg(theme.text_success).add_modifier(Modifier::BOLD)),
+            Span::raw("   — Nicht hochladen, lokale Kopie behalten"),
+        ]),
+    ];
+
+    let block = Block::default()
+        .title(" Bearbeitung beendet ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let para = Paragraph::new(Text::from(message_lines))
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .alignment(Alignment::Left);
+
+    let area = centered_rect(60, 35, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(para, area);
+}
+
+/// Render the warning shown before F4 downloads a large remote file.
+pub fn render_edit_size_dialog(frame: &mut Frame, dlg: &EditSizeDialog, theme: &Theme) {
+    let message_lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                "⚠   Große Datei   ⚠",
+                Style::default().fg(theme.text_warning).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Datei: "),
+            Span::styled(dlg.name.clone(), Style::default().fg(theme.dialog_active_border)),
+            Span::raw(format!("  ({})", format_size(dlg.size).trim())),
+        ]),
+        Line::from(""),
+        Line::from(format!(
+            "Die Datei ist größer als {} und wird vor dem Bearbeiten vollständig \
+             heruntergeladen. Vela reagiert währenddessen nicht.",
+            format_size(EDIT_SIZE_LIMIT).trim()
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Y / Enter", Style::default().fg(theme.text_warning).add_modifier(Modifier::BOLD)),
+            Span::raw(" — Trotzdem bearbeiten"),
+        ]),
+        Line::from(vec![
+            Span::styled("N / Esc", Style::default().fg(theme.text_success).add_modifier(Modifier::BOLD)),
+            Span::raw("   — Abbrechen"),
+        ]),
+    ];
+
+    let block = Block::default()
+        .title(" Bearbeiten ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_warning_border));
+
+    let para = Paragraph::new(Text::from(message_lines))
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .alignment(Alignment::Left);
+
+    let area = centered_rect(60, 45, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(para, area);
+}
+
 pub fn render_host_key_dialog(frame: &mut Frame, dlg: &HostKeyDialog, theme: &Theme) {
     let message_lines: Vec<Line> = vec![
         Line::from(vec![
@@ -953,7 +2284,7 @@ pub fn render_host_key_dialog(frame: &mut Frame, dlg: &HostKeyDialog, theme: &Th
         Line::from(vec![
             Span::raw("Host:        "),
             Span::styled(
-                format!("{}:{}", dlg.host, dlg.port),
+                host_port(&dlg.host, dlg.port),
                 Style::default().fg(theme.dialog_active_border),
             ),
         ]),
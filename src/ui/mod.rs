@@ -5,39 +5,75 @@ pub mod theme;
 
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Position, Rect},
 };
 
-use crate::app::App;
+use crate::app::{ActivePanel, App};
+use crate::transfer::queue::TransferHandle;
 use dialogs::{
-    render_delete_dialog, render_help_dialog, render_host_key_dialog, render_mkdir_dialog,
-    render_password_dialog, render_permission_dialog, render_profile_dialog, render_rename_dialog,
-    render_shell_dialog,
+    render_batch_rename_dialog, render_chown_dialog, render_delete_dialog, render_edit_conflict_dialog, render_edit_size_dialog, render_edit_upload_dialog, render_goto_dialog, render_grep_dialog, render_help_dialog, render_host_key_dialog, render_info_dialog, render_mark_age_dialog, render_mark_pattern_dialog, render_mkdir_dialog,
+    render_connect_dialog, render_log_dialog, render_palette_dialog, render_password_dialog, render_quit_dialog, render_permission_dialog, render_profile_dialog, render_rename_dialog, render_search_dialog,
+    render_shell_dialog, render_sync_dialog, render_temp_cleanup_dialog, render_touch_dialog, render_upload_as_dialog,
 };
-use panels::render_panels;
-use statusbar::render_statusbar;
+use panels::{panel_areas, render_entry_detail, render_panels, row_at};
+use statusbar::{render_statusbar, LinkState, TransferKind};
+use theme::Theme;
 
-/// Top-level render function called each frame.
-pub fn render(frame: &mut Frame, app: &App) {
-    let theme = app.theme_choice.resolve();
-
-    let chunks = Layout::default()
+/// Split the screen into the panel area, the entry detail line and the
+/// status bar.
+fn main_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(0),    // panels take remaining space
+            Constraint::Length(1), // full name of the highlighted entry
             Constraint::Length(2), // status bar (2 lines: gauge + file info)
         ])
-        .split(frame.area());
+        .split(area)
+}
+
+/// Map a mouse position on a `screen`-sized terminal to the logical panel
+/// under it and the visible row position that was hit (None on the border or
+/// below the last entry). Returns None outside both panels.
+pub fn hit_test(app: &App, screen: Rect, column: u16, row: u16) -> Option<(ActivePanel, Option<usize>)> {
+    let (local_area, remote_area) = panel_areas(app, main_layout(screen)[0]);
+    let pos = Position { x: column, y: row };
+    let (side, area) = if local_area.contains(pos) {
+        (app.remote_side.toggle(), local_area)
+    } else if remote_area.contains(pos) {
+        (app.remote_side, remote_area)
+    } else {
+        return None;
+    };
+    Some((side, row_at(app.panel(side), area, row)))
+}
+
+/// Top-level render function called each frame.
+pub fn render(frame: &mut Frame, app: &App) {
+    let theme = if app.no_color {
+        Theme::no_color()
+    } else {
+        app.theme_choice.resolve()
+    };
+
+    let chunks = main_layout(frame.area());
 
     render_panels(frame, app, chunks[0], &theme);
+    render_entry_detail(frame, app, chunks[1], &theme);
 
+    // A running connection test gets an animated spinner in front of its message.
+    let status = match (&app.connection_test, &app.status_message) {
+        (Some(test), Some(msg)) => Some(format!("{} {}", test.spinner(), msg)),
+        (_, msg) => msg.clone(),
+    };
     render_statusbar(
         frame,
-        chunks[1],
+        chunks[2],
         app.is_connected(),
-        app.status_message.as_deref(),
-        app.upload_progress.as_ref(),
-        app.download_progress.as_ref(),
+        status.as_deref(),
+        active_transfer(app),
+        app.queue_progress(),
+        link_state(app),
         &theme,
     );
 
@@ -51,23 +87,103 @@ pub fn render(frame: &mut Frame, app: &App) {
     if let Some(ref dlg) = app.rename_dialog {
         render_rename_dialog(frame, dlg, &theme);
     }
+    if let Some(ref dlg) = app.batch_rename_dialog {
+        render_batch_rename_dialog(frame, dlg, &theme);
+    }
     if let Some(ref dlg) = app.mkdir_dialog {
         render_mkdir_dialog(frame, dlg, &theme);
     }
+    if let Some(ref dlg) = app.touch_dialog {
+        render_touch_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.goto_dialog {
+        render_goto_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.upload_as_dialog {
+        render_upload_as_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.mark_pattern_dialog {
+        render_mark_pattern_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.mark_age_dialog {
+        render_mark_age_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.chown_dialog {
+        render_chown_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.search_dialog {
+        render_search_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.grep_dialog {
+        render_grep_dialog(frame, dlg, &theme);
+    }
     if let Some(ref dlg) = app.delete_dialog {
         render_delete_dialog(frame, dlg, &theme);
     }
     if let Some(ref dlg) = app.shell_dialog {
-        render_shell_dialog(frame, dlg, &app.left.path, &theme);
+        let cwd = if dlg.remote { &app.remote().path } else { &app.local().path };
+        render_shell_dialog(frame, dlg, cwd, &theme);
+    }
+    if let Some(ref dlg) = app.temp_cleanup_dialog {
+        render_temp_cleanup_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.sync_dialog {
+        render_sync_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.info_dialog {
+        render_info_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.log_dialog {
+        render_log_dialog(frame, dlg, &app.status_log, &theme);
+    }
+    if let Some(ref dlg) = app.palette_dialog {
+        render_palette_dialog(frame, dlg, &theme);
     }
     if let Some(ref dlg) = app.permission_dialog {
         render_permission_dialog(frame, dlg, &theme);
     }
+    if let Some(ref dlg) = app.edit_conflict_dialog {
+        render_edit_conflict_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.edit_upload_dialog {
+        render_edit_upload_dialog(frame, dlg, &theme);
+    }
+    if let Some(ref dlg) = app.edit_size_dialog {
+        render_edit_size_dialog(frame, dlg, &theme);
+    }
     if let Some(ref dlg) = app.host_key_dialog {
         render_host_key_dialog(frame, dlg, &theme);
     }
+    if let Some(ref pending) = app.pending_connect {
+        render_connect_dialog(frame, pending, &theme);
+    }
+    if app.quit_confirm_visible {
+        render_quit_dialog(frame, &theme);
+    }
     // Help overlay on top of everything else
     if app.help_visible {
         render_help_dialog(frame, &theme);
     }
 }
+
+/// Connection health for the status bar indicator.
+fn link_state(app: &App) -> LinkState {
+    if !app.is_connected() {
+        LinkState::Disconnected
+    } else if app.latency_failed {
+        LinkState::Unresponsive
+    } else {
+        LinkState::Connected(app.latency)
+    }
+}
+
+/// The running transfer shown in the status bar (upload before download
+/// before copy before delete).
+fn active_transfer(app: &App) -> Option<(&TransferHandle, TransferKind)> {
+    app.upload_progress
+        .as_ref()
+        .map(|h| (h, TransferKind::Upload))
+        .or_else(|| app.download_progress.as_ref().map(|h| (h, TransferKind::Download)))
+        .or_else(|| app.copy_progress.as_ref().map(|h| (h, TransferKind::Copy)))
+        .or_else(|| app.delete_progress.as_ref().map(|h| (h, TransferKind::Delete)))
+}
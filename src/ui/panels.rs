@@ -5,29 +5,41 @@ use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
 };
 
-use crate::app::{ActivePanel, App, PanelState};
+use crate::app::{App, CompareStatus, FileEntry, PanelState};
+use crate::config::settings::{Column, ColumnWidths};
 use crate::ui::theme::Theme;
-use std::collections::HashSet;
 
-// Column widths (in characters)
-const COL_SIZE: u16 = 9;   // e.g. "   1.2 KB"
-const COL_DATE: u16 = 16;  // e.g. "2024-03-15 14:22"
-const COL_PERM: u16 = 9;   // e.g. "rwxr-xr-x"
+// Column widths (in characters); the others come from settings `column_widths`.
+const COL_SIZE_EXACT: u16 = 17; // e.g. "       12,345,678" (up to 99 TB)
 const COL_PADDING: u16 = 2;
+/// Narrower than this, the name column takes space from the optional columns.
+const NAME_MIN_WIDTH: u16 = 12;
+/// Order in which optional columns are dropped on narrow panels.
+const DROP_ORDER: [Column; 3] = [Column::Perm, Column::Date, Column::Size];
+/// Rows of context kept above and below the selection while scrolling
+/// (vim's `scrolloff`).
+const SCROLL_MARGIN: usize = 3;
 
 /// Render a single file panel inside the given area.
-/// `show_permissions` adds a "rwxr-xr-x" column (used for the remote panel).
+/// `columns` / `widths` set the columns right of the name (settings);
+/// `show_permissions` allows the "rwxr-xr-x" column (remote panel, local with Ctrl+O);
+/// `exact_sizes` shows byte counts instead of rounded units,
+/// `relative_dates` the age of an entry instead of its timestamp.
+#[allow(clippy::too_many_arguments)]
 pub fn render_panel(
     frame: &mut Frame,
     panel: &PanelState,
     area: Rect,
     is_active: bool,
     label: &str,
+    columns: &[Column],
+    widths: &ColumnWidths,
     show_permissions: bool,
-    marked: &HashSet<usize>,
+    exact_sizes: bool,
+    relative_dates: bool,
     theme: &Theme,
 ) {
     let border_style = if is_active {
@@ -37,53 +49,64 @@ pub fn render_panel(
     };
 
     let title = format!(" {} — {} ", label, panel.path.display());
-    let block = Block::default()
+    let mut block = Block::default()
         .title(title.as_str())
         .borders(Borders::ALL)
         .border_style(border_style);
+    if theme.no_color && is_active {
+        // Without colours the focused panel is marked by a heavy border.
+        block = block
+            .border_type(BorderType::Thick)
+            .title_style(Style::default().add_modifier(Modifier::BOLD));
+    }
+    if let Some(free) = panel.free_space {
+        let free_str = format!(" Frei: {} ", format_size(free).trim_start());
+        block = block.title(Line::from(free_str).right_aligned());
+    }
+    block = block.title_bottom(Line::from(panel_summary(panel)));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Fixed columns: 1 (mark "✓") + 2 (icon) + COL_PADDING*2 (two separators)
-    // + COL_SIZE + COL_DATE + 2 (highlight_symbol "► ")
-    // Optional: + COL_PADDING + COL_PERM if show_permissions
-    let perm_cols = if show_permissions { COL_PADDING + COL_PERM } else { 0 };
-    let fixed_cols = 1 + 2 + COL_PADDING * 2 + COL_SIZE + COL_DATE + 2 + perm_cols;
-    let name_width = inner.width.saturating_sub(fixed_cols) as usize;
+    let (shown, name_width) =
+        layout_columns(columns, widths, show_permissions, exact_sizes, inner.width);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
 
     let items: Vec<ListItem> = panel
-        .entries
-        .iter()
-        .enumerate()
+        .visible_entries()
         .map(|(idx, e)| {
-            let is_marked = marked.contains(&idx);
+            let is_marked = panel.is_marked(e);
+            let compare = panel.compare.get(idx).copied().flatten();
 
             let (icon, base_style) = if e.is_dir {
                 ("▶ ", Style::default().fg(theme.directory_icon).add_modifier(Modifier::BOLD))
+            } else if e.is_executable() {
+                ("  ", Style::default().fg(theme.text_success))
             } else {
                 ("  ", Style::default().fg(theme.file_name))
             };
 
-            // Marked entries get a distinct name style.
-            let name_style = if is_marked {
-                Style::default().fg(theme.marked_entry).add_modifier(Modifier::BOLD)
-            } else {
-                base_style
+            // Marked entries get a distinct name style; in compare mode the
+            // others are coloured by their status.
+            let name_style = match compare {
+                _ if is_marked => {
+                    Style::default().fg(theme.marked_entry).add_modifier(Modifier::BOLD)
+                }
+                Some(status) => base_style.fg(compare_color(status, theme)),
+                None => base_style,
             };
 
-            let mark_str = if is_marked { "✓" } else { " " };
-
-            let name = truncate_name(&e.name, name_width);
-            let size_str = match e.size {
-                Some(s) => format_size(s),
-                None => format!("{:>width$}", "", width = COL_SIZE as usize),
-            };
-            let date_str = match e.modified {
-                Some(t) => format_time(t),
-                None => format!("{:>width$}", "", width = COL_DATE as usize),
+            let mark_str = match compare {
+                _ if is_marked => "✓",
+                Some(status) => status.symbol(),
+                None => " ",
             };
 
+            let name = truncate_name(&display_name(e), name_width);
+
             let mut spans = vec![
                 // Mark indicator replaces the icon's first char slot
                 Span::styled(
@@ -95,35 +118,47 @@ pub fn render_panel(
                     format!("{:<width$}", name, width = name_width),
                     name_style,
                 ),
-                Span::raw("  "),
-                Span::styled(size_str, Style::default().fg(theme.size_text)),
-                Span::raw("  "),
-                Span::styled(date_str, Style::default().fg(theme.date_text)),
             ];
 
-            if show_permissions {
-                let perm_str = match &e.permissions {
-                    Some(p) => format!("  {:>width$}", p, width = COL_PERM as usize),
-                    None => format!("  {:>width$}", "", width = COL_PERM as usize),
+            for &(column, width) in &shown {
+                let (text, color) = match column {
+                    Column::Size => {
+                        let text = match e.size {
+                            Some(s) if exact_sizes => format_bytes_exact(s),
+                            Some(s) => format_size(s),
+                            None => String::new(),
+                        };
+                        (text, theme.size_text)
+                    }
+                    Column::Date => {
+                        let text = match e.modified {
+                            Some(t) if relative_dates => format_age(t, now),
+                            Some(t) => format_time(t),
+                            None => String::new(),
+                        };
+                        (text, theme.date_text)
+                    }
+                    Column::Perm => (e.permissions.clone().unwrap_or_default(), theme.permission_text),
                 };
-                spans.push(Span::styled(
-                    perm_str,
-                    Style::default().fg(theme.permission_text),
-                ));
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(fit_cell(&text, width), Style::default().fg(color)));
             }
 
             ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let mut list_state = ListState::default();
-    list_state.select(Some(panel.selected));
+    let offset = scroll_offset(panel, inner.height as usize);
+    panel.scroll_offset.set(offset);
+    panel.view_height.set(inner.height as usize);
+    let mut list_state = ListState::default()
+        .with_offset(offset)
+        .with_selected(Some(panel.selected));
 
     let list = List::new(items)
         .highlight_style(
-            Style::default()
-                .bg(theme.highlight_bg)
-                .fg(theme.highlight_fg)
+            theme
+                .fill(theme.highlight_bg, theme.highlight_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(theme.highlight_symbol);
@@ -131,18 +166,65 @@ pub fn render_panel(
     frame.render_stateful_widget(list, inner, &mut list_state);
 }
 
-/// Render both panels side by side.
-/// When `app.panels_swapped` is true the remote panel appears on the left and
-/// the local panel on the right — purely visual, the data model is unchanged.
+/// Pick the optional columns for a panel `inner_width` wide: the configured
+/// ones in their order, without permissions unless `show_permissions`. When
+/// the name would get narrower than `NAME_MIN_WIDTH`, columns are dropped in
+/// `DROP_ORDER`. Returns the columns with their widths and the name width.
+fn layout_columns(
+    columns: &[Column],
+    widths: &ColumnWidths,
+    show_permissions: bool,
+    exact_sizes: bool,
+    inner_width: u16,
+) -> (Vec<(Column, usize)>, usize) {
+    let mut shown: Vec<(Column, u16)> = Vec::new();
+    for &column in columns {
+        if shown.iter().any(|(c, _)| *c == column) || (column == Column::Perm && !show_permissions) {
+            continue;
+        }
+        let width = match column {
+            Column::Size if exact_sizes => widths.size.max(COL_SIZE_EXACT),
+            Column::Size => widths.size,
+            Column::Date => widths.date,
+            Column::Perm => widths.perm,
+        };
+        shown.push((column, width));
+    }
+    // 1 (mark "✓") + 2 (icon) + 2 (highlight_symbol "► ") + a separator per column.
+    let name_width = |shown: &[(Column, u16)]| {
+        let fixed: u16 = 1 + 2 + 2 + shown.iter().map(|(_, w)| COL_PADDING + w).sum::<u16>();
+        inner_width.saturating_sub(fixed)
+    };
+    for drop in DROP_ORDER {
+        if name_width(&shown) >= NAME_MIN_WIDTH {
+            break;
+        }
+        shown.retain(|(c, _)| *c != drop);
+    }
+    let name = name_width(&shown) as usize;
+    (shown.into_iter().map(|(c, w)| (c, w as usize)).collect(), name)
+}
+
+/// Right-align `text` (padding trimmed) in `width` columns, cutting it off
+/// on the right when it is longer.
+fn fit_cell(text: &str, width: usize) -> String {
+    let cut: String = text.trim().chars().take(width).collect();
+    format!("{:>width$}", cut, width = width)
+}
+
+/// Render both panels side by side (or only the active one, Ctrl+W).
+/// Placement comes from `panel_areas`; the focus border follows the logical
+/// panel (`app.active`), so it lands on whichever side that panel is drawn.
 pub fn render_panels(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
-    let mid = area.width / 2;
-    let left_area = Rect { x: area.x,       y: area.y, width: mid,              height: area.height };
-    let right_area = Rect { x: area.x + mid, y: area.y, width: area.width - mid, height: area.height };
+    let (local_area, remote_area) = panel_areas(app, area);
 
     let connected = app.is_connected();
+    let loading = app.pending_listing.as_ref().is_some_and(|p| p.visible());
     let remote_label = if connected {
         if let Some(ref conn) = app.sftp {
-            format!("Remote [{}@{}]", conn.user, conn.host)
+            // The old listing stays on screen until the new one arrives.
+            let marker = if loading { " (Lade…)" } else { "" };
+            format!("Remote [{}@{}]{}", conn.user, conn.host, marker)
         } else {
             "Remote".to_string()
         }
@@ -150,32 +232,195 @@ pub fn render_panels(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         "Remote [nicht verbunden — F9 für Profile]".to_string()
     };
 
+    // Single-panel mode draws only the active panel (see `panel_areas`).
+    let remote_active = app.is_remote_active();
+    if !app.single_panel || !remote_active {
+        render_panel(
+            frame,
+            app.local(),
+            local_area,
+            !remote_active,
+            "Local",
+            &app.columns,
+            &app.column_widths,
+            app.local_permissions,
+            app.exact_sizes,
+            app.relative_dates,
+            theme,
+        );
+    }
+    if app.quick_connect_shown() && (!app.single_panel || remote_active) {
+        render_quick_connect(frame, app, remote_area, remote_active, theme);
+    } else if !app.single_panel || remote_active {
+        render_panel(
+            frame,
+            app.remote(),
+            remote_area,
+            remote_active,
+            &remote_label,
+            &app.columns,
+            &app.column_widths,
+            connected,
+            app.exact_sizes,
+            app.relative_dates,
+            theme,
+        );
+    }
+}
+
+/// Disconnected remote panel: the recently used profiles (`app.quick_profiles`)
+/// with the highlight on `app.quick_selected`; Enter connects.
+fn render_quick_connect(frame: &mut Frame, app: &App, area: Rect, is_active: bool, theme: &Theme) {
+    let border_style = if is_active {
+        Style::default().fg(theme.panel_active_border)
+    } else {
+        Style::default().fg(theme.panel_inactive_border)
+    };
+    let mut block = Block::default()
+        .title(" Remote [nicht verbunden] — Zuletzt verwendet ")
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title_bottom(Line::from(" Enter: verbinden · F9: alle Profile "));
+    if theme.no_color && is_active {
+        block = block
+            .border_type(BorderType::Thick)
+            .title_style(Style::default().add_modifier(Modifier::BOLD));
+    }
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let items: Vec<ListItem> = app
+        .quick_profiles
+        .iter()
+        .map(|p| {
+            let used = p.last_used.map(|t| format_relative(t, now)).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", p.name),
+                    Style::default().fg(theme.file_name).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{}@{}:{}  ", p.user, p.host, p.port),
+                    Style::default().fg(theme.size_text),
+                ),
+                Span::styled(used, Style::default().fg(theme.date_text)),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(app.quick_selected));
+    let list = List::new(items)
+        .highlight_style(
+            theme
+                .fill(theme.highlight_bg, theme.highlight_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(theme.highlight_symbol);
+    frame.render_stateful_widget(list, inner, &mut list_state);
+}
+
+/// Screen areas of (local panel, remote panel) inside `area`.
+/// In single-panel mode the active panel gets all of `area` and the other
+/// an empty rect, so neither drawing nor mouse hits reach it.
+pub fn panel_areas(app: &App, area: Rect) -> (Rect, Rect) {
+    if app.single_panel {
+        return if app.is_remote_active() {
+            (Rect::default(), area)
+        } else {
+            (area, Rect::default())
+        };
+    }
+    let mid = area.width / 2;
+    let left_area = Rect { x: area.x,       y: area.y, width: mid,              height: area.height };
+    let right_area = Rect { x: area.x + mid, y: area.y, width: area.width - mid, height: area.height };
+
     // Determine which physical area gets which logical panel.
-    let (local_area, remote_area) = if app.panels_swapped {
+    if app.screen_left() == app.remote_side {
         (right_area, left_area)
     } else {
         (left_area, right_area)
-    };
+    }
+}
 
-    render_panel(
-        frame,
-        &app.left,
-        local_area,
-        app.active == ActivePanel::Left,
-        "Local",
-        false,
-        &app.left.marked.clone(),
-        theme,
-    );
-    render_panel(
-        frame,
-        &app.right,
-        remote_area,
-        app.active == ActivePanel::Right,
-        &remote_label,
-        connected,
-        &app.right.marked.clone(),
-        theme,
+/// First visible row for a list of `height` rows: the previous offset,
+/// moved only as far as needed to keep `SCROLL_MARGIN` rows around the
+/// selection (fewer in panels too short for a full margin on both sides).
+fn scroll_offset(panel: &PanelState, height: usize) -> usize {
+    let len = panel.visible_len();
+    if height == 0 || len <= height {
+        return 0;
+    }
+    let margin = SCROLL_MARGIN.min(height.saturating_sub(1) / 2);
+    let selected = panel.selected;
+    let mut offset = panel.scroll_offset.get();
+    if selected < offset + margin {
+        offset = selected.saturating_sub(margin);
+    } else if selected + margin >= offset + height {
+        offset = selected + margin + 1 - height;
+    }
+    offset.min(len - height)
+}
+
+/// Visible row position under screen row `y` in a panel drawn at `area`,
+/// or None for the border or the empty space below the last entry.
+/// Uses the scroll offset of the last `render_panel`.
+pub fn row_at(panel: &PanelState, area: Rect, y: u16) -> Option<usize> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    if y < inner.y || y >= inner.y + inner.height {
+        return None;
+    }
+    let pos = panel.scroll_offset.get() + (y - inner.y) as usize;
+    (pos < panel.visible_len()).then_some(pos)
+}
+
+/// Name colour of an entry in compare mode.
+fn compare_color(status: CompareStatus, theme: &Theme) -> ratatui::style::Color {
+    match status {
+        CompareStatus::OnlyHere => theme.text_info,
+        CompareStatus::Newer => theme.text_success,
+        CompareStatus::Older => theme.text_warning,
+        CompareStatus::Differs => theme.text_danger,
+        CompareStatus::Identical => theme.text_muted,
+    }
+}
+
+/// Name as shown in the list. Symlinks: "name@ → target", executables
+/// "name*", like `ls -F -l`.
+fn display_name(e: &FileEntry) -> String {
+    match (e.is_symlink, &e.link_target) {
+        (true, Some(target)) => format!("{}@ → {}", e.name, target),
+        (true, None) => format!("{}@", e.name),
+        _ if e.is_executable() => format!("{}*", e.name),
+        _ => e.name.clone(),
+    }
+}
+
+/// Detail line below the panels: the untruncated name of the highlighted
+/// entry in the active panel. Names wider than the screen keep their end,
+/// which is usually what tells similar names apart.
+pub fn render_entry_detail(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let name = match app.panel(app.active).selected_entry() {
+        Some(e) if e.name != ".." => display_name(e),
+        _ => String::new(),
+    };
+    let budget = area.width.saturating_sub(1) as usize;
+    let count = name.chars().count();
+    let shown = if count <= budget {
+        name
+    } else {
+        let tail: String = name.chars().skip(count + 1 - budget).collect();
+        format!("…{}", tail)
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(" {}", shown),
+            Style::default().fg(theme.filename_text),
+        ))),
+        area,
     );
 }
 
@@ -194,7 +439,45 @@ fn truncate_name(name: &str, max_len: usize) -> String {
     }
 }
 
-fn format_size(bytes: u64) -> String {
+/// Footer line: "N Dateien, M Ordner, X gesamt" for the shown entries
+/// (non-recursive), plus "K markiert (Y)" while anything is marked.
+fn panel_summary(panel: &PanelState) -> String {
+    let (mut files, mut dirs, mut bytes) = (0usize, 0usize, 0u64);
+    for (_, e) in panel.visible_entries().filter(|(_, e)| e.name != "..") {
+        if e.is_dir {
+            dirs += 1;
+        } else {
+            files += 1;
+            bytes += e.size.unwrap_or(0);
+        }
+    }
+    let mut summary = format!(
+        " {} Dateien, {} Ordner, {} gesamt ",
+        files,
+        dirs,
+        format_size(bytes).trim_start()
+    );
+    if !panel.marked.is_empty() {
+        let marked_bytes: u64 = panel
+            .entries
+            .iter()
+            .filter(|e| panel.is_marked(e) && !e.is_dir)
+            .filter_map(|e| e.size)
+            .sum();
+        summary.push_str(&format!(
+            "— {} markiert ({}) ",
+            panel.marked.len(),
+            format_size(marked_bytes).trim_start()
+        ));
+    }
+    let hidden = panel.hidden_count();
+    if hidden > 0 {
+        summary.push_str(&format!("— {} versteckt ", hidden));
+    }
+    summary
+}
+
+pub fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut value = bytes as f64;
     let mut unit_idx = 0;
@@ -209,36 +492,93 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Exact byte count with thousands separators, e.g. "1,234,567".
+pub fn format_bytes_exact(bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Format a SystemTime as "YYYY-MM-DD HH:MM" (local time via UTC offset).
 fn format_time(t: SystemTime) -> String {
     let secs = match t.duration_since(UNIX_EPOCH) {
         Ok(d) => d.as_secs() as i64,
-        Err(_) => return format!("{:>width$}", "—", width = COL_DATE as usize),
+        Err(_) => return "—".to_string(),
     };
 
-    // Compute local offset from TZ environment (simple approach via libc).
-    // We use a manual calendar calculation to avoid pulling in chrono.
-    let local_secs = secs + local_utc_offset_secs();
+    // Local offset for this instant comes from libc (DST-aware); the calendar
+    // calculation is manual to avoid pulling in chrono.
+    let local_secs = secs + local_utc_offset_secs(secs);
     let (year, month, day, hour, min) = secs_to_datetime(local_secs);
     format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, hour, min)
 }
 
-/// Returns the local UTC offset in seconds using the C `timezone` global.
-fn local_utc_offset_secs() -> i64 {
-    // Safe: reads a global set by the OS, no mutation.
+/// Date column in relative mode: the age of `t` (see `format_relative`).
+/// Pre-epoch times show as in `format_time`.
+fn format_age(t: SystemTime, now: u64) -> String {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => format_relative(d.as_secs(), now),
+        Err(_) => format_time(t),
+    }
+}
+
+/// Full local timestamp "YYYY-MM-DD HH:MM:SS" for `secs` since the Unix epoch.
+pub fn format_timestamp(secs: u64) -> String {
+    let local_secs = secs as i64 + local_utc_offset_secs(secs as i64);
+    let (year, month, day, hour, min) = secs_to_datetime(local_secs);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year, month, day, hour, min, local_secs.rem_euclid(60)
+    )
+}
+
+/// Coarse age of `secs` relative to `now`, e.g. "vor 40 Sek.", "vor 5 Min.",
+/// "vor 2 Std.", "gestern", "vor 3 Tagen", "vor 2 Wochen"; older than eight
+/// weeks falls back to the date. Timestamps up to a minute ahead of `now`
+/// count as "gerade eben", later ones are shown as "in Zukunft".
+pub fn format_relative(secs: u64, now: u64) -> String {
+    if secs > now + 60 {
+        return "in Zukunft".to_string();
+    }
+    let age = now.saturating_sub(secs);
+    match age {
+        0..=9 => "gerade eben".to_string(),
+        10..=59 => format!("vor {} Sek.", age),
+        60..=3_599 => format!("vor {} Min.", age / 60),
+        3_600..=86_399 => format!("vor {} Std.", age / 3_600),
+        86_400..=172_799 => "gestern".to_string(),
+        172_800..=604_799 => format!("vor {} Tagen", age / 86_400),
+        604_800..=1_209_599 => "vor 1 Woche".to_string(),
+        1_209_600..=4_838_399 => format!("vor {} Wochen", age / 604_800),
+        _ => format!("am {}", &format_timestamp(secs)[..10]),
+    }
+}
+
+/// Local UTC offset in seconds at the instant `secs`, daylight saving time
+/// included (`localtime_r` applies the zone's rules for that date).
+fn local_utc_offset_secs(secs: i64) -> i64 {
     #[cfg(unix)]
     {
-        extern "C" {
-            fn tzset();
-            static timezone: std::ffi::c_long;
-        }
+        let t = secs as libc::time_t;
+        // Safe: localtime_r only writes into the zeroed `tm` we own and
+        // returns NULL when the time cannot be represented.
         unsafe {
-            tzset();
-            -(timezone as i64)
+            let mut tm: libc::tm = std::mem::zeroed();
+            if libc::localtime_r(&t, &mut tm).is_null() {
+                return 0;
+            }
+            tm.tm_gmtoff as i64
         }
     }
     #[cfg(not(unix))]
     {
+        let _ = secs;
         0
     }
 }
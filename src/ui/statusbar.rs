@@ -6,28 +6,85 @@ use ratatui::{
     widgets::{Block, Paragraph},
 };
 
-use crate::transfer::queue::TransferHandle;
+use std::time::Duration;
+
+use crate::transfer::queue::{QueueProgress, TransferHandle};
 use crate::ui::theme::Theme;
 
+/// Connection state shown at the right end of the second status row.
+#[derive(Debug, Clone, Copy)]
+pub enum LinkState {
+    Disconnected,
+    /// Connected; the last measured round trip, if any yet.
+    Connected(Option<Duration>),
+    /// The last latency probe failed.
+    Unresponsive,
+}
+
 /// Render the function-key hint bar (and optional transfer progress) at the bottom.
 /// `connected` controls whether F3-Disconnect is shown.
 /// `upload` / `download` are `Some(handle)` while the respective transfer is running.
+/// `queue` is the position of the current queue run; while other jobs
+/// finished or wait, the label adds "Auftrag n/m" and the file count over
+/// the whole run.
+/// `link` is drawn in both modes at the right end of the second row.
+#[allow(clippy::too_many_arguments)]
 pub fn render_statusbar(
     frame: &mut Frame,
     area: Rect,
     connected: bool,
     message: Option<&str>,
-    upload: Option<&TransferHandle>,
-    download: Option<&TransferHandle>,
+    transfer: Option<(&TransferHandle, TransferKind)>,
+    queue: QueueProgress,
+    link: LinkState,
     theme: &Theme,
 ) {
-    if let Some(handle) = upload {
-        render_transfer_bar(frame, area, handle, message, TransferKind::Upload, theme);
-    } else if let Some(handle) = download {
-        render_transfer_bar(frame, area, handle, message, TransferKind::Download, theme);
-    } else {
-        render_hint_bar(frame, area, connected, message, theme);
+    match transfer {
+        Some((handle, kind)) => render_transfer_bar(frame, area, handle, message, kind, queue, theme),
+        None => render_hint_bar(frame, area, connected, message, theme),
     }
+    render_link_indicator(frame, area, link, theme);
+}
+
+// ---------------------------------------------------------------------------
+// Connection indicator
+// ---------------------------------------------------------------------------
+
+/// Latency buckets: below `LATENCY_GOOD` green, below `LATENCY_SLOW` yellow,
+/// red above.
+const LATENCY_GOOD: Duration = Duration::from_millis(100);
+const LATENCY_SLOW: Duration = Duration::from_millis(400);
+
+fn render_link_indicator(frame: &mut Frame, area: Rect, link: LinkState, theme: &Theme) {
+    if area.height < 2 {
+        return;
+    }
+    let (text, color) = match link {
+        LinkState::Disconnected => ("○ getrennt".to_string(), theme.text_muted),
+        LinkState::Unresponsive => ("● keine Antwort".to_string(), theme.text_danger),
+        LinkState::Connected(None) => ("● verbunden".to_string(), theme.text_success),
+        LinkState::Connected(Some(rtt)) => {
+            let color = if rtt < LATENCY_GOOD {
+                theme.text_success
+            } else if rtt < LATENCY_SLOW {
+                theme.text_warning
+            } else {
+                theme.text_danger
+            };
+            (format!("● {} ms", rtt.as_millis()), color)
+        }
+    };
+    let text = format!(" {} ", text);
+    let width = (text.chars().count() as u16).min(area.width);
+    let rect = Rect {
+        x: area.x + area.width - width,
+        y: area.y + 1,
+        width,
+        height: 1,
+    };
+    let para = Paragraph::new(Span::styled(text, Style::default().fg(color)))
+        .style(Style::default().bg(theme.hint_bar_bg));
+    frame.render_widget(para, rect);
 }
 
 // ---------------------------------------------------------------------------
@@ -63,14 +120,12 @@ fn render_hint_bar(frame: &mut Frame, area: Rect, connected: bool, message: Opti
     let mut spans: Vec<Span> = Vec::new();
     for (key, label) in &hints {
         let key_style = if *key == "F3" && connected {
-            Style::default()
-                .bg(theme.hint_badge_danger_bg)
-                .fg(theme.hint_badge_fg)
+            theme
+                .fill(theme.hint_badge_danger_bg, theme.hint_badge_fg)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default()
-                .bg(theme.hint_badge_bg)
-                .fg(theme.hint_badge_fg)
+            theme
+                .fill(theme.hint_badge_bg, theme.hint_badge_fg)
                 .add_modifier(Modifier::BOLD)
         };
         spans.push(Span::styled(format!(" {} ", key), key_style));
@@ -95,12 +150,17 @@ fn render_hint_bar(frame: &mut Frame, area: Rect, connected: bool, message: Opti
 }
 
 // ---------------------------------------------------------------------------
-// Transfer progress bar (shared by upload and download)
+// Transfer progress bar (shared by upload, download and remote copy)
 // ---------------------------------------------------------------------------
 
-enum TransferKind {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
     Upload,
     Download,
+    /// Server-side copy between two remote paths.
+    Copy,
+    /// Background removal of remote directory trees.
+    Delete,
 }
 
 fn render_transfer_bar(
@@ -109,6 +169,7 @@ fn render_transfer_bar(
     handle: &TransferHandle,
     _message: Option<&str>,
     kind: TransferKind,
+    queue: QueueProgress,
     theme: &Theme,
 ) {
     // Read progress without holding the lock for long.
@@ -125,6 +186,8 @@ fn render_transfer_bar(
     let (verb, bar_color) = match kind {
         TransferKind::Upload => ("Upload", theme.upload_bar),
         TransferKind::Download => ("Download", theme.download_bar),
+        TransferKind::Copy => ("Copy", theme.download_bar),
+        TransferKind::Delete => ("Lösche…", theme.download_bar),
     };
 
     // Split the 2-row status area: row 0 = progress bar, row 1 = filename.
@@ -138,7 +201,18 @@ fn render_transfer_bar(
     // mismatch between the bar background and the text baseline.
     let width = rows[0].width as usize;
     let pct = (fraction * 100.0).round() as u64;
-    let label = format!(" {} {}/{} — {}% ", verb, files_done, files_total, pct);
+    let label = if queue.is_single() {
+        format!(" {} {}/{} — {}% ", verb, files_done, files_total, pct)
+    } else {
+        format!(
+            " {} {}/{} — {}% — {} ",
+            verb,
+            files_done,
+            files_total,
+            pct,
+            queue.label(files_done, files_total)
+        )
+    };
 
     // Number of filled columns (█) vs empty columns (░).
     let filled = ((fraction * width as f64).round() as usize).min(width);
@@ -151,9 +225,8 @@ fn render_transfer_bar(
 
     // Build each column as a styled character.
     // The label is overlaid by replacing bar characters at the label position.
-    let mut bar_chars: Vec<char> = std::iter::repeat('█')
-        .take(filled)
-        .chain(std::iter::repeat('░').take(empty))
+    let mut bar_chars: Vec<char> = std::iter::repeat_n('█', filled)
+        .chain(std::iter::repeat_n('░', empty))
         .collect();
 
     // Overlay the label text onto bar_chars (centred).
@@ -173,9 +246,8 @@ fn render_transfer_bar(
     let bar_line = Line::from(vec![
         Span::styled(
             filled_str,
-            Style::default()
-                .fg(theme.transfer_filled_fg)
-                .bg(bar_color)
+            theme
+                .fill(bar_color, theme.transfer_filled_fg)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
@@ -1,8 +1,10 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::config::settings::config_dir;
+
 const HIGHLIGHT_SYMBOL: &str = "► ";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,6 +12,8 @@ pub enum ThemeChoice {
     Auto,
     Dark,
     Light,
+    Monochrome,
+    HighContrast,
     Custom(String),
 }
 
@@ -18,15 +22,17 @@ impl ThemeChoice {
         match self {
             ThemeChoice::Dark => Theme::dark(),
             ThemeChoice::Light => Theme::light(),
+            ThemeChoice::Monochrome => Theme::monochrome(),
+            ThemeChoice::HighContrast => Theme::high_contrast(),
             ThemeChoice::Custom(name) => {
                 let path = themes_dir().join(format!("{}.toml", name));
-                Theme::from_toml_file(&path).unwrap_or_else(|| Theme::dark())
+                Theme::from_toml_file(&path).unwrap_or_else(Theme::dark)
             }
             ThemeChoice::Auto => match std::env::var("COLORFGBG") {
                 Ok(val) => {
                     let bg_val = val
-                        .split(|c| c == ':' || c == ';')
-                        .last()
+                        .split([':', ';'])
+                        .next_back()
                         .and_then(|s| s.parse::<u8>().ok());
                     match bg_val {
                         Some(b) if b < 8 => Theme::dark(),
@@ -43,6 +49,8 @@ impl ThemeChoice {
             ThemeChoice::Auto => "Auto",
             ThemeChoice::Dark => "Dark",
             ThemeChoice::Light => "Light",
+            ThemeChoice::Monochrome => "Monochrome",
+            ThemeChoice::HighContrast => "High-Contrast",
             ThemeChoice::Custom(name) => name.as_str(),
         }
     }
@@ -52,6 +60,8 @@ impl ThemeChoice {
             ThemeChoice::Auto => "Auto",
             ThemeChoice::Dark => "Dark",
             ThemeChoice::Light => "Light",
+            ThemeChoice::Monochrome => "Monochrome",
+            ThemeChoice::HighContrast => "High-Contrast",
             ThemeChoice::Custom(name) => name.as_str(),
         }
     }
@@ -61,6 +71,8 @@ impl ThemeChoice {
             "Auto" | "auto" => ThemeChoice::Auto,
             "Dark" | "dark" => ThemeChoice::Dark,
             "Light" | "light" => ThemeChoice::Light,
+            "Monochrome" | "monochrome" => ThemeChoice::Monochrome,
+            "High-Contrast" | "high-contrast" | "high_contrast" => ThemeChoice::HighContrast,
             name => ThemeChoice::Custom(name.to_string()),
         }
     }
@@ -128,6 +140,10 @@ pub struct Theme {
     pub shell_label: Color,
     pub highlight_primary_bg: Color,
     pub highlight_primary_fg: Color,
+
+    // Accessibility
+    /// All colours are the terminal default; filled elements use reverse video.
+    pub no_color: bool,
 }
 
 impl Theme {
@@ -189,6 +205,8 @@ impl Theme {
 
             highlight_primary_bg: Color::Blue,
             highlight_primary_fg: Color::White,
+
+            no_color: false,
         }
     }
 
@@ -250,6 +268,212 @@ impl Theme {
 
             highlight_primary_bg: Color::Cyan,
             highlight_primary_fg: Color::Black,
+
+            no_color: false,
+        }
+    }
+
+    /// Grey scale only — for terminals whose palette makes the colours unreadable.
+    pub fn monochrome() -> Self {
+        Self {
+            panel_active_border: Color::White,
+            panel_inactive_border: Color::DarkGray,
+            directory_icon: Color::White,
+            file_name: Color::Gray,
+            marked_entry: Color::White,
+            mark_indicator: Color::White,
+            size_text: Color::Gray,
+            date_text: Color::DarkGray,
+            permission_text: Color::DarkGray,
+            highlight_bg: Color::White,
+            highlight_fg: Color::Black,
+            highlight_symbol: HIGHLIGHT_SYMBOL,
+
+            hint_badge_bg: Color::Gray,
+            hint_badge_fg: Color::Black,
+            hint_badge_danger_bg: Color::White,
+            hint_label: Color::Gray,
+            status_message: Color::White,
+            hint_bar_bg: Color::Black,
+            transfer_filled_fg: Color::Black,
+            transfer_empty_bg: Color::DarkGray,
+            upload_bar: Color::White,
+            download_bar: Color::Gray,
+            transfer_row_bg: Color::Black,
+            filename_text: Color::White,
+
+            dialog_active_border: Color::White,
+            dialog_inactive_border: Color::DarkGray,
+            dialog_warning_border: Color::Gray,
+            dialog_error_border: Color::White,
+            dialog_success_border: Color::Gray,
+
+            text_primary: Color::White,
+            text_secondary: Color::Gray,
+            text_muted: Color::DarkGray,
+            text_active: Color::White,
+            text_inactive: Color::Gray,
+            cursor_bg: Color::White,
+            cursor_fg: Color::Black,
+            toggle_on: Color::White,
+            toggle_off: Color::DarkGray,
+            text_danger: Color::White,
+            text_success: Color::Gray,
+            text_warning: Color::White,
+            text_info: Color::Gray,
+            profile_active: Color::White,
+            badge_bg: Color::Gray,
+            badge_fg: Color::Black,
+
+            shell_cursor_bg: Color::White,
+            shell_cursor_fg: Color::Black,
+            shell_output_bg: Color::Black,
+            shell_label: Color::White,
+
+            highlight_primary_bg: Color::White,
+            highlight_primary_fg: Color::Black,
+
+            no_color: false,
+        }
+    }
+
+    /// Bright colours with a solid selection bar.
+    pub fn high_contrast() -> Self {
+        Self {
+            panel_active_border: Color::LightYellow,
+            panel_inactive_border: Color::Gray,
+            directory_icon: Color::LightCyan,
+            file_name: Color::White,
+            marked_entry: Color::LightMagenta,
+            mark_indicator: Color::LightMagenta,
+            size_text: Color::White,
+            date_text: Color::Gray,
+            permission_text: Color::Gray,
+            highlight_bg: Color::LightYellow,
+            highlight_fg: Color::Black,
+            highlight_symbol: HIGHLIGHT_SYMBOL,
+
+            hint_badge_bg: Color::White,
+            hint_badge_fg: Color::Black,
+            hint_badge_danger_bg: Color::LightRed,
+            hint_label: Color::White,
+            status_message: Color::LightYellow,
+            hint_bar_bg: Color::Black,
+            transfer_filled_fg: Color::Black,
+            transfer_empty_bg: Color::Gray,
+            upload_bar: Color::LightGreen,
+            download_bar: Color::LightCyan,
+            transfer_row_bg: Color::Black,
+            filename_text: Color::White,
+
+            dialog_active_border: Color::LightYellow,
+            dialog_inactive_border: Color::Gray,
+            dialog_warning_border: Color::LightYellow,
+            dialog_error_border: Color::LightRed,
+            dialog_success_border: Color::LightGreen,
+
+            text_primary: Color::White,
+            text_secondary: Color::White,
+            text_muted: Color::Gray,
+            text_active: Color::White,
+            text_inactive: Color::Gray,
+            cursor_bg: Color::LightYellow,
+            cursor_fg: Color::Black,
+            toggle_on: Color::LightGreen,
+            toggle_off: Color::Gray,
+            text_danger: Color::LightRed,
+            text_success: Color::LightGreen,
+            text_warning: Color::LightYellow,
+            text_info: Color::LightCyan,
+            profile_active: Color::LightGreen,
+            badge_bg: Color::White,
+            badge_fg: Color::Black,
+
+            shell_cursor_bg: Color::White,
+            shell_cursor_fg: Color::Black,
+            shell_output_bg: Color::Black,
+            shell_label: Color::LightYellow,
+
+            highlight_primary_bg: Color::LightYellow,
+            highlight_primary_fg: Color::Black,
+
+            no_color: false,
+        }
+    }
+
+    /// Terminal default colours only (`--no-color`, `NO_COLOR`, `no_color = true`).
+    /// Cues that are normally colour-only fall back to bold, reverse video or
+    /// heavier borders — see `fill()` and the panel border.
+    pub fn no_color() -> Self {
+        Self {
+            panel_active_border: Color::Reset,
+            panel_inactive_border: Color::Reset,
+            directory_icon: Color::Reset,
+            file_name: Color::Reset,
+            marked_entry: Color::Reset,
+            mark_indicator: Color::Reset,
+            size_text: Color::Reset,
+            date_text: Color::Reset,
+            permission_text: Color::Reset,
+            highlight_bg: Color::Reset,
+            highlight_fg: Color::Reset,
+            highlight_symbol: HIGHLIGHT_SYMBOL,
+
+            hint_badge_bg: Color::Reset,
+            hint_badge_fg: Color::Reset,
+            hint_badge_danger_bg: Color::Reset,
+            hint_label: Color::Reset,
+            status_message: Color::Reset,
+            hint_bar_bg: Color::Reset,
+            transfer_filled_fg: Color::Reset,
+            transfer_empty_bg: Color::Reset,
+            upload_bar: Color::Reset,
+            download_bar: Color::Reset,
+            transfer_row_bg: Color::Reset,
+            filename_text: Color::Reset,
+
+            dialog_active_border: Color::Reset,
+            dialog_inactive_border: Color::Reset,
+            dialog_warning_border: Color::Reset,
+            dialog_error_border: Color::Reset,
+            dialog_success_border: Color::Reset,
+
+            text_primary: Color::Reset,
+            text_secondary: Color::Reset,
+            text_muted: Color::Reset,
+            text_active: Color::Reset,
+            text_inactive: Color::Reset,
+            cursor_bg: Color::Reset,
+            cursor_fg: Color::Reset,
+            toggle_on: Color::Reset,
+            toggle_off: Color::Reset,
+            text_danger: Color::Reset,
+            text_success: Color::Reset,
+            text_warning: Color::Reset,
+            text_info: Color::Reset,
+            profile_active: Color::Reset,
+            badge_bg: Color::Reset,
+            badge_fg: Color::Reset,
+
+            shell_cursor_bg: Color::Reset,
+            shell_cursor_fg: Color::Reset,
+            shell_output_bg: Color::Reset,
+            shell_label: Color::Reset,
+
+            highlight_primary_bg: Color::Reset,
+            highlight_primary_fg: Color::Reset,
+
+            no_color: true,
+        }
+    }
+
+    /// Style for filled elements (selection bar, input cursor, key badges,
+    /// progress fill): `bg`/`fg` normally, reverse video in no-color mode.
+    pub fn fill(&self, bg: Color, fg: Color) -> Style {
+        if self.no_color {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(bg).fg(fg)
         }
     }
 
@@ -443,6 +667,8 @@ impl ThemeToml {
             shell_label: parse_color(&self.shell_label)?,
             highlight_primary_bg: parse_color(&self.highlight_primary_bg)?,
             highlight_primary_fg: parse_color(&self.highlight_primary_fg)?,
+
+            no_color: false,
         })
     }
 }
@@ -501,15 +727,6 @@ fn color_name(c: Color) -> String {
 // Persistence: settings.toml + theme files
 // ---------------------------------------------------------------------------
 
-fn config_dir() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    PathBuf::from(home).join(".config").join("vela")
-}
-
-fn settings_path() -> PathBuf {
-    config_dir().join("settings.toml")
-}
-
 fn themes_dir() -> PathBuf {
     config_dir().join("themes")
 }
@@ -527,7 +744,8 @@ pub fn custom_theme_names() -> Vec<String> {
             if path.extension().and_then(|e| e.to_str()) == Some("toml") {
                 if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
                     let lower = stem.to_lowercase();
-                    if lower != "dark" && lower != "light" {
+                    let builtin = ["dark", "light", "monochrome", "high-contrast", "high_contrast"];
+                    if !builtin.contains(&lower.as_str()) {
                         names.push(stem.to_string());
                     }
                 }
@@ -538,35 +756,6 @@ pub fn custom_theme_names() -> Vec<String> {
     names
 }
 
-pub fn load_theme_choice() -> ThemeChoice {
-    let path = settings_path();
-    if !path.exists() {
-        return ThemeChoice::Auto;
-    }
-    let content = match fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => return ThemeChoice::Auto,
-    };
-    // Simple TOML parse: theme = "value"
-    for line in content.lines() {
-        let line = line.trim();
-        if let Some(val) = line.strip_prefix("theme = ") {
-            let v = val.trim().trim_matches('"').trim_matches('\'');
-            return ThemeChoice::from_str(v);
-        }
-    }
-    ThemeChoice::Auto
-}
-
-pub fn save_theme_choice(choice: &ThemeChoice) {
-    let content = format!("theme = \"{}\"\n", choice.ser_name());
-    let path = settings_path();
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    let _ = fs::write(&path, content);
-}
-
 /// Ensure the theme template files exist in ~/.config/vela/themes/.
 /// Called once on startup. Does not overwrite existing files.
 pub fn ensure_themes() {